@@ -0,0 +1,8 @@
+//! Run with `cargo bench --features bench`.
+
+use criterion::{criterion_group, criterion_main};
+
+use lr_schedulers::bench::{compiled_vs_analytic, dispatch, per_step};
+
+criterion_group!(benches, per_step, compiled_vs_analytic, dispatch);
+criterion_main!(benches);
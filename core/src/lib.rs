@@ -0,0 +1,89 @@
+//! The `Scheduler` trait of [lr-schedulers](https://github.com/yusekiya/lr-schedulers).
+//! 
+//! Crates that accept a learning rate scheduler can depend on this crate alone, without the implementations and optional features
+//! of lr-schedulers. The trait is re-exported as `lr_schedulers::Scheduler`, so both paths name the same trait.
+//! Its surface only changes with a major version of this crate.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use lr_schedulers_core::Scheduler;
+//! 
+//! fn train(scheduler: &mut impl Scheduler, losses: &[f64]) -> Vec<f64> {
+//!     losses
+//!         .iter()
+//!         .map(|&loss| {
+//!             let lr = scheduler.get_lr(loss);
+//!             scheduler.step(loss);
+//!             lr
+//!         })
+//!         .collect()
+//! }
+//! 
+//! struct Halving(f64);
+//! 
+//! impl Scheduler for Halving {
+//!     fn step(&mut self, _loss: f64) {
+//!         self.0 /= 2.0;
+//!     }
+//! 
+//!     fn get_lr(&self, _loss: f64) -> f64 {
+//!         self.0
+//!     }
+//! }
+//! 
+//! assert_eq!(train(&mut Halving(1.0), &[0.3, 0.2, 0.1]), [1.0, 0.5, 0.25]);
+//! ```
+
+/// Common interface of learning rate schedulers.
+/// 
+/// Implementations should not panic in `step`, `get_lr` or `get_last_lr`, whatever the loss.
+pub trait Scheduler {
+    /// Proceeds the step of scheduler.
+    fn step(&mut self, loss: f64);
+    /// Returns a learning rate for the current step.
+    fn get_lr(&self, loss: f64) -> f64; // The argument `loss` is for schedulers such as ReduceLROnPlateau.
+    /// Returns the learning rate used for the most recent step, i.e. the value `get_lr` returned before the last call of `step`.
+    /// 
+    /// Use this instead of `get_lr` to report the learning rate of a finished step, e.g. in logs written after `step`.
+    /// For a scheduler constructed with `init_step > 0`, the most recent step is `init_step - 1`.
+    /// Returns None if there is no such step, or if the scheduler does not track it.
+    fn get_last_lr(&self) -> Option<f64> {
+        None
+    }
+    /// Returns the number of steps until a finite schedule reaches its final learning rate, e.g. for ETA calculations.
+    /// 
+    /// Returns None if the schedule has no end, which is the default.
+    fn remaining_steps(&self) -> Option<usize> {
+        None
+    }
+    /// Returns the completed fraction of a finite schedule in `[0, 1]`, e.g. for progress bars.
+    /// 
+    /// Returns None if the schedule has no end, which is the default.
+    fn progress(&self) -> Option<f64> {
+        None
+    }
+}
+
+impl<S: Scheduler + ?Sized> Scheduler for Box<S> {
+    fn step(&mut self, loss: f64) {
+        (**self).step(loss)
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        (**self).get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        (**self).get_last_lr()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        (**self).remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        (**self).progress()
+    }
+}
+
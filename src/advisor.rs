@@ -0,0 +1,130 @@
+use crate::config::SchedulerConfig;
+use crate::semantics::Semantics;
+
+/// Budgets below this number of steps are considered very short.
+pub const SHORT_BUDGET: usize = 1_000;
+/// Budgets of at least this number of steps are considered long.
+pub const LONG_BUDGET: usize = 100_000;
+
+/// Kind of training a schedule is suggested for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskKind {
+    /// Training a model from scratch.
+    Pretraining,
+    /// Adapting a pretrained model.
+    FineTuning,
+}
+
+/// A suggested scheduler configuration with the reason for the choice.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    /// Suggested configuration.
+    pub config: SchedulerConfig,
+    /// Heuristic the suggestion is based on.
+    pub rationale: &'static str,
+}
+
+/// Suggests a schedule for a training budget of `budget_steps` steps, based on published heuristics.
+/// 
+/// The rules are applied in the following order:
+/// 
+/// 1. If training may be `resumable` beyond the budget, CosineAnnealingWarmRestarts with doubling periods.
+/// 2. For fine-tuning, a linear decay to 0.
+/// 3. For budgets shorter than `SHORT_BUDGET`, a linear decay to 0. REX, which is reported to do better for such budgets,
+///    is not a scheduler of this crate, so the linear decay is suggested as the closest schedule.
+/// 4. For budgets of at least `LONG_BUDGET`, a step decay by 10 at 1/3 and 2/3 of the budget.
+/// 5. Otherwise, a cosine decay to 0 that holds 0 after the budget.
+/// 
+/// The suggestion is a starting point to be tuned, not a guarantee of the best schedule.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::advisor::{suggest, TaskKind};
+/// # use lr_schedulers::config::SchedulerConfig;
+/// let suggestion = suggest(0.1, 10_000, TaskKind::Pretraining, false);
+/// assert_eq!(
+///     suggestion.config,
+///     SchedulerConfig::CosineAnnealing { eta_0: 0.1, eta_1: 0.0, t_max: 10_000, decay_once: true, min_fraction: None }
+/// );
+/// println!("{}", suggestion.rationale);
+/// ```
+pub fn suggest(base_lr: f64, budget_steps: usize, task_kind: TaskKind, resumable: bool) -> Suggestion {
+    let budget_steps = budget_steps.max(1);
+    if resumable {
+        // Periods of t_0, 2*t_0 and 4*t_0 steps fill the budget.
+        return Suggestion {
+            config: SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: base_lr, eta_1: 0.0, t_0: (budget_steps / 7).max(1), t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native },
+            rationale: "Warm restarts reach a low learning rate at the end of every period, \
+                so training can be stopped or extended at period boundaries (Loshchilov & Hutter, 2017).",
+        };
+    }
+    if task_kind == TaskKind::FineTuning {
+        return Suggestion {
+            config: linear_decay(base_lr, budget_steps),
+            rationale: "A linear decay to zero is the standard schedule for fine-tuning pretrained models (Devlin et al., 2019).",
+        };
+    }
+    if budget_steps < SHORT_BUDGET {
+        return Suggestion {
+            config: linear_decay(base_lr, budget_steps),
+            rationale: "For very short budgets, decays that stay high longer than cosine perform better (Chen et al., REX, 2022). \
+                A linear decay to zero is the closest of them available here.",
+        };
+    }
+    if budget_steps >= LONG_BUDGET {
+        return Suggestion {
+            config: SchedulerConfig::Exponential { base_lr, gamma: 0.1, decay_steps: budget_steps.div_ceil(3), staircase: true },
+            rationale: "For long budgets, a step decay by 10 at fixed fractions of the budget is the schedule \
+                of the long training runs on ImageNet (Goyal et al., 2017).",
+        };
+    }
+    Suggestion {
+        config: SchedulerConfig::CosineAnnealing { eta_0: base_lr, eta_1: 0.0, t_max: budget_steps, decay_once: true, min_fraction: None },
+        rationale: "A cosine decay to zero over the whole budget is a robust default (Loshchilov & Hutter, 2017).",
+    }
+}
+
+fn linear_decay(base_lr: f64, total_iters: usize) -> SchedulerConfig {
+    SchedulerConfig::Linear { base_lr, start_factor: 1.0, end_factor: 0.0, total_iters }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_rules() {
+        assert_eq!(suggest(1.0, 100_000, TaskKind::FineTuning, false).config, linear_decay(1.0, 100_000));
+        assert_eq!(
+            suggest(1.0, 700, TaskKind::FineTuning, true).config,
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.0, t_0: 100, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native }
+        );
+    }
+
+    #[test]
+    fn very_short_budget() {
+        assert_eq!(suggest(1.0, 0, TaskKind::Pretraining, false).config, linear_decay(1.0, 1));
+        assert_eq!(suggest(1.0, SHORT_BUDGET - 1, TaskKind::Pretraining, false).config, linear_decay(1.0, SHORT_BUDGET - 1));
+    }
+
+    #[test]
+    fn medium_budget() {
+        for budget in [SHORT_BUDGET, LONG_BUDGET - 1] {
+            let scheduler = suggest(1.0, budget, TaskKind::Pretraining, false).config.build(0);
+            let expected_lrs = [(0, 1.0), (budget / 2, 0.5), (budget, 0.0), (2 * budget, 0.0)];
+            for (step, exp_lr) in expected_lrs {
+                assert!((scheduler.lr_at(step) - exp_lr).abs() < 1e-3, "Budget {}: Step {}", budget, step);
+            }
+        }
+    }
+
+    #[test]
+    fn long_budget() {
+        let scheduler = suggest(1.0, 300_000, TaskKind::Pretraining, false).config.build(0);
+        let expected_lrs = [(0, 1.0), (99_999, 1.0), (100_000, 0.1), (199_999, 0.1), (200_000, 0.01), (299_999, 0.01)];
+        for (step, exp_lr) in expected_lrs {
+            assert!((scheduler.lr_at(step) - exp_lr).abs() < 1e-12, "Step {}", step);
+        }
+    }
+}
\ No newline at end of file
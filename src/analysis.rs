@@ -0,0 +1,483 @@
+use std::fmt;
+
+use crate::config::SchedulerConfig;
+use crate::Scheduler;
+
+/// Returns the learning rates of the first `steps` steps of `scheduler` without modifying it.
+/// 
+/// The loss passed to the scheduler is 0.0, so metric-driven schedulers do not see any improvement.
+pub fn trajectory<S: Scheduler + Clone>(scheduler: &S, steps: usize) -> Vec<f64> {
+    let mut scheduler = scheduler.clone();
+    let mut lrs = Vec::with_capacity(steps);
+    for _ in 0 .. steps {
+        lrs.push(scheduler.get_lr(0.0));
+        scheduler.step(0.0);
+    }
+    lrs
+}
+
+/// Differences between two learning rate trajectories.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduleDiff {
+    /// Number of steps compared.
+    pub steps: usize,
+    /// Largest absolute difference.
+    pub max_abs: f64,
+    /// Mean absolute difference.
+    pub mean_abs: f64,
+    /// Largest relative difference, `|a - b| / max(|a|, |b|)`.
+    pub max_rel: f64,
+    /// Mean relative difference.
+    pub mean_rel: f64,
+    /// First step whose absolute difference exceeds the tolerance.
+    pub first_exceeding: Option<usize>,
+}
+
+impl ScheduleDiff {
+    /// Returns true if no step exceeds the tolerance.
+    pub fn is_within_tolerance(&self) -> bool {
+        self.first_exceeding.is_none()
+    }
+}
+
+/// Compares the first `steps` learning rates of two schedulers without modifying them.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::analysis::compare;
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::linear::LinearLR;
+/// let a = ExponentialLR::new(1.0, 0.5, 0);
+/// let b = LinearLR::new(1.0, 1.0, 0.5, 2, 0);
+/// let diff = compare(&a, &b, 3, 0.1);
+/// // Trajectories: [1.0, 0.5, 0.25] and [1.0, 0.75, 0.5].
+/// assert_eq!(diff.max_abs, 0.25);
+/// assert_eq!(diff.first_exceeding, Some(1));
+/// ```
+pub fn compare<A, B>(a: &A, b: &B, steps: usize, tolerance: f64) -> ScheduleDiff
+where
+    A: Scheduler + Clone,
+    B: Scheduler + Clone,
+{
+    compare_trajectories(&trajectory(a, steps), &trajectory(b, steps), tolerance)
+}
+
+/// Compares two learning rate trajectories, e.g. a scheduler against values exported from another framework.
+/// 
+/// Only the common prefix of the two slices is compared.
+pub fn compare_trajectories(a: &[f64], b: &[f64], tolerance: f64) -> ScheduleDiff {
+    let mut diff = ScheduleDiff {
+        steps: a.len().min(b.len()),
+        max_abs: 0.0,
+        mean_abs: 0.0,
+        max_rel: 0.0,
+        mean_rel: 0.0,
+        first_exceeding: None,
+    };
+    for (i, (x, y)) in a.iter().zip(b).enumerate() {
+        let abs = (x - y).abs();
+        let scale = x.abs().max(y.abs());
+        let rel = if scale > 0.0 { abs / scale } else { 0.0 };
+        diff.max_abs = diff.max_abs.max(abs);
+        diff.max_rel = diff.max_rel.max(rel);
+        diff.mean_abs += abs;
+        diff.mean_rel += rel;
+        if diff.first_exceeding.is_none() && (abs > tolerance || abs.is_nan()) {
+            diff.first_exceeding = Some(i);
+        }
+    }
+    if diff.steps > 0 {
+        diff.mean_abs /= diff.steps as f64;
+        diff.mean_rel /= diff.steps as f64;
+    }
+    diff
+}
+
+/// Direction of the learning rate within a [`Phase`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseKind {
+    /// The learning rate stays the same.
+    Constant,
+    /// The learning rate increases at every step, e.g. a warmup.
+    Increasing,
+    /// The learning rate decreases at every step, e.g. a decay.
+    Decreasing,
+}
+
+/// Maximal range of steps in which the learning rate changes in one direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Phase {
+    /// Direction of the learning rate.
+    pub kind: PhaseKind,
+    /// First step of the phase.
+    pub start: usize,
+    /// Last step of the phase, which is also the first step of the next phase.
+    pub end: usize,
+}
+
+/// Summary of a learning rate trajectory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleStats {
+    /// Number of steps summarized.
+    pub steps: usize,
+    /// Sum of learning rates over the steps, i.e. the total learning budget, accumulated by [`compensated_sum`].
+    pub integral: f64,
+    /// Smallest learning rate.
+    pub min_lr: f64,
+    /// Largest learning rate.
+    pub max_lr: f64,
+    /// Phases in order of steps. Empty if fewer than two steps are summarized.
+    pub phases: Vec<Phase>,
+    sorted_lrs: Vec<f64>,
+}
+
+impl ScheduleStats {
+    /// Returns the number of steps whose learning rate is larger than `threshold`.
+    pub fn steps_above(&self, threshold: f64) -> usize {
+        self.sorted_lrs.len() - self.sorted_lrs.partition_point(|&lr| lr <= threshold)
+    }
+
+    /// Returns the steps at which the direction of the learning rate changes.
+    pub fn phase_boundaries(&self) -> Vec<usize> {
+        self.phases.iter().skip(1).map(|phase| phase.start).collect()
+    }
+}
+
+/// Summarizes the first `steps` learning rates of `scheduler` without modifying it.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::analysis::{summarize, PhaseKind};
+/// # use lr_schedulers::linear::LinearLR;
+/// // Trajectory: [0.0, 0.5, 1.0, 1.0].
+/// let stats = summarize(&LinearLR::new(1.0, 0.0, 1.0, 2, 0), 4);
+/// assert_eq!(stats.integral, 2.5);
+/// assert_eq!(stats.steps_above(0.5), 2);
+/// assert_eq!(stats.phases[0].kind, PhaseKind::Increasing);
+/// assert_eq!(stats.phase_boundaries(), [2]);
+/// ```
+pub fn summarize<S: Scheduler + Clone>(scheduler: &S, steps: usize) -> ScheduleStats {
+    summarize_trajectory(&trajectory(scheduler, steps))
+}
+
+/// Summarizes a learning rate trajectory. NaN learning rates are ignored by `min_lr` and `max_lr`.
+pub fn summarize_trajectory(lrs: &[f64]) -> ScheduleStats {
+    let mut phases: Vec<Phase> = Vec::new();
+    for (i, pair) in lrs.windows(2).enumerate() {
+        let kind = if pair[1] > pair[0] {
+            PhaseKind::Increasing
+        } else if pair[1] < pair[0] {
+            PhaseKind::Decreasing
+        } else {
+            PhaseKind::Constant
+        };
+        match phases.last_mut() {
+            Some(phase) if phase.kind == kind => phase.end = i + 1,
+            _ => phases.push(Phase { kind, start: i, end: i + 1 }),
+        }
+    }
+    let mut sorted_lrs = lrs.to_vec();
+    sorted_lrs.sort_by(f64::total_cmp);
+    ScheduleStats {
+        steps: lrs.len(),
+        integral: compensated_sum(lrs.iter().copied()),
+        min_lr: lrs.iter().copied().fold(f64::NAN, f64::min),
+        max_lr: lrs.iter().copied().fold(f64::NAN, f64::max),
+        phases,
+        sorted_lrs,
+    }
+}
+
+/// Returns the sum of `values` by compensated (Kahan-Babuska-Neumaier) summation.
+/// 
+/// Learning rates themselves do not drift over long trainings, since schedulers evaluate them from the step count,
+/// and the incrementally updated ones are periodically recomputed from it, e.g. [`CosineAnnealingLR`](crate::cosine_annealing::CosineAnnealingLR)
+/// stays within `1e-13` of its closed form over 10M steps. Sums over the steps, such as the learning budget, do accumulate:
+/// the error of a naive sum of n values is bounded by `(n - 1) * ε * Σ|x_i|` with `ε = 2^-53`, which becomes visible after millions of steps.
+/// The error of the compensated sum is bounded by `ε * |Σ x_i| + O(n ε²) * Σ|x_i|`,
+/// i.e. it is within about an ulp of the exact sum for any practical number of steps.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::analysis::compensated_sum;
+/// let values = [1.0, 1e-16, 1e-16, -1.0];
+/// assert_eq!(values.iter().sum::<f64>(), 0.0);
+/// assert_eq!(compensated_sum(values), 2e-16);
+/// ```
+pub fn compensated_sum(values: impl IntoIterator<Item = f64>) -> f64 {
+    let (mut sum, mut compensation) = (0.0f64, 0.0);
+    for value in values {
+        let next = sum + value;
+        compensation += if sum.abs() >= value.abs() { (sum - next) + value } else { (value - next) + sum };
+        sum = next;
+    }
+    sum + compensation
+}
+
+/// Shape of a learning rate trajectory, checked by [`check_shape`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    /// The learning rate never increases.
+    MonotoneDecreasing,
+    /// The learning rate never increases once it has decreased, e.g. a warmup followed by a decay.
+    SingleMode,
+    /// The learning rate repeats every `period` steps. A period of 0 is treated as 1.
+    Periodic { period: usize },
+    /// The learning rate may rise again, e.g. at warm restarts, but never above the previous peak.
+    NonIncreasingEnvelope,
+}
+
+/// A step at which a trajectory does not have the expected [`Shape`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeViolation {
+    /// The expected shape.
+    pub shape: Shape,
+    /// The first step violating the shape.
+    pub step: usize,
+    /// Learning rate at the step.
+    pub lr: f64,
+}
+
+impl fmt::Display for ShapeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the learning rate {} at step {} violates the shape {:?}", self.lr, self.step, self.shape)
+    }
+}
+
+impl std::error::Error for ShapeViolation {}
+
+/// Checks that the first `steps` learning rates of `scheduler` have `shape`, without modifying the scheduler.
+/// 
+/// Differences up to `tolerance` are ignored, e.g. rounding errors of a learning rate that should be constant.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::analysis::{check_shape, Shape};
+/// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+/// let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 4, 1, 0);
+/// assert!(check_shape(&scheduler, Shape::Periodic { period: 5 }, 100, 1e-12).is_ok());
+/// assert!(check_shape(&scheduler, Shape::NonIncreasingEnvelope, 100, 1e-12).is_ok());
+/// let violation = check_shape(&scheduler, Shape::MonotoneDecreasing, 100, 1e-12).unwrap_err();
+/// assert_eq!(violation.step, 5);
+/// ```
+pub fn check_shape<S: Scheduler + Clone>(scheduler: &S, shape: Shape, steps: usize, tolerance: f64) -> Result<(), ShapeViolation> {
+    check_trajectory_shape(&trajectory(scheduler, steps), shape, tolerance)
+}
+
+/// Panics with the violation unless the first `steps` learning rates of `scheduler` have `shape`.
+/// 
+/// This is a shorthand of [`check_shape`] for tests, e.g. to guard hand-written schedules in CI.
+#[track_caller]
+pub fn assert_shape<S: Scheduler + Clone>(scheduler: &S, shape: Shape, steps: usize, tolerance: f64) {
+    if let Err(violation) = check_shape(scheduler, shape, steps, tolerance) {
+        panic!("{}", violation);
+    }
+}
+
+/// Checks that a learning rate trajectory has `shape`, ignoring differences up to `tolerance`.
+/// 
+/// A NaN learning rate violates every shape.
+pub fn check_trajectory_shape(lrs: &[f64], shape: Shape, tolerance: f64) -> Result<(), ShapeViolation> {
+    let violation = |step: usize| Err(ShapeViolation { shape, step, lr: lrs[step] });
+    if let Some(step) = lrs.iter().position(|lr| lr.is_nan()) {
+        return violation(step);
+    }
+    match shape {
+        Shape::MonotoneDecreasing => {
+            let mut min = f64::INFINITY;
+            for (i, &lr) in lrs.iter().enumerate() {
+                if lr > min + tolerance {
+                    return violation(i);
+                }
+                min = min.min(lr);
+            }
+        }
+        Shape::SingleMode => {
+            let (mut max, mut min) = (f64::NEG_INFINITY, f64::INFINITY);
+            let mut decreasing = false;
+            for (i, &lr) in lrs.iter().enumerate() {
+                if decreasing && lr > min + tolerance {
+                    return violation(i);
+                }
+                if !decreasing && lr < max - tolerance {
+                    decreasing = true;
+                }
+                max = max.max(lr);
+                if decreasing {
+                    min = min.min(lr);
+                }
+            }
+        }
+        Shape::Periodic { period } => {
+            let period = period.max(1);
+            for i in period .. lrs.len() {
+                if (lrs[i] - lrs[i - period]).abs() > tolerance {
+                    return violation(i);
+                }
+            }
+        }
+        Shape::NonIncreasingEnvelope => {
+            // The peak is updated where the learning rate starts decreasing after a rise.
+            let mut peak = f64::INFINITY;
+            let mut rising = true;
+            for (i, &lr) in lrs.iter().enumerate() {
+                if lr > peak + tolerance {
+                    return violation(i);
+                }
+                if i == 0 {
+                    continue;
+                }
+                if lr > lrs[i - 1] + tolerance {
+                    rising = true;
+                } else if lr < lrs[i - 1] - tolerance && rising {
+                    peak = lrs[i - 1];
+                    rising = false;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rescales the learning rates of `config` so that the integral of its first `steps` learning rates equals `target_integral`.
+/// 
+/// The shape of the schedule is kept by [`SchedulerConfig::scale_lr`].
+/// Use the `integral` of [`summarize`] on a reference schedule as `target_integral` to compare schedules of different shapes at the same learning budget.
+/// Returns None if the integral of `config` is zero or not finite, since such a schedule cannot be rescaled.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::analysis::rescale_to_budget;
+/// # use lr_schedulers::config::SchedulerConfig;
+/// let config = SchedulerConfig::Linear { base_lr: 1.0, start_factor: 1.0, end_factor: 0.0, total_iters: 4 };
+/// // Integral: 1.0 + 0.75 + 0.5 + 0.25 = 2.5.
+/// let rescaled = rescale_to_budget(&config, 5.0, 4).unwrap();
+/// assert_eq!(rescaled, SchedulerConfig::Linear { base_lr: 2.0, start_factor: 1.0, end_factor: 0.0, total_iters: 4 });
+/// ```
+pub fn rescale_to_budget(config: &SchedulerConfig, target_integral: f64, steps: usize) -> Option<SchedulerConfig> {
+    let integral = summarize(&config.build_any(0), steps).integral;
+    if integral == 0.0 || !integral.is_finite() {
+        return None;
+    }
+    Some(config.scale_lr(target_integral / integral))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosine_annealing::CosineAnnealingLR;
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use crate::semantics::Semantics;
+    use crate::Scheduler;
+    use super::*;
+
+    #[test]
+    fn identical_schedules() {
+        let a = CosineAnnealingLR::new(1.0, 0.0, 10, 0);
+        let diff = compare(&a, &a.clone(), 50, 0.0);
+        assert_eq!(diff.max_abs, 0.0);
+        assert!(diff.is_within_tolerance());
+        // The compared schedulers are not advanced.
+        assert_eq!(a.get_lr(0.0), 1.0);
+    }
+
+    #[test]
+    fn diverging_schedules() {
+        // Both decay identically over the first period, then one restarts while the other rises.
+        let a = CosineAnnealingLR::new(1.0, 0.0, 4, 0);
+        let b = CosineAnnealingWarmRestarts::new(1.0, 0.0, 4, 1, 0);
+        let diff = compare(&a, &b, 6, 1e-10);
+        assert_eq!(diff.first_exceeding, Some(5));
+        assert!((diff.max_abs - (1.0 - (2.0f64.sqrt() - 1.0) / (2.0 * 2.0f64.sqrt()))).abs() < 1e-10);
+    }
+
+    #[test]
+    fn relative_difference() {
+        let diff = compare_trajectories(&[1.0, 0.0, 2.0], &[0.5, 0.0, 2.0, 7.0], 1.0);
+        assert_eq!(diff.steps, 3);
+        assert_eq!(diff.max_rel, 0.5);
+        assert_eq!(diff.mean_rel, 0.5 / 3.0);
+        assert_eq!(diff.first_exceeding, None);
+        let diff = compare_trajectories(&[f64::NAN], &[1.0], 1.0);
+        assert_eq!(diff.first_exceeding, Some(0));
+    }
+
+    #[test]
+    fn check_shapes() {
+        let warmup_decay = [0.1, 0.5, 1.0, 1.0, 0.8, 0.4, 0.4, 0.1];
+        assert!(check_trajectory_shape(&warmup_decay, Shape::SingleMode, 0.0).is_ok());
+        assert!(check_trajectory_shape(&warmup_decay, Shape::NonIncreasingEnvelope, 0.0).is_ok());
+        assert_eq!(check_trajectory_shape(&warmup_decay, Shape::MonotoneDecreasing, 0.0).unwrap_err().step, 1);
+        let restarts = [1.0, 0.5, 0.1, 0.9, 0.4, 0.95, 0.2];
+        assert_eq!(check_trajectory_shape(&restarts, Shape::SingleMode, 0.0).unwrap_err().step, 3);
+        let violation = check_trajectory_shape(&restarts, Shape::NonIncreasingEnvelope, 0.0).unwrap_err();
+        assert_eq!(violation, ShapeViolation { shape: Shape::NonIncreasingEnvelope, step: 5, lr: 0.95 });
+        assert!(check_trajectory_shape(&restarts, Shape::NonIncreasingEnvelope, 0.1).is_ok());
+        assert_eq!(check_trajectory_shape(&[1.0, 0.5, 1.0, 0.4], Shape::Periodic { period: 2 }, 0.05).unwrap_err().step, 3);
+        assert_eq!(check_trajectory_shape(&[1.0, f64::NAN], Shape::Periodic { period: 0 }, 1.0).unwrap_err().step, 1);
+        assert!(check_trajectory_shape(&[], Shape::MonotoneDecreasing, 0.0).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "at step 3 violates the shape MonotoneDecreasing")]
+    fn assert_shape_panics() {
+        assert_shape(&CosineAnnealingLR::new(1.0, 0.0, 2, 0), Shape::MonotoneDecreasing, 10, 1e-12);
+    }
+
+    #[test]
+    fn warm_restart_phases() {
+        // Trajectory: [1.0, 0.5, 0.0, 1.0, 0.5, 0.0].
+        let stats = summarize(&CosineAnnealingWarmRestarts::new(1.0, 0.0, 2, 1, 0), 6);
+        assert!((stats.integral - 3.0).abs() < 1e-12);
+        assert_eq!(stats.min_lr, 0.0);
+        assert_eq!(stats.max_lr, 1.0);
+        assert_eq!(stats.steps_above(0.9), 2);
+        assert_eq!(stats.steps_above(-1.0), 6);
+        let kinds: Vec<_> = stats.phases.iter().map(|phase| phase.kind).collect();
+        assert_eq!(kinds, [PhaseKind::Decreasing, PhaseKind::Increasing, PhaseKind::Decreasing]);
+        assert_eq!(stats.phase_boundaries(), [2, 3]);
+        assert_eq!(stats.phases.last().unwrap().end, 5);
+    }
+
+    #[test]
+    fn match_reference_budget() {
+        let reference = summarize(&CosineAnnealingLR::new(0.1, 0.0, 100, 0), 100).integral;
+        let configs = [
+            SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.97, decay_steps: 1, staircase: false },
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.1, t_0: 10, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native },
+        ];
+        for config in configs {
+            let rescaled = rescale_to_budget(&config, reference, 100).unwrap();
+            let integral = summarize(&rescaled.build_any(0), 100).integral;
+            assert!((integral - reference).abs() < 1e-12, "{:?}: {} != {}", config, integral, reference);
+        }
+        let zero = SchedulerConfig::Constant { base_lr: 0.0, factor: 1.0, total_iters: 0 };
+        assert_eq!(rescale_to_budget(&zero, reference, 100), None);
+    }
+
+    #[test]
+    fn compensated_long_sum() {
+        // Values in [0.5, 1) are multiples of 2^-53, so the reference is exact in fixed point.
+        let values = (0 .. 1_000_000u64).map(|i| 0.5 + 0.5 * (i as f64 * 0.618_033_988_749_895).fract());
+        let scale = 2.0f64.powi(60);
+        let reference = values.clone().map(|x| (x * scale) as i128).sum::<i128>() as f64 / scale;
+        let error = (compensated_sum(values.clone()) - reference).abs();
+        let naive_error = (values.sum::<f64>() - reference).abs();
+        assert!(error <= f64::EPSILON * reference, "error: {}", error);
+        assert!(naive_error > 1e3 * f64::EPSILON * reference, "naive error: {}", naive_error);
+    }
+
+    #[test]
+    fn short_trajectories() {
+        let stats = summarize_trajectory(&[]);
+        assert_eq!(stats.steps, 0);
+        assert!(stats.min_lr.is_nan());
+        assert!(stats.phases.is_empty());
+        let stats = summarize_trajectory(&[0.1, 0.1]);
+        assert_eq!(stats.phases, [Phase { kind: PhaseKind::Constant, start: 0, end: 1 }]);
+    }
+}
\ No newline at end of file
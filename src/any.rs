@@ -0,0 +1,164 @@
+use crate::constant::ConstantLR;
+use crate::cosine_annealing::CosineAnnealingLR;
+use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+use crate::exponential::ExponentialLR;
+use crate::linear::LinearLR;
+use crate::pbt::Perturbable;
+use crate::{Bounded, ClosedForm, Scheduler};
+
+/// One of the built-in closed-form schedulers, [`ConstantLR`], [`LinearLR`], [`ExponentialLR`], [`CosineAnnealingLR`]
+/// and [`CosineAnnealingWarmRestarts`], dispatched statically.
+/// 
+/// Calls are forwarded with a `match` instead of a vtable, so per-step calls can be inlined in tight loops
+/// and no allocation is needed. Use `Box<dyn Scheduler>` instead to hold other schedulers, e.g.
+/// [`WarmupStableDecay`](crate::wsd::WarmupStableDecay), [`Schedule`](crate::phases::Schedule) or schedulers defined outside this crate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::any::AnyScheduler;
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::Scheduler;
+/// let mut schedulers: Vec<AnyScheduler> = vec![
+///     ExponentialLR::new(1.0, 0.5, 0).into(),
+///     LinearLR::new(1.0, 1.0, 0.5, 1, 0).into(),
+/// ];
+/// for scheduler in schedulers.iter_mut() {
+///     // Note: loss value is not used in these schedulers.
+///     scheduler.step(0.01);
+///     assert_eq!(scheduler.get_lr(0.01), 0.5);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub enum AnyScheduler {
+    /// A [`ConstantLR`] scheduler.
+    Constant(ConstantLR),
+    /// A [`LinearLR`] scheduler.
+    Linear(LinearLR),
+    /// An [`ExponentialLR`] scheduler.
+    Exponential(ExponentialLR),
+    /// A [`CosineAnnealingLR`] scheduler.
+    CosineAnnealing(CosineAnnealingLR),
+    /// A [`CosineAnnealingWarmRestarts`] scheduler.
+    CosineAnnealingWarmRestarts(CosineAnnealingWarmRestarts),
+}
+
+macro_rules! dispatch {
+    ($self:expr, $s:ident => $body:expr) => {
+        match $self {
+            AnyScheduler::Constant($s) => $body,
+            AnyScheduler::Linear($s) => $body,
+            AnyScheduler::Exponential($s) => $body,
+            AnyScheduler::CosineAnnealing($s) => $body,
+            AnyScheduler::CosineAnnealingWarmRestarts($s) => $body,
+        }
+    };
+}
+
+impl Scheduler for AnyScheduler {
+    #[inline]
+    fn step(&mut self, loss: f64) {
+        dispatch!(self, s => s.step(loss))
+    }
+
+    #[inline]
+    fn get_lr(&self, loss: f64) -> f64 {
+        dispatch!(self, s => s.get_lr(loss))
+    }
+
+    #[inline]
+    fn get_last_lr(&self) -> Option<f64> {
+        dispatch!(self, s => s.get_last_lr())
+    }
+
+    #[inline]
+    fn remaining_steps(&self) -> Option<usize> {
+        dispatch!(self, s => s.remaining_steps())
+    }
+
+    #[inline]
+    fn progress(&self) -> Option<f64> {
+        dispatch!(self, s => s.progress())
+    }
+}
+
+impl Perturbable for AnyScheduler {
+    #[inline]
+    fn perturb(&mut self, factor: f64) {
+        dispatch!(self, s => s.perturb(factor))
+    }
+}
+
+impl Bounded for AnyScheduler {
+    #[inline]
+    fn bounds(&self) -> (f64, f64) {
+        dispatch!(self, s => s.bounds())
+    }
+}
+
+impl ClosedForm for AnyScheduler {
+    #[inline]
+    fn lr_at(&self, step: usize) -> f64 {
+        dispatch!(self, s => s.lr_at(step))
+    }
+
+    #[inline]
+    fn lr_at_f64(&self, t: f64) -> f64 {
+        dispatch!(self, s => s.lr_at_f64(t))
+    }
+
+    #[inline]
+    fn horizon(&self) -> Option<usize> {
+        dispatch!(self, s => s.horizon())
+    }
+}
+
+macro_rules! impl_from {
+    ($($variant:ident($ty:ty)),*) => {
+        $(
+            impl From<$ty> for AnyScheduler {
+                fn from(scheduler: $ty) -> Self {
+                    AnyScheduler::$variant(scheduler)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(
+    Constant(ConstantLR),
+    Linear(LinearLR),
+    Exponential(ExponentialLR),
+    CosineAnnealing(CosineAnnealingLR),
+    CosineAnnealingWarmRestarts(CosineAnnealingWarmRestarts)
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::config::SchedulerConfig;
+    use crate::semantics::Semantics;
+    use super::*;
+
+    #[test]
+    fn match_boxed() {
+        let configs = [
+            SchedulerConfig::Constant { base_lr: 1.0, factor: 0.5, total_iters: 3 },
+            SchedulerConfig::Linear { base_lr: 1.0, start_factor: 0.1, end_factor: 1.0, total_iters: 5 },
+            SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.9, decay_steps: 1, staircase: false },
+            SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 4, decay_once: false, min_fraction: None },
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.1, t_0: 2, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native },
+        ];
+        for config in configs {
+            let mut boxed = config.build(1);
+            let mut any = config.build_any(1);
+            for i in 0 .. 10 {
+                assert_eq!(any.get_lr(0.0), boxed.get_lr(0.0), "{:?}: Step {}", config, i);
+                assert_eq!(any.lr_at(i), boxed.lr_at(i), "{:?}: Step {}", config, i);
+                // Proceed a step with dummy loss.
+                any.step(0.0);
+                boxed.step(0.0);
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use crate::pbt::Perturbable;
+use crate::{Bounded, ClosedForm, Scheduler};
+
+/// Binds a schedule of dimensionless factors to a concrete base learning rate.
+/// 
+/// The learning rate is `base_lr` times the value of the wrapped schedule, which is typically constructed with a base learning rate of 1,
+/// e.g. from [`SchedulerConfig::normalized`](crate::config::SchedulerConfig::normalized).
+/// A single schedule definition can then be reused for parameter groups and sweeps over the base learning rate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::applied::Applied;
+/// # use lr_schedulers::config::SchedulerConfig;
+/// # use lr_schedulers::ClosedForm;
+/// let shape = SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 100, decay_once: false, min_fraction: None };
+/// let backbone = Applied::new(shape.build_any(0), 1e-4);
+/// let head = Applied::new(shape.build_any(0), 1e-3);
+/// assert_eq!(backbone.lr_at(100), 1e-5);
+/// assert_eq!(head.lr_at(0), 1e-3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Applied<S> {
+    schedule: S,
+    base_lr: f64,
+}
+
+impl<S: Scheduler> Applied<S> {
+    /// Constructs an Applied instance multiplying the values of `schedule` by `base_lr`.
+    pub fn new(schedule: S, base_lr: f64) -> Self {
+        Applied { schedule, base_lr }
+    }
+
+    /// Returns the base learning rate.
+    pub fn base_lr(&self) -> f64 {
+        self.base_lr
+    }
+
+    /// Changes the base learning rate without resetting the step.
+    pub fn set_base_lr(&mut self, base_lr: f64) {
+        self.base_lr = base_lr;
+    }
+
+    /// Returns the factor of the current step, i.e. the learning rate of the wrapped schedule.
+    pub fn factor(&self, loss: f64) -> f64 {
+        self.schedule.get_lr(loss)
+    }
+
+    /// Returns the wrapped schedule.
+    pub fn inner(&self) -> &S {
+        &self.schedule
+    }
+}
+
+impl<S: Scheduler> Scheduler for Applied<S> {
+    fn step(&mut self, loss: f64) {
+        self.schedule.step(loss);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.base_lr * self.schedule.get_lr(loss)
+    }
+
+    /// Returns the last factor of the wrapped schedule multiplied by the current base learning rate.
+    fn get_last_lr(&self) -> Option<f64> {
+        self.schedule.get_last_lr().map(|factor| self.base_lr * factor)
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.schedule.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.schedule.progress()
+    }
+}
+
+impl<S: ClosedForm> ClosedForm for Applied<S> {
+    fn lr_at(&self, step: usize) -> f64 {
+        self.base_lr * self.schedule.lr_at(step)
+    }
+
+    fn lr_at_f64(&self, t: f64) -> f64 {
+        self.base_lr * self.schedule.lr_at_f64(t)
+    }
+
+    fn horizon(&self) -> Option<usize> {
+        self.schedule.horizon()
+    }
+}
+
+impl<S: Bounded> Bounded for Applied<S> {
+    fn bounds(&self) -> (f64, f64) {
+        let (min, max) = self.schedule.bounds();
+        let (a, b) = (self.base_lr * min, self.base_lr * max);
+        (a.min(b), a.max(b))
+    }
+}
+
+impl<S: Scheduler> Perturbable for Applied<S> {
+    fn perturb(&mut self, factor: f64) {
+        self.base_lr *= factor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::SchedulerConfig;
+    use super::*;
+
+    #[test]
+    fn match_absolute_schedule() {
+        let config = SchedulerConfig::Linear { base_lr: 0.4, start_factor: 0.25, end_factor: 1.0, total_iters: 3 };
+        let (base_lr, factors) = config.normalized().unwrap();
+        assert_eq!(base_lr, 0.4);
+        let mut applied = Applied::new(factors.build_any(0), base_lr);
+        let mut reference = config.build_any(0);
+        for i in 0 .. 6 {
+            assert_eq!(applied.get_lr(0.0), reference.get_lr(0.0), "Step {}", i);
+            applied.step(0.0);
+            reference.step(0.0);
+            assert_eq!(applied.get_last_lr(), reference.get_last_lr(), "Step {}", i);
+        }
+        assert_eq!(applied.bounds(), (0.1, 0.4));
+        applied.set_base_lr(-1.0);
+        assert_eq!(applied.bounds(), (-1.0, -0.25));
+    }
+}
\ No newline at end of file
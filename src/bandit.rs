@@ -0,0 +1,234 @@
+use crate::rng::SchedulerRng;
+use crate::Scheduler;
+
+/// Rule by which [`BanditScheduler`] picks a candidate for the next window.
+/// 
+/// Candidates that have not been tried yet are always picked first, in order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// Picks a random candidate with the given probability, and the candidate of the best mean reward otherwise.
+    EpsilonGreedy(f64),
+    /// Picks the candidate maximizing `mean + c * sqrt(ln(windows) / pulls)` for the given exploration weight `c`.
+    Ucb(f64),
+}
+
+/// A candidate picked by [`BanditScheduler`] for a window of steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Choice {
+    /// Number of steps processed before the window.
+    pub step: usize,
+    /// Index of the picked candidate.
+    pub arm: usize,
+}
+
+/// Selects among candidate schedulers with a multi-armed bandit, e.g. for automatic schedule selection.
+/// 
+/// Training is divided into windows of `window` steps, and a candidate picked by the [`Strategy`] drives the learning rate
+/// during each window. The reward of a window is the decrease of the loss over it, i.e. the last loss passed to `step`
+/// before the window minus the last one in the window, where the first window starts from its first loss.
+/// The candidates are compared by their mean rewards.
+/// A window whose reward is not finite, e.g. due to a NaN loss, does not update the estimates.
+/// 
+/// All candidates are stepped at every step, so each of them stays at the global step whichever is picked.
+/// The choices are recorded, and a run is reproduced by calling `replay` with them before every step,
+/// even when the losses differ.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::bandit::{BanditScheduler, Choice, Strategy};
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::Scheduler;
+/// let candidates = vec![ConstantLR::new(0.1, 1.0, 0, 0), ConstantLR::new(1.0, 1.0, 0, 0)];
+/// let mut scheduler = BanditScheduler::new(candidates, 2, Strategy::EpsilonGreedy(0.0), 42).unwrap();
+/// // The loss decreases faster with the larger learning rate.
+/// for loss in [4.0, 3.9, 3.8, 3.0, 2.9, 2.0] {
+///     scheduler.step(loss);
+/// }
+/// assert_eq!(scheduler.arm(), 1);
+/// assert_eq!(scheduler.get_lr(2.0), 1.0);
+/// assert_eq!(scheduler.choices(), [Choice { step: 0, arm: 0 }, Choice { step: 2, arm: 1 }, Choice { step: 4, arm: 1 }, Choice { step: 6, arm: 1 }]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BanditScheduler<S> {
+    arms: Vec<S>,
+    window: usize,
+    strategy: Strategy,
+    rng: SchedulerRng,
+    step: usize,
+    arm: usize,
+    window_start_loss: Option<f64>,
+    rewards: Vec<(f64, usize)>,
+    choices: Vec<Choice>,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> BanditScheduler<S> {
+    /// Constructs a BanditScheduler instance with windows of `window` steps and the random seed `seed`.
+    /// 
+    /// A window of 0 is treated as 1. Returns None if there is no candidate.
+    pub fn new(arms: Vec<S>, window: usize, strategy: Strategy, seed: u64) -> Option<Self> {
+        if arms.is_empty() {
+            return None;
+        }
+        let rewards = vec![(0.0, 0); arms.len()];
+        Some(BanditScheduler {
+            arms,
+            window: window.max(1),
+            strategy,
+            rng: SchedulerRng::new(seed),
+            step: 0,
+            arm: 0,
+            window_start_loss: None,
+            rewards,
+            choices: vec![Choice { step: 0, arm: 0 }],
+            last_lr: None,
+        })
+    }
+
+    /// Follows the choice of `history` for the current window, if it was recorded at the current step.
+    /// 
+    /// Call this before every `step` to reproduce the choices of another scheduler constructed with the same parameters.
+    pub fn replay(&mut self, history: &[Choice]) {
+        let current = self.choices.len() - 1;
+        if let Some(choice) = history.get(current).filter(|c| c.step == self.step && c.arm < self.arms.len()) {
+            self.arm = choice.arm;
+            self.choices[current] = *choice;
+        }
+    }
+
+    /// Returns the choices made so far, in order, including the one of the current window.
+    pub fn choices(&self) -> &[Choice] {
+        &self.choices
+    }
+
+    /// Returns the index of the candidate driving the current window.
+    pub fn arm(&self) -> usize {
+        self.arm
+    }
+
+    /// Returns the mean reward of each candidate, or None for a candidate without a finished window.
+    pub fn estimates(&self) -> Vec<Option<f64>> {
+        self.rewards.iter().map(|&(sum, pulls)| (pulls > 0).then(|| sum / pulls as f64)).collect()
+    }
+
+    /// Returns the candidates.
+    pub fn arms(&self) -> &[S] {
+        &self.arms
+    }
+
+    fn select(&mut self) -> usize {
+        if let Some(untried) = self.rewards.iter().position(|&(_, pulls)| pulls == 0) {
+            return untried;
+        }
+        let means = self.rewards.iter().map(|&(sum, pulls)| sum / pulls as f64);
+        match self.strategy {
+            Strategy::EpsilonGreedy(epsilon) => {
+                if self.rng.next_f64() < epsilon {
+                    self.rng.next_below(self.arms.len())
+                } else {
+                    argmax(means)
+                }
+            }
+            Strategy::Ucb(c) => {
+                let windows = self.rewards.iter().map(|&(_, pulls)| pulls).sum::<usize>() as f64;
+                argmax(means.zip(&self.rewards).map(|(mean, &(_, pulls))| c.mul_add((windows.ln() / pulls as f64).sqrt(), mean)))
+            }
+        }
+    }
+}
+
+impl<S: Scheduler> Scheduler for BanditScheduler<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        let start = *self.window_start_loss.get_or_insert(loss);
+        for arm in &mut self.arms {
+            arm.step(loss);
+        }
+        self.step = self.step.saturating_add(1);
+        if self.step.is_multiple_of(self.window) {
+            let reward = start - loss;
+            if reward.is_finite() {
+                let (sum, pulls) = &mut self.rewards[self.arm];
+                *sum += reward;
+                *pulls += 1;
+            }
+            self.window_start_loss = Some(loss);
+            self.arm = self.select();
+            self.choices.push(Choice { step: self.step, arm: self.arm });
+        }
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.arms[self.arm].get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the remaining steps of the current arm.
+    fn remaining_steps(&self) -> Option<usize> {
+        self.arms[self.arm].remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.arms[self.arm].progress()
+    }
+}
+
+/// Returns the index of the first largest value, ignoring NaN.
+fn argmax(values: impl Iterator<Item = f64>) -> usize {
+    let mut best = (0, f64::NEG_INFINITY);
+    for (i, value) in values.enumerate() {
+        if value > best.1 {
+            best = (i, value);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant::ConstantLR;
+    use super::*;
+
+    fn candidates() -> Vec<ConstantLR> {
+        [0.1, 0.2, 0.3].iter().map(|&lr| ConstantLR::new(lr, 1.0, 0, 0)).collect()
+    }
+
+    #[test]
+    fn ucb_explores_then_exploits() {
+        let mut scheduler = BanditScheduler::new(candidates(), 1, Strategy::Ucb(0.1), 0).unwrap();
+        let mut loss = 100.0;
+        for _ in 0 .. 30 {
+            // The loss decreases in proportion to the learning rate.
+            loss -= 10.0 * scheduler.get_lr(loss);
+            scheduler.step(loss);
+        }
+        let arms: Vec<_> = scheduler.choices().iter().map(|c| c.arm).collect();
+        assert_eq!(arms[.. 3], [0, 1, 2]);
+        assert!(arms[3 ..].iter().all(|&arm| arm == 2));
+        assert_eq!(scheduler.estimates()[0], Some(0.0));
+        assert_eq!(BanditScheduler::<ConstantLR>::new(Vec::new(), 1, Strategy::Ucb(1.0), 0).map(|s| s.arm()), None);
+    }
+
+    #[test]
+    fn replay_choices() {
+        let mut scheduler = BanditScheduler::new(candidates(), 2, Strategy::EpsilonGreedy(0.5), 7).unwrap();
+        let mut lrs = Vec::new();
+        for i in 0 .. 40 {
+            lrs.push(scheduler.get_lr(0.0));
+            scheduler.step((i % 5) as f64);
+        }
+        // A replay reproduces the learning rates with other losses.
+        let mut replayed = BanditScheduler::new(candidates(), 2, Strategy::EpsilonGreedy(0.5), 0).unwrap();
+        for (i, exp_lr) in lrs.iter().enumerate() {
+            replayed.replay(scheduler.choices());
+            assert_eq!(replayed.get_lr(0.0), *exp_lr, "Step {}", i);
+            replayed.step(f64::NAN);
+        }
+        assert_eq!(replayed.choices()[.. 20], scheduler.choices()[.. 20]);
+        assert_eq!(replayed.estimates(), [None, None, None]);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::config::SchedulerConfig;
+
+/// Evaluates the first `steps` learning rates of every configured scheduler.
+/// 
+/// Schedules are evaluated by random access through [`ClosedForm::lr_at`](crate::ClosedForm::lr_at).
+/// With the `rayon` feature enabled, schedules are evaluated in parallel.
+/// The result is in the same order as `configs`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::batch::evaluate_many;
+/// # use lr_schedulers::config::SchedulerConfig;
+/// let configs: Vec<_> = [0.5, 0.25]
+///     .into_iter()
+///     .map(|gamma| SchedulerConfig::Exponential { base_lr: 1.0, gamma, decay_steps: 1, staircase: false })
+///     .collect();
+/// let lrs = evaluate_many(&configs, 3);
+/// assert_eq!(lrs, [[1.0, 0.5, 0.25], [1.0, 0.25, 0.0625]]);
+/// ```
+pub fn evaluate_many(configs: &[SchedulerConfig], steps: usize) -> Vec<Vec<f64>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        configs.par_iter().map(|config| evaluate(config, steps)).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        configs.iter().map(|config| evaluate(config, steps)).collect()
+    }
+}
+
+fn evaluate(config: &SchedulerConfig, steps: usize) -> Vec<f64> {
+    let scheduler = config.build(0);
+    (0 .. steps).map(|step| scheduler.lr_at(step)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::trajectory;
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn match_stepping() {
+        let configs: Vec<_> = (1 .. 20)
+            .map(|total_iters| SchedulerConfig::Linear {
+                base_lr: 1.0, start_factor: 0.1, end_factor: 1.0, total_iters
+            })
+            .collect();
+        let lrs = evaluate_many(&configs, 30);
+        for (total_iters, lrs) in (1 .. 20).zip(lrs) {
+            let expected = trajectory(&LinearLR::new(1.0, 0.1, 1.0, total_iters, 0), 30);
+            assert_eq!(lrs, expected, "total_iters {}", total_iters);
+        }
+    }
+}
\ No newline at end of file
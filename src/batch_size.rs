@@ -0,0 +1,121 @@
+use crate::value::ValueScheduler;
+use crate::Scheduler;
+
+/// Policy to convert a scheduled value into an integer batch size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Rounds down.
+    Floor,
+    /// Rounds up.
+    Ceil,
+    /// Rounds to the nearest integer, with halves rounded away from zero.
+    Nearest,
+    /// Rounds to the nearest multiple of the given number, e.g. to keep batches divisible by the number of devices.
+    MultipleOf(usize),
+}
+
+impl Rounding {
+    fn apply(self, value: f64) -> usize {
+        let rounded = match self {
+            Rounding::Floor => value.floor(),
+            Rounding::Ceil => value.ceil(),
+            Rounding::Nearest => value.round(),
+            Rounding::MultipleOf(n) => {
+                let n = n.max(1) as f64;
+                (value / n).round() * n
+            }
+        };
+        // `as` saturates, and NaN becomes 0, which is then replaced with the minimum batch size below.
+        rounded as usize
+    }
+}
+
+/// Schedules the batch size with the curve of a learning rate scheduler.
+/// 
+/// The value returned by the wrapped scheduler is interpreted as a batch size and rounded by the given policy.
+/// The batch size is at least 1 (or the multiple for `Rounding::MultipleOf`).
+/// This implements "increase the batch size instead of decaying the learning rate" policies.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::batch_size::{BatchSizeScheduler, Rounding};
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::value::ValueScheduler;
+/// // Grow the batch size from 32 to 128 over 3 steps in multiples of 8.
+/// let mut scheduler = BatchSizeScheduler::new(LinearLR::new(32.0, 1.0, 4.0, 3, 0), Rounding::MultipleOf(8));
+/// let mut batch_sizes = Vec::new();
+/// for _ in 0 .. 5 {
+///     // Note: loss value is not used in this scheduler.
+///     batch_sizes.push(scheduler.get_value(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(batch_sizes, [32, 64, 96, 128, 128]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BatchSizeScheduler<S> {
+    scheduler: S,
+    rounding: Rounding,
+}
+
+impl<S: Scheduler> BatchSizeScheduler<S> {
+    /// Constructs a BatchSizeScheduler instance.
+    pub fn new(scheduler: S, rounding: Rounding) -> Self {
+        BatchSizeScheduler { scheduler, rounding }
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> ValueScheduler<usize> for BatchSizeScheduler<S> {
+    fn step(&mut self, loss: f64) {
+        self.scheduler.step(loss);
+    }
+
+    fn get_value(&self, loss: f64) -> usize {
+        let minimum = match self.rounding {
+            Rounding::MultipleOf(n) => n.max(1),
+            _ => 1,
+        };
+        self.rounding.apply(self.scheduler.get_lr(loss)).max(minimum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exponential::ExponentialLR;
+    use super::*;
+
+    #[test]
+    fn rounding_policies() {
+        let values = [2.5, 7.2, 12.0, 0.3];
+        let cases = [
+            (Rounding::Floor, [2, 7, 12, 1]),
+            (Rounding::Ceil, [3, 8, 12, 1]),
+            (Rounding::Nearest, [3, 7, 12, 1]),
+            (Rounding::MultipleOf(4), [4, 8, 12, 4]),
+        ];
+        for (rounding, expected) in cases {
+            for (value, exp_batch_size) in values.iter().zip(expected) {
+                // A scheduler that stays at `value`.
+                let scheduler = BatchSizeScheduler::new(ExponentialLR::new(*value, 1.0, 0), rounding);
+                let batch_size = scheduler.get_value(0.0);
+                assert_eq!(batch_size, exp_batch_size, "{:?}: {}", rounding, value);
+            }
+        }
+    }
+
+    #[test]
+    fn geometric_growth() {
+        let mut scheduler = BatchSizeScheduler::new(ExponentialLR::new(16.0, 2.0, 0), Rounding::Nearest);
+        let expected_batch_sizes = [16, 32, 64, 128];
+        for (i, exp_batch_size) in expected_batch_sizes.iter().enumerate() {
+            assert_eq!(scheduler.get_value(0.0), *exp_batch_size, "Step {}", i);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+        }
+    }
+}
\ No newline at end of file
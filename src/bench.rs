@@ -0,0 +1,88 @@
+use std::hint::black_box;
+
+use criterion::Criterion;
+
+use crate::any::AnyScheduler;
+use crate::compiled::{CompiledSchedule, Precision};
+use crate::config::SchedulerConfig;
+use crate::semantics::Semantics;
+use crate::Scheduler;
+
+/// Number of steps of the schedules used in the scenarios.
+pub const HORIZON: usize = 10_000;
+
+/// Returns a configuration of each of the built-in closed-form schedulers of [`SchedulerConfig`],
+/// i.e. the schedulers of [`AnyScheduler`], with the given horizon.
+pub fn configs(horizon: usize) -> Vec<SchedulerConfig> {
+    vec![
+        SchedulerConfig::Constant { base_lr: 1.0, factor: 0.1, total_iters: horizon / 2 },
+        SchedulerConfig::Linear { base_lr: 1.0, start_factor: 0.1, end_factor: 1.0, total_iters: horizon },
+        SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.999, decay_steps: 1, staircase: false },
+        SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.0, t_max: horizon, decay_once: false, min_fraction: None },
+        SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.0, t_0: horizon / 10, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native },
+    ]
+}
+
+/// Returns a short name of the scheduler configured by `config`.
+pub fn name(config: &SchedulerConfig) -> &'static str {
+    match config {
+        SchedulerConfig::Constant { .. } => "constant",
+        SchedulerConfig::Linear { .. } => "linear",
+        SchedulerConfig::Exponential { .. } => "exponential",
+        SchedulerConfig::CosineAnnealing { .. } => "cosine_annealing",
+        SchedulerConfig::CosineAnnealingWarmRestarts { .. } => "cosine_annealing_warm_restarts",
+    }
+}
+
+/// Measures one `get_lr` and one `step` of `scheduler`, which is the overhead a training loop pays per step.
+/// 
+/// The scheduler is advanced continuously over the iterations, so long-running costs such as re-synchronization are included.
+pub fn bench_step<S: Scheduler>(c: &mut Criterion, id: &str, mut scheduler: S) {
+    c.bench_function(id, |b| {
+        b.iter(|| {
+            let lr = scheduler.get_lr(black_box(0.0));
+            scheduler.step(black_box(0.0));
+            lr
+        })
+    });
+}
+
+/// Per-step cost of each scheduler of [`configs`].
+pub fn per_step(c: &mut Criterion) {
+    for config in configs(HORIZON) {
+        bench_step(c, &format!("per_step/{}", name(&config)), config.build_any(0));
+    }
+}
+
+/// Per-step cost of each scheduler compared with the same schedule precomputed by [`CompiledSchedule`].
+pub fn compiled_vs_analytic(c: &mut Criterion) {
+    for config in configs(HORIZON) {
+        let analytic = config.build_any(0);
+        let compiled = CompiledSchedule::from_scheduler(&analytic, HORIZON, Precision::F64);
+        bench_step(c, &format!("analytic/{}", name(&config)), analytic);
+        bench_step(c, &format!("compiled/{}", name(&config)), compiled);
+    }
+}
+
+/// Per-step cost of static dispatch through [`AnyScheduler`] compared with dynamic dispatch through `Box<dyn Scheduler>`.
+/// 
+/// All schedulers are stepped in turn, so that the call sites see every implementation.
+pub fn dispatch(c: &mut Criterion) {
+    let configs = configs(HORIZON);
+    let mut any: Vec<AnyScheduler> = configs.iter().map(|config| config.build_any(0)).collect();
+    let mut boxed: Vec<Box<dyn Scheduler>> = configs
+        .iter()
+        .map(|config| config.build(0) as Box<dyn Scheduler>)
+        .collect();
+    c.bench_function("dispatch/enum", |b| b.iter(|| step_all(&mut any)));
+    c.bench_function("dispatch/dyn", |b| b.iter(|| step_all(&mut boxed)));
+}
+
+fn step_all<S: Scheduler>(schedulers: &mut [S]) -> f64 {
+    let mut sum = 0.0;
+    for scheduler in schedulers.iter_mut() {
+        sum += scheduler.get_lr(black_box(0.0));
+        scheduler.step(black_box(0.0));
+    }
+    sum
+}
\ No newline at end of file
@@ -0,0 +1,266 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::any::AnyScheduler;
+use crate::config::SchedulerConfig;
+use crate::history::Record;
+
+/// Version of the checkpoint format written by this crate.
+pub const CURRENT_VERSION: u32 = 1;
+
+/// Upgrades a checkpoint of the version equal to its index plus 1 to the next version.
+type Migration = fn(Value) -> Result<Value, String>;
+
+/// Migrations from each older version, in order. `MIGRATIONS[v - 1]` upgrades version `v` to `v + 1`.
+const MIGRATIONS: [Migration; CURRENT_VERSION as usize - 1] = [];
+
+/// Error in saving or loading a checkpoint.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// The file could not be read or written.
+    Io(io::Error),
+    /// The checkpoint is not valid JSON or does not match the format.
+    Format(serde_json::Error),
+    /// The checkpoint has no version or a version this crate cannot read, such as one written by a newer crate.
+    UnsupportedVersion(Option<u64>),
+    /// The migration from the given version failed.
+    Migration { version: u32, message: String },
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckpointError::Io(e) => write!(f, "cannot access the checkpoint: {}", e),
+            CheckpointError::Format(e) => write!(f, "invalid checkpoint: {}", e),
+            CheckpointError::UnsupportedVersion(Some(version)) => {
+                write!(f, "unsupported checkpoint version {} (supported up to {})", version, CURRENT_VERSION)
+            }
+            CheckpointError::UnsupportedVersion(None) => write!(f, "the checkpoint has no version"),
+            CheckpointError::Migration { version, message } => {
+                write!(f, "cannot migrate the checkpoint from version {}: {}", version, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CheckpointError::Io(e) => Some(e),
+            CheckpointError::Format(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CheckpointError {
+    fn from(e: io::Error) -> Self {
+        CheckpointError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for CheckpointError {
+    fn from(e: serde_json::Error) -> Self {
+        CheckpointError::Format(e)
+    }
+}
+
+/// State of a scheduler saved with the checkpoint of a model, in a versioned JSON format.
+/// 
+/// The state of a scheduler is its configuration and the number of steps processed,
+/// from which the scheduler is restored as `config.build_any(step)`.
+/// Checkpoints written by older versions of the format are upgraded on load, so they can be read after upgrading this crate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::checkpoint::SchedulerCheckpoint;
+/// # use lr_schedulers::config::SchedulerConfig;
+/// # use lr_schedulers::{ClosedForm, Scheduler};
+/// let config = SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.5, decay_steps: 1, staircase: false };
+/// let checkpoint = SchedulerCheckpoint::new(config, 3);
+/// let json = checkpoint.to_json().unwrap();
+/// 
+/// let restored = SchedulerCheckpoint::from_json(&json).unwrap();
+/// assert_eq!(restored, checkpoint);
+/// assert_eq!(restored.restore().get_lr(0.01), 0.125);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerCheckpoint {
+    /// Version of the format, which is `CURRENT_VERSION` for a checkpoint constructed by this crate.
+    pub version: u32,
+    /// Configuration of the scheduler.
+    pub config: SchedulerConfig,
+    /// Number of steps processed, including `init_step`.
+    pub step: usize,
+    /// Learning rates and metrics recorded so far, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<Record>>,
+}
+
+impl SchedulerCheckpoint {
+    /// Constructs a SchedulerCheckpoint instance without history.
+    pub fn new(config: SchedulerConfig, step: usize) -> Self {
+        SchedulerCheckpoint { version: CURRENT_VERSION, config, step, history: None }
+    }
+
+    /// Attaches the records of a history, e.g. `history.records().copied().collect()`.
+    pub fn with_history(self, history: Vec<Record>) -> Self {
+        SchedulerCheckpoint { history: Some(history), ..self }
+    }
+
+    /// Constructs the scheduler at the saved step.
+    pub fn restore(&self) -> AnyScheduler {
+        self.config.build_any(self.step)
+    }
+
+    /// Serializes the checkpoint to JSON.
+    pub fn to_json(&self) -> Result<String, CheckpointError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserializes a checkpoint from JSON, migrating it from an older version if necessary.
+    pub fn from_json(json: &str) -> Result<Self, CheckpointError> {
+        let value = migrate(serde_json::from_str(json)?)?;
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Writes the checkpoint to `path` as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Reads a checkpoint written by `save`, migrating it from an older version if necessary.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CheckpointError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+}
+
+/// Upgrades a checkpoint in any supported version to `CURRENT_VERSION`.
+pub fn migrate(mut value: Value) -> Result<Value, CheckpointError> {
+    let found = value.get("version").and_then(Value::as_u64);
+    let mut version = match found {
+        Some(version) if (1 ..= CURRENT_VERSION as u64).contains(&version) => version as u32,
+        _ => return Err(CheckpointError::UnsupportedVersion(found)),
+    };
+    while version < CURRENT_VERSION {
+        value = MIGRATIONS[version as usize - 1](value)
+            .map_err(|message| CheckpointError::Migration { version, message })?;
+        version += 1;
+        value["version"] = version.into();
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::relative_eq;
+    use crate::cosine_annealing::CosineAnnealingLR;
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use crate::exponential::ExponentialLR;
+    use crate::semantics::Semantics;
+    use crate::{ClosedForm, Scheduler};
+    use super::*;
+
+    #[test]
+    fn round_trip_with_history() {
+        let config = SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.1, t_0: 3, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native };
+        let history = vec![Record { step: 0, metric: 2.5, lr: 1.0 }, Record { step: 1, metric: 2.0, lr: 0.775 }];
+        let checkpoint = SchedulerCheckpoint::new(config.clone(), 2).with_history(history);
+        let path = std::env::temp_dir().join(format!("lr-checkpoint-{}.json", std::process::id()));
+        checkpoint.save(&path).unwrap();
+        let loaded = SchedulerCheckpoint::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, checkpoint);
+        let mut restored = loaded.restore();
+        let mut reference = config.build_any(0);
+        for _ in 0 .. 2 {
+            reference.step(0.0);
+        }
+        for i in 0 .. 10 {
+            assert!(relative_eq!(restored.get_lr(0.0), reference.get_lr(0.0), epsilon = 1e-12), "Step {}", i);
+            restored.step(0.0);
+            reference.step(0.0);
+        }
+    }
+
+    #[test]
+    fn round_trip_options() {
+        let schedulers: [(SchedulerConfig, AnyScheduler); 5] = [
+            (
+                SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 4, decay_once: true, min_fraction: None },
+                CosineAnnealingLR::decay_once(1.0, 0.1, 4, 0).into(),
+            ),
+            (
+                SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.0, t_max: 4, decay_once: false, min_fraction: Some(0.2) },
+                CosineAnnealingLR::new(1.0, 0.0, 4, 0).with_min_fraction(0.2).into(),
+            ),
+            (
+                SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.5, decay_steps: 3, staircase: true },
+                ExponentialLR::new(1.0, 0.5, 0).with_decay_steps(3, true).into(),
+            ),
+            (
+                SchedulerConfig::CosineAnnealingWarmRestarts {
+                    eta_0: 1.0, eta_1: 0.0, t_0: 3, t_mult: 2, steps_per_epoch: 4, semantics: Semantics::Native
+                },
+                CosineAnnealingWarmRestarts::new(1.0, 0.0, 3, 2, 0).with_epoch_alignment(4).into(),
+            ),
+            (
+                SchedulerConfig::CosineAnnealingWarmRestarts {
+                    eta_0: 1.0, eta_1: 0.0, t_0: 3, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::PyTorchParity
+                },
+                CosineAnnealingWarmRestarts::new(1.0, 0.0, 3, 2, 0).with_semantics(Semantics::PyTorchParity).into(),
+            ),
+        ];
+        for (config, mut reference) in schedulers {
+            let checkpoint = SchedulerCheckpoint::new(config.clone(), 7);
+            let loaded = SchedulerCheckpoint::from_json(&checkpoint.to_json().unwrap()).unwrap();
+            assert_eq!(loaded, checkpoint);
+            let mut restored = loaded.restore();
+            for _ in 0 .. 7 {
+                reference.step(0.0);
+            }
+            for i in 0 .. 20 {
+                assert!(relative_eq!(restored.get_lr(0.0), reference.get_lr(0.0), epsilon = 1e-12), "{:?}: Step {}", config, i);
+                restored.step(0.0);
+                reference.step(0.0);
+            }
+        }
+        let restored = SchedulerCheckpoint::new(SchedulerConfig::CosineAnnealing {
+            eta_0: 1.0, eta_1: 0.0, t_max: 4, decay_once: true, min_fraction: Some(0.2)
+        }, 0).restore();
+        assert!(matches!(restored, AnyScheduler::CosineAnnealing(ref s) if s.is_decay_once() && s.min_fraction() == Some(0.2)));
+        // Missing options take their defaults.
+        let json = r#"{"version": 1, "config": {"Exponential": {"base_lr": 1.0, "gamma": 0.5}}, "step": 0}"#;
+        let config = SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.5, decay_steps: 1, staircase: false };
+        assert_eq!(SchedulerCheckpoint::from_json(json).unwrap().config, config);
+    }
+
+    #[test]
+    fn load_frozen_fixtures() {
+        // Checkpoints written by released versions of the format must keep loading.
+        let loaded = SchedulerCheckpoint::from_json(include_str!("../fixtures/checkpoint_v1.json")).unwrap();
+        let config = SchedulerConfig::CosineAnnealingWarmRestarts {
+            eta_0: 0.1, eta_1: 0.001, t_0: 10, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native
+        };
+        assert_eq!(loaded.config, config);
+        assert_eq!(loaded.step, 25);
+        assert_eq!(loaded.history.as_ref().map(Vec::len), Some(2));
+        assert_eq!(loaded.restore().get_lr(0.0), loaded.config.build_any(0).lr_at(25));
+    }
+
+    #[test]
+    fn reject_unsupported_versions() {
+        let json = r#"{"version": 99, "config": {"Exponential": {"base_lr": 1.0, "gamma": 0.5}}, "step": 0}"#;
+        assert!(matches!(SchedulerCheckpoint::from_json(json), Err(CheckpointError::UnsupportedVersion(Some(99)))));
+        let json = r#"{"config": {"Exponential": {"base_lr": 1.0, "gamma": 0.5}}, "step": 0}"#;
+        assert!(matches!(SchedulerCheckpoint::from_json(json), Err(CheckpointError::UnsupportedVersion(None))));
+        assert!(matches!(SchedulerCheckpoint::from_json("{"), Err(CheckpointError::Format(_))));
+    }
+}
\ No newline at end of file
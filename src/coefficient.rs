@@ -0,0 +1,111 @@
+use crate::value::ValueScheduler;
+
+/// Adapts a loss coefficient multiplicatively to keep an observed quantity near a target, as the adaptive KL penalty of PPO.
+/// 
+/// After each observation, the coefficient is multiplied by `factor` if the observation exceeds `target * tolerance`,
+/// and divided by `factor` if it is below `target / tolerance`, then clamped to the bounds.
+/// The defaults are the values of PPO, `tolerance = 1.5` and `factor = 2`, and the coefficient is bounded to `[0, ∞)`.
+/// Observations that are not finite are ignored.
+/// 
+/// As a [`ValueScheduler`], `step` takes the observation, e.g. the KL divergence, in place of the loss.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::coefficient::AdaptiveCoefficient;
+/// # use lr_schedulers::value::ValueScheduler;
+/// let mut beta = AdaptiveCoefficient::new(1.0, 0.01);
+/// for kl in [0.02, 0.012, 0.004] {
+///     beta.step(kl);
+/// }
+/// // Doubled for the large KL, kept for the one near the target, and halved for the small one.
+/// assert_eq!(beta.get_value(0.0), 1.0);
+/// beta.step(0.05);
+/// assert_eq!(beta.coefficient(), 2.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AdaptiveCoefficient {
+    coefficient: f64,
+    target: f64,
+    tolerance: f64,
+    factor: f64,
+    min: f64,
+    max: f64,
+}
+
+impl AdaptiveCoefficient {
+    /// Constructs an AdaptiveCoefficient instance starting from `initial` and keeping observations near `target`.
+    pub fn new(initial: f64, target: f64) -> Self {
+        AdaptiveCoefficient { coefficient: initial, target, tolerance: 1.5, factor: 2.0, min: 0.0, max: f64::INFINITY }
+    }
+
+    /// Changes the band of observations regarded as near the target to `[target / tolerance, target * tolerance]`,
+    /// and the factor by which the coefficient is adjusted outside the band.
+    pub fn with_rule(self, tolerance: f64, factor: f64) -> Self {
+        AdaptiveCoefficient { tolerance, factor, ..self }
+    }
+
+    /// Clamps the coefficient to `[min, max]`. The bounds are swapped if `min > max`.
+    pub fn with_bounds(self, min: f64, max: f64) -> Self {
+        let (min, max) = if min > max { (max, min) } else { (min, max) };
+        AdaptiveCoefficient { min, max, coefficient: self.coefficient.clamp(min, max), ..self }
+    }
+
+    /// Adjusts the coefficient for `observation`.
+    pub fn observe(&mut self, observation: f64) {
+        if !observation.is_finite() {
+            return;
+        }
+        if observation > self.target * self.tolerance {
+            self.coefficient *= self.factor;
+        } else if observation < self.target / self.tolerance {
+            self.coefficient /= self.factor;
+        }
+        self.coefficient = self.coefficient.clamp(self.min, self.max);
+    }
+
+    /// Changes the target from the next observation on.
+    pub fn set_target(&mut self, target: f64) {
+        self.target = target;
+    }
+
+    /// Returns the target.
+    pub fn target(&self) -> f64 {
+        self.target
+    }
+
+    /// Returns the current coefficient.
+    pub fn coefficient(&self) -> f64 {
+        self.coefficient
+    }
+}
+
+impl ValueScheduler<f64> for AdaptiveCoefficient {
+    /// Adjusts the coefficient for the observation passed as `loss`.
+    fn step(&mut self, loss: f64) {
+        self.observe(loss);
+    }
+
+    fn get_value(&self, _loss: f64) -> f64 {
+        self.coefficient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_target() {
+        let mut beta = AdaptiveCoefficient::new(1.0, 1.0).with_rule(2.0, 4.0).with_bounds(8.0, 0.25);
+        let observations = [3.0, 3.0, 1.9, f64::NAN, 0.4, 0.4, 0.4];
+        let expected = [4.0, 8.0, 8.0, 8.0, 2.0, 0.5, 0.25];
+        for (i, (observation, exp)) in observations.iter().zip(expected).enumerate() {
+            beta.step(*observation);
+            assert_eq!(beta.get_value(0.0), exp, "Step {}", i);
+        }
+        beta.set_target(0.1);
+        beta.observe(0.4);
+        assert_eq!(beta.coefficient(), 1.0);
+    }
+}
@@ -0,0 +1,673 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::{progress_at, ClosedForm, Scheduler};
+
+/// Error returned when a combinator requires a finite schedule but the scheduler has no horizon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unbounded;
+
+impl fmt::Display for Unbounded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the scheduler has no finite horizon")
+    }
+}
+
+impl Error for Unbounded {}
+
+/// Plays a finite schedule backwards.
+/// 
+/// The learning rate at step i is `lr_at(horizon - i)` of the wrapped scheduler, and `lr_at(0)` is held after the horizon.
+/// This turns a warmup into a symmetric warmdown, for example.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::combinators::Reversed;
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = Reversed::new(LinearLR::new(1.0, 0.0, 1.0, 4, 0)).unwrap();
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 6 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(learning_rates, [1.0, 0.75, 0.5, 0.25, 0.0, 0.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Reversed<S> {
+    scheduler: S,
+    horizon: usize,
+    step: usize,
+}
+
+impl<S: ClosedForm> Reversed<S> {
+    /// Constructs a Reversed instance.
+    /// 
+    /// Returns an error if `scheduler` has no horizon.
+    pub fn new(scheduler: S) -> Result<Self, Unbounded> {
+        let horizon = scheduler.horizon().ok_or(Unbounded)?;
+        Ok(Reversed { scheduler, horizon, step: 0 })
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: ClosedForm> Scheduler for Reversed<S> {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.horizon().map(|horizon| horizon.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.horizon().map(|horizon| progress_at(self.step, horizon))
+    }
+}
+
+impl<S: ClosedForm> ClosedForm for Reversed<S> {
+    fn lr_at(&self, step: usize) -> f64 {
+        self.scheduler.lr_at(self.horizon.saturating_sub(step))
+    }
+
+    fn horizon(&self) -> Option<usize> {
+        Some(self.horizon)
+    }
+}
+
+/// Replays a schedule every `period` steps.
+/// 
+/// The learning rate at step i is `lr_at(i % period)` of the wrapped scheduler, multiplied by `factor^(i / period)`,
+/// which generalizes warm restarts to arbitrary curves.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::combinators::Repeated;
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::Scheduler;
+/// // Each repetition peaks at half of the previous one.
+/// let mut scheduler = Repeated::new(LinearLR::new(1.0, 1.0, 0.0, 2, 0), 0.5).unwrap();
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 6 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(learning_rates, [1.0, 0.5, 0.5, 0.25, 0.25, 0.125]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Repeated<S> {
+    scheduler: S,
+    period: usize,
+    factor: f64,
+    step: usize,
+}
+
+impl<S: ClosedForm> Repeated<S> {
+    /// Constructs a Repeated instance whose period is the horizon of `scheduler`.
+    /// 
+    /// Returns an error if `scheduler` has no horizon.
+    /// The learning rate at the horizon itself is not played, since the next repetition starts there.
+    pub fn new(scheduler: S, factor: f64) -> Result<Self, Unbounded> {
+        let period = scheduler.horizon().ok_or(Unbounded)?;
+        Ok(Self::with_period(scheduler, period, factor))
+    }
+
+    /// Constructs a Repeated instance with an explicit `period`, which also accepts schedulers without a horizon.
+    /// 
+    /// The parameter `period` must be larger than 0. When 0 is provided, its value is replaced with 1.
+    pub fn with_period(scheduler: S, period: usize, factor: f64) -> Self {
+        Repeated { scheduler, period: period.max(1), factor, step: 0 }
+    }
+
+    /// Returns the number of steps of a repetition.
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: ClosedForm> Scheduler for Repeated<S> {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+}
+
+impl<S: ClosedForm> ClosedForm for Repeated<S> {
+    fn lr_at(&self, step: usize) -> f64 {
+        let repetition = step / self.period;
+        self.scheduler.lr_at(step % self.period) * self.factor.powf(repetition as f64)
+    }
+}
+
+/// Plays two schedules back to back.
+/// 
+/// The first schedule is played until its horizon, and the second one starts from its step 0 there,
+/// so no explicit milestone is needed. Chains are built with [`ClosedForm::then`].
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::cosine_annealing::CosineAnnealingLR;
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::{ClosedForm, Scheduler};
+/// # fn main() -> Result<(), lr_schedulers::combinators::Unbounded> {
+/// let warmup = LinearLR::new(1.0, 0.0, 1.0, 2, 0);
+/// let decay = CosineAnnealingLR::decay_once(1.0, 0.1, 2, 0);
+/// let tail = ConstantLR::new(0.1, 1.0, 0, 0);
+/// let mut scheduler = warmup.then(decay)?.then(tail)?;
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 6 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// for (target, expected) in learning_rates.iter().zip([0.0, 0.5, 1.0, 0.55, 0.1, 0.1]) {
+///     assert!((target - expected).abs() < 1e-12);
+/// }
+/// assert_eq!(scheduler.horizon(), Some(4));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Concat<A, B> {
+    first: A,
+    second: B,
+    switch: usize,
+    step: usize,
+}
+
+impl<A: ClosedForm, B: ClosedForm> Concat<A, B> {
+    /// Constructs a Concat instance.
+    /// 
+    /// Returns an error if `first` has no horizon.
+    pub fn new(first: A, second: B) -> Result<Self, Unbounded> {
+        let switch = first.horizon().ok_or(Unbounded)?;
+        Ok(Concat { first, second, switch, step: 0 })
+    }
+
+    /// Returns the step at which the second schedule starts.
+    pub fn switch_step(&self) -> usize {
+        self.switch
+    }
+}
+
+impl<A: ClosedForm, B: ClosedForm> Scheduler for Concat<A, B> {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.horizon().map(|horizon| horizon.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.horizon().map(|horizon| progress_at(self.step, horizon))
+    }
+}
+
+impl<A: ClosedForm, B: ClosedForm> ClosedForm for Concat<A, B> {
+    fn lr_at(&self, step: usize) -> f64 {
+        match step.checked_sub(self.switch) {
+            Some(step) => self.second.lr_at(step),
+            None => self.first.lr_at(step),
+        }
+    }
+
+    /// Returns the sum of the horizons, or None if the second schedule has no horizon.
+    fn horizon(&self) -> Option<usize> {
+        self.second.horizon().map(|horizon| self.switch + horizon)
+    }
+}
+
+/// Replaces the first steps of a schedule with a linear warmup that ends on the schedule.
+/// 
+/// The learning rate increases linearly from 0 at step 0 to `lr_at(steps)` of the wrapped scheduler at step `steps`,
+/// and follows the wrapped scheduler afterwards. The target of the warmup is the learning rate of the wrapped scheduler
+/// at the handoff, not its initial or peak learning rate, so there is no jump when the warmup ends in the middle of a decay.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::combinators::Warmup;
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = Warmup::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 2);
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 5 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(learning_rates, [0.0, 0.25, 0.5, 0.25, 0.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Warmup<S> {
+    scheduler: S,
+    steps: usize,
+    step: usize,
+}
+
+impl<S: ClosedForm> Warmup<S> {
+    /// Constructs a Warmup instance whose warmup lasts `steps` steps.
+    pub fn new(scheduler: S, steps: usize) -> Self {
+        Warmup { scheduler, steps, step: 0 }
+    }
+
+    /// Returns the learning rate reached at the end of the warmup, i.e. `lr_at(steps)` of the wrapped scheduler.
+    pub fn target_lr(&self) -> f64 {
+        self.scheduler.lr_at(self.steps)
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: ClosedForm> Scheduler for Warmup<S> {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.horizon().map(|horizon| horizon.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.horizon().map(|horizon| progress_at(self.step, horizon))
+    }
+}
+
+impl<S: ClosedForm> ClosedForm for Warmup<S> {
+    fn lr_at(&self, step: usize) -> f64 {
+        if step >= self.steps {
+            self.scheduler.lr_at(step)
+        } else {
+            self.target_lr() * step as f64 / self.steps as f64
+        }
+    }
+
+    /// Returns the horizon of the wrapped scheduler, but not earlier than the end of the warmup.
+    fn horizon(&self) -> Option<usize> {
+        self.scheduler.horizon().map(|horizon| horizon.max(self.steps))
+    }
+}
+
+/// Shape of the weight of the second schedule in [`Blend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Crossfade {
+    /// The weight increases linearly.
+    Linear,
+    /// The weight follows a half cosine, which is flat at both ends of the window.
+    Cosine,
+}
+
+/// Crossfades from one schedule to another over a window of steps.
+/// 
+/// Like [`Concat`], the second schedule starts from its step 0 at `start`, but its learning rate is mixed with that of the first one
+/// with a weight increasing from 0 to 1 over `window` steps. This avoids the jump of a hard switch.
+/// The first schedule is ignored after `start + window`. With `window = 0`, the schedules are switched at `start`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::combinators::{Blend, Crossfade};
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::Scheduler;
+/// let high = ConstantLR::new(1.0, 1.0, 0, 0);
+/// let low = ConstantLR::new(0.2, 1.0, 0, 0);
+/// let mut scheduler = Blend::new(high, low, 1, 4, Crossfade::Linear);
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 7 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// for (target, expected) in learning_rates.iter().zip([1.0, 1.0, 0.8, 0.6, 0.4, 0.2, 0.2]) {
+///     assert!((target - expected).abs() < 1e-12);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Blend<A, B> {
+    first: A,
+    second: B,
+    start: usize,
+    window: usize,
+    crossfade: Crossfade,
+    step: usize,
+}
+
+impl<A: ClosedForm, B: ClosedForm> Blend<A, B> {
+    /// Constructs a Blend instance.
+    pub fn new(first: A, second: B, start: usize, window: usize, crossfade: Crossfade) -> Self {
+        Blend { first, second, start, window, crossfade, step: 0 }
+    }
+
+    /// Returns the weight of the second schedule at `step`, from 0 to 1.
+    pub fn weight(&self, step: usize) -> f64 {
+        let Some(t) = step.checked_sub(self.start) else {
+            return 0.0;
+        };
+        if t >= self.window {
+            return 1.0;
+        }
+        let progress = t as f64 / self.window as f64;
+        match self.crossfade {
+            Crossfade::Linear => progress,
+            Crossfade::Cosine => 0.5 * (1.0 - (std::f64::consts::PI * progress).cos()),
+        }
+    }
+}
+
+impl<A: ClosedForm, B: ClosedForm> Scheduler for Blend<A, B> {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.horizon().map(|horizon| horizon.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.horizon().map(|horizon| progress_at(self.step, horizon))
+    }
+}
+
+impl<A: ClosedForm, B: ClosedForm> ClosedForm for Blend<A, B> {
+    fn lr_at(&self, step: usize) -> f64 {
+        let weight = self.weight(step);
+        if weight == 0.0 {
+            return self.first.lr_at(step);
+        }
+        let second = self.second.lr_at(step - self.start);
+        if weight == 1.0 {
+            return second;
+        }
+        weight.mul_add(second - self.first.lr_at(step), self.first.lr_at(step))
+    }
+
+    /// Returns the horizon of the second schedule offset by `start`, but not earlier than the end of the window.
+    fn horizon(&self) -> Option<usize> {
+        self.second.horizon().map(|horizon| self.start + horizon.max(self.window))
+    }
+}
+
+/// Mixes two schedules by a weight set from outside at every step, e.g. by a bandit or a PID controller.
+/// 
+/// The learning rate is `(1 - mix) * a + mix * b`, where `a` and `b` are the learning rates of the two schedulers,
+/// which are both stepped at every step, so each of them stays a well-defined schedule whatever the controller does.
+/// The mix used at each step is kept in a history, from which the scheduler is restored after a checkpoint.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::combinators::Modulated;
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::Scheduler;
+/// let high = ConstantLR::new(1.0, 1.0, 0, 0);
+/// let low = ConstantLR::new(0.2, 1.0, 0, 0);
+/// let mut scheduler = Modulated::new(high, low, 0.0);
+/// let mut learning_rates = Vec::new();
+/// for mix in [0.0, 0.5, 1.0] {
+///     scheduler.set_mix(mix);
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(learning_rates, [1.0, 0.6, 0.2]);
+/// assert_eq!(scheduler.history(), [0.0, 0.5, 1.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Modulated<A, B> {
+    first: A,
+    second: B,
+    mix: f64,
+    history: Vec<f64>,
+}
+
+impl<A: Scheduler, B: Scheduler> Modulated<A, B> {
+    /// Constructs a Modulated instance with the initial `mix`. See `set_mix`.
+    pub fn new(first: A, second: B, mix: f64) -> Self {
+        Modulated { first, second, mix: clamp_mix(mix), history: Vec::new() }
+    }
+
+    /// Constructs a Modulated instance restored from a checkpoint.
+    /// 
+    /// The schedulers must be at the step following the last step of `history`, and the mix continues from the last one in `history`.
+    pub fn with_history(first: A, second: B, history: Vec<f64>) -> Self {
+        let mix = history.last().copied().unwrap_or(0.0);
+        Modulated { first, second, mix, history }
+    }
+
+    /// Sets the weight of the second schedule from the current step on.
+    /// 
+    /// The weight is clamped to `[0, 1]`, and NaN is treated as 0.
+    pub fn set_mix(&mut self, mix: f64) {
+        self.mix = clamp_mix(mix);
+    }
+
+    /// Returns the weight of the second schedule for the current step.
+    pub fn mix(&self) -> f64 {
+        self.mix
+    }
+
+    /// Returns the mix used at each step taken so far, in order.
+    pub fn history(&self) -> &[f64] {
+        &self.history
+    }
+
+    /// Returns the first scheduler.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the second scheduler.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+}
+
+impl<A: Scheduler, B: Scheduler> Scheduler for Modulated<A, B> {
+    fn step(&mut self, loss: f64) {
+        self.history.push(self.mix);
+        self.first.step(loss);
+        self.second.step(loss);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        mix_lrs(self.first.get_lr(loss), self.second.get_lr(loss), self.mix)
+    }
+
+    /// Returns the mix of the last learning rates of the schedulers with the last mix, or None if either does not track it.
+    fn get_last_lr(&self) -> Option<f64> {
+        let mix = *self.history.last()?;
+        Some(mix_lrs(self.first.get_last_lr()?, self.second.get_last_lr()?, mix))
+    }
+
+    /// Returns the remaining steps of the longer schedule, or None if either is unbounded.
+    fn remaining_steps(&self) -> Option<usize> {
+        Some(self.first.remaining_steps()?.max(self.second.remaining_steps()?))
+    }
+
+    /// Returns the lesser progress of the two schedules, or None if either is unbounded.
+    fn progress(&self) -> Option<f64> {
+        Some(self.first.progress()?.min(self.second.progress()?))
+    }
+}
+
+fn clamp_mix(mix: f64) -> f64 {
+    if mix.is_nan() {
+        0.0
+    } else {
+        mix.clamp(0.0, 1.0)
+    }
+}
+
+/// Returns `(1 - mix) * a + mix * b`, which is exactly `a` or `b` at the ends.
+fn mix_lrs(a: f64, b: f64, mix: f64) -> f64 {
+    if mix == 0.0 {
+        a
+    } else if mix == 1.0 {
+        b
+    } else {
+        mix.mul_add(b - a, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant::ConstantLR;
+    use crate::cosine_annealing::CosineAnnealingLR;
+    use crate::exponential::ExponentialLR;
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn reverse_cosine_decay() {
+        let decay = CosineAnnealingLR::decay_once(1.0, 0.1, 8, 0);
+        let reversed = Reversed::new(decay.clone()).unwrap();
+        for i in 0 ..= 8 {
+            assert_eq!(reversed.lr_at(i), decay.lr_at(8 - i), "Step {}", i);
+        }
+        assert_eq!(reversed.lr_at(20), 1.0);
+        assert_eq!(reversed.horizon(), Some(8));
+    }
+
+    #[test]
+    fn reject_unbounded() {
+        let err = Reversed::new(ExponentialLR::new(1.0, 0.5, 0)).unwrap_err();
+        assert_eq!(err, Unbounded);
+        assert_eq!(err.to_string(), "the scheduler has no finite horizon");
+        assert!(Reversed::new(CosineAnnealingLR::new(1.0, 0.0, 4, 0)).is_err());
+        assert!(Repeated::new(ExponentialLR::new(1.0, 0.5, 0), 1.0).is_err());
+        assert!(ExponentialLR::new(1.0, 0.5, 0).then(ExponentialLR::new(1.0, 0.5, 0)).is_err());
+    }
+
+    #[test]
+    fn concat_unbounded_tail() {
+        let scheduler = LinearLR::new(1.0, 0.5, 1.0, 3, 0).then(ExponentialLR::new(1.0, 0.5, 0)).unwrap();
+        assert_eq!(scheduler.switch_step(), 3);
+        assert_eq!(scheduler.horizon(), None);
+        let expected_lrs = [0.5, 0.5 + 0.5 / 3.0, 0.5 + 1.0 / 3.0, 1.0, 0.5, 0.25];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            assert!((scheduler.lr_at(i) - exp_lr).abs() < 1e-15, "Step {}", i);
+        }
+    }
+
+    #[test]
+    fn repeat_with_period() {
+        let mut scheduler = Repeated::with_period(ExponentialLR::new(1.0, 0.5, 0), 3, 1.0);
+        let expected_lrs = [1.0, 0.5, 0.25, 1.0, 0.5, 0.25, 1.0];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            assert_eq!(scheduler.get_lr(0.0), *exp_lr, "Step {}", i);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.get_last_lr(), Some(1.0));
+        assert_eq!(Repeated::with_period(ExponentialLR::new(1.0, 0.5, 0), 0, 1.0).period(), 1);
+    }
+
+    #[test]
+    fn warmup_ends_on_schedule() {
+        let decay = CosineAnnealingLR::decay_once(1.0, 0.1, 10, 0);
+        let scheduler = Warmup::new(decay.clone(), 4);
+        assert_eq!(scheduler.target_lr(), decay.lr_at(4));
+        assert_eq!(scheduler.lr_at(0), 0.0);
+        assert_eq!(scheduler.lr_at(2), 0.5 * decay.lr_at(4));
+        for i in 4 .. 20 {
+            assert_eq!(scheduler.lr_at(i), decay.lr_at(i), "Step {}", i);
+        }
+        assert_eq!(scheduler.horizon(), Some(10));
+        assert_eq!(Warmup::new(decay, 20).horizon(), Some(20));
+        let unbounded = Warmup::new(ExponentialLR::new(1.0, 0.5, 0), 0);
+        assert_eq!((unbounded.lr_at(0), unbounded.horizon()), (1.0, None));
+    }
+
+    #[test]
+    fn modulated_history_restores() {
+        let first = LinearLR::new(1.0, 1.0, 0.0, 10, 0);
+        let second = ExponentialLR::new(0.5, 0.5, 0);
+        let mut scheduler = Modulated::new(first, second.clone(), 2.0);
+        assert_eq!(scheduler.mix(), 1.0);
+        assert_eq!(scheduler.get_last_lr(), None);
+        for mix in [0.25, f64::NAN, 0.75] {
+            scheduler.set_mix(mix);
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.history(), [0.25, 0.0, 0.75]);
+        let restored = Modulated::with_history(LinearLR::new(1.0, 1.0, 0.0, 10, 3), ExponentialLR::new(0.5, 0.5, 3), scheduler.history().to_vec());
+        assert_eq!(restored.mix(), 0.75);
+        assert_eq!(restored.get_lr(0.0), scheduler.get_lr(0.0));
+        assert_eq!(restored.get_last_lr(), scheduler.get_last_lr());
+        assert!((scheduler.get_last_lr().unwrap() - (0.25 * 0.8 + 0.75 * second.lr_at(2))).abs() < 1e-15);
+    }
+
+    #[test]
+    fn cosine_crossfade() {
+        let first = LinearLR::new(1.0, 1.0, 0.0, 10, 0);
+        let second = ExponentialLR::new(0.5, 0.5, 0);
+        let blend = Blend::new(first.clone(), second.clone(), 2, 2, Crossfade::Cosine);
+        assert_eq!(blend.lr_at(1), first.lr_at(1));
+        assert_eq!(blend.lr_at(2), first.lr_at(2));
+        assert!((blend.lr_at(3) - 0.5 * (first.lr_at(3) + second.lr_at(1))).abs() < 1e-15);
+        assert_eq!(blend.lr_at(4), second.lr_at(2));
+        assert_eq!(blend.horizon(), None);
+        let switch = Blend::new(first, ConstantLR::new(0.1, 1.0, 0, 0), 3, 0, Crossfade::Linear);
+        assert_eq!(switch.lr_at(2), 0.8);
+        assert_eq!(switch.lr_at(3), 0.1);
+        assert_eq!(switch.horizon(), Some(3));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,287 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::analysis::trajectory;
+use crate::combinators::Unbounded;
+use crate::constant::ConstantLR;
+use crate::cosine_annealing::CosineAnnealingLR;
+use crate::exponential::ExponentialLR;
+use crate::linear::LinearLR;
+use crate::phases::Schedule;
+use crate::wsd::WarmupStableDecay;
+use crate::{progress_at, Bounded, ClosedForm, Scheduler};
+
+/// Precision used to store a precomputed trajectory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    /// Stores learning rates as `f64` without loss.
+    F64,
+    /// Stores learning rates as `f32`, halving the memory for long horizons at a relative error of about 6e-8.
+    F32,
+}
+
+/// Largest number of steps precomputed by the conversions of finite schedulers, i.e. 2 GiB of `f64`.
+pub const MAX_LEN: usize = 1 << 28;
+
+/// Error returned when a scheduler cannot be converted into a [`CompiledSchedule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompileError {
+    /// The scheduler has no horizon.
+    Unbounded,
+    /// The schedule has more steps than [`MAX_LEN`], with the number of steps.
+    TooLong(usize),
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompileError::Unbounded => write!(f, "{}", Unbounded),
+            CompileError::TooLong(len) => write!(f, "the schedule of {} steps exceeds the limit of {} steps", len, MAX_LEN),
+        }
+    }
+}
+
+impl Error for CompileError {}
+
+#[derive(Debug, Clone)]
+enum Table {
+    F64(Vec<f64>),
+    F32(Vec<f32>),
+}
+
+/// Serves a precomputed learning rate trajectory with O(1) lookup.
+/// 
+/// The trajectory of the first `horizon` steps is evaluated once at construction,
+/// so `step` and `get_lr` only increment and index. After the horizon, the last learning rate is held.
+/// An empty trajectory (`horizon = 0`) returns 0.0.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::compiled::{CompiledSchedule, Precision};
+/// # use lr_schedulers::cosine_annealing::CosineAnnealingLR;
+/// # use lr_schedulers::{ClosedForm, Scheduler};
+/// let cosine = CosineAnnealingLR::new(1.0, 0.0, 1000, 0);
+/// let mut scheduler = CompiledSchedule::from_closed_form(&cosine, 1000, Precision::F64);
+/// for _ in 0 .. 500 {
+///     // Note: loss value is not used in this scheduler.
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(scheduler.get_lr(0.01), cosine.lr_at(500));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledSchedule {
+    table: Table,
+    bounds: (f64, f64),
+    step: usize,
+}
+
+impl CompiledSchedule {
+    /// Constructs a CompiledSchedule instance from an explicit trajectory.
+    /// 
+    /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
+    pub fn from_lrs(lrs: Vec<f64>, precision: Precision, init_step: usize) -> Self {
+        let table = match precision {
+            Precision::F64 => Table::F64(lrs),
+            Precision::F32 => Table::F32(lrs.into_iter().map(|lr| lr as f32).collect()),
+        };
+        let bounds = match &table {
+            Table::F64(lrs) => min_max(lrs.iter().copied()),
+            Table::F32(lrs) => min_max(lrs.iter().map(|&lr| lr as f64)),
+        };
+        CompiledSchedule { table, bounds: bounds.unwrap_or((0.0, 0.0)), step: init_step }
+    }
+
+    /// Constructs a CompiledSchedule instance by evaluating the first `horizon` steps of `scheduler` at random access.
+    pub fn from_closed_form<S: ClosedForm>(scheduler: &S, horizon: usize, precision: Precision) -> Self {
+        let lrs = (0 .. horizon).map(|step| scheduler.lr_at(step)).collect();
+        Self::from_lrs(lrs, precision, 0)
+    }
+
+    /// Constructs a CompiledSchedule instance by stepping a copy of `scheduler` for `horizon` steps.
+    /// 
+    /// The trajectory starts from the current step of `scheduler`.
+    pub fn from_scheduler<S: Scheduler + Clone>(scheduler: &S, horizon: usize, precision: Precision) -> Self {
+        Self::from_lrs(trajectory(scheduler, horizon), precision, 0)
+    }
+
+    /// Returns the number of precomputed steps, which is also the horizon of [`ClosedForm`].
+    pub fn len(&self) -> usize {
+        match &self.table {
+            Table::F64(lrs) => lrs.len(),
+            Table::F32(lrs) => lrs.len(),
+        }
+    }
+
+    /// Returns true if no step is precomputed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Scheduler for CompiledSchedule {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        Some(self.len().saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        Some(progress_at(self.step, self.len()))
+    }
+}
+
+impl Bounded for CompiledSchedule {
+    /// Returns the bounds of the table, which are computed at construction.
+    fn bounds(&self) -> (f64, f64) {
+        self.bounds
+    }
+}
+
+impl ClosedForm for CompiledSchedule {
+    fn lr_at(&self, step: usize) -> f64 {
+        match &self.table {
+            Table::F64(lrs) => lrs.get(step).or(lrs.last()).copied().unwrap_or(0.0),
+            Table::F32(lrs) => lrs.get(step).or(lrs.last()).map(|&lr| lr as f64).unwrap_or(0.0),
+        }
+    }
+
+    fn horizon(&self) -> Option<usize> {
+        Some(self.len())
+    }
+}
+
+/// Evaluates the first `len` steps of `scheduler` into a CompiledSchedule at `step`.
+fn compile_at<S: ClosedForm>(scheduler: &S, len: usize, step: usize) -> CompiledSchedule {
+    let lrs = (0 .. len).map(|step| scheduler.lr_at(step)).collect();
+    CompiledSchedule::from_lrs(lrs, Precision::F64, step)
+}
+
+macro_rules! impl_try_from_finite {
+    ($($ty:ty),*) => {
+        $(
+            /// Evaluates the finite schedule up to its horizon, keeping the current step.
+            /// 
+            /// Returns an error if the scheduler has no horizon, or if the schedule has more than [`MAX_LEN`] steps.
+            impl TryFrom<$ty> for CompiledSchedule {
+                type Error = CompileError;
+
+                fn try_from(scheduler: $ty) -> Result<Self, CompileError> {
+                    let horizon = scheduler.horizon().ok_or(CompileError::Unbounded)?;
+                    // Includes the learning rate at the horizon, which is held afterwards.
+                    let len = horizon.saturating_add(1);
+                    if len > MAX_LEN {
+                        return Err(CompileError::TooLong(len));
+                    }
+                    Ok(compile_at(&scheduler, len, scheduler.current_step()))
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_finite!(ConstantLR, LinearLR, CosineAnnealingLR, WarmupStableDecay, Schedule);
+
+/// Evaluates the first `horizon` steps of the exponential decay, keeping the current step.
+/// 
+/// ```
+/// # use lr_schedulers::compiled::CompiledSchedule;
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = ExponentialLR::new(1.0, 0.5, 0);
+/// scheduler.step(0.01);
+/// let compiled = CompiledSchedule::from((scheduler, 3));
+/// assert_eq!(compiled.get_lr(0.01), 0.5);
+/// assert_eq!(compiled.get_last_lr(), Some(1.0));
+/// assert_eq!(compiled.len(), 3);
+/// ```
+impl From<(ExponentialLR, usize)> for CompiledSchedule {
+    fn from((scheduler, horizon): (ExponentialLR, usize)) -> Self {
+        compile_at(&scheduler, horizon, scheduler.current_step())
+    }
+}
+
+/// Returns the smallest and the largest of `lrs`, or None if it is empty.
+pub(crate) fn min_max(mut lrs: impl Iterator<Item = f64>) -> Option<(f64, f64)> {
+    let first = lrs.next()?;
+    Some(lrs.fold((first, first), |(min, max), lr| (min.min(lr), max.max(lr))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use crate::exponential::ExponentialLR;
+    use super::*;
+
+    #[test]
+    fn match_analytic() {
+        let analytic = CosineAnnealingWarmRestarts::new(1.0, 0.0, 10, 2, 0);
+        let mut scheduler = CompiledSchedule::from_closed_form(&analytic, 100, Precision::F64);
+        for i in 0 .. 100 {
+            assert_eq!(scheduler.get_lr(0.0), analytic.lr_at(i), "Step {}", i);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn hold_after_horizon() {
+        let mut scheduler = CompiledSchedule::from_scheduler(&ExponentialLR::new(1.0, 0.5, 0), 3, Precision::F32);
+        let expected_lrs = [1.0, 0.5, 0.25, 0.25, 0.25];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            assert_eq!(scheduler.get_lr(0.0), *exp_lr, "Step {}", i);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.len(), 3);
+    }
+
+    #[test]
+    fn quantization_error() {
+        let analytic = ExponentialLR::new(0.1, 0.999, 0);
+        let scheduler = CompiledSchedule::from_closed_form(&analytic, 1000, Precision::F32);
+        for i in 0 .. 1000 {
+            let rel = (scheduler.lr_at(i) - analytic.lr_at(i)).abs() / analytic.lr_at(i);
+            assert!(rel < 1e-7, "Step {}: relative error {}", i, rel);
+        }
+    }
+
+    #[test]
+    fn empty_table() {
+        let scheduler = CompiledSchedule::from_lrs(Vec::new(), Precision::F64, 0);
+        assert_eq!(scheduler.get_lr(0.0), 0.0);
+    }
+
+    #[test]
+    fn convert_keeping_step() {
+        let mut linear = LinearLR::new(1.0, 0.0, 1.0, 4, 1);
+        linear.step(0.0);
+        let mut compiled = CompiledSchedule::try_from(linear.clone()).unwrap();
+        for i in 2 .. 10 {
+            assert_eq!(compiled.get_lr(0.0), linear.get_lr(0.0), "Step {}", i);
+            assert_eq!(compiled.get_last_lr(), linear.get_last_lr(), "Step {}", i);
+            compiled.step(0.0);
+            linear.step(0.0);
+        }
+        let cosine = CosineAnnealingLR::new(1.0, 0.0, 4, 0);
+        assert_eq!(CompiledSchedule::try_from(cosine).unwrap_err(), CompileError::Unbounded);
+        let cosine = CosineAnnealingLR::decay_once(1.0, 0.0, 4, 6);
+        let compiled = CompiledSchedule::try_from(cosine.clone()).unwrap();
+        assert_eq!((compiled.get_lr(0.0), compiled.get_last_lr()), (cosine.get_lr(0.0), cosine.get_last_lr()));
+        let linear = LinearLR::new(1.0, 0.0, 1.0, usize::MAX, 0);
+        assert_eq!(CompiledSchedule::try_from(linear).unwrap_err(), CompileError::TooLong(usize::MAX));
+        assert_eq!(CompiledSchedule::try_from(LinearLR::new(1.0, 0.0, 1.0, MAX_LEN, 0)).unwrap_err(), CompileError::TooLong(MAX_LEN + 1));
+        assert_eq!(compiled.bounds(), (0.0, 1.0));
+        assert_eq!(CompiledSchedule::from_lrs(vec![0.5, 2.0, 1.0], Precision::F32, 0).bounds(), (0.5, 2.0));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,504 @@
+use std::hash::{Hash, Hasher};
+
+use crate::any::AnyScheduler;
+use crate::constant::ConstantLR;
+use crate::cosine_annealing::CosineAnnealingLR;
+use crate::cosine_annealing_warm_restarts::{CosineAnnealingWarmRestarts, MAX_T};
+use crate::exponential::ExponentialLR;
+use crate::linear::LinearLR;
+use crate::semantics::{Semantics, SEMANTICS_VERSION};
+use crate::ClosedForm;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Parameters of a scheduler, independent of its progress.
+/// 
+/// Each variant holds the constructor parameters of the scheduler of the same name except for `init_step`,
+/// followed by the parameters of its builders, so that every configuration of it can be saved and restored,
+/// e.g. by [`checkpoint`](crate::checkpoint). The builder parameters take their defaults when they are missing from serialized data.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SchedulerConfig {
+    /// Parameters of [`ConstantLR`].
+    Constant { base_lr: f64, factor: f64, total_iters: usize },
+    /// Parameters of [`LinearLR`].
+    Linear { base_lr: f64, start_factor: f64, end_factor: f64, total_iters: usize },
+    /// Parameters of [`ExponentialLR`] with the arguments of `with_decay_steps`, which are 1 and false by default.
+    Exponential {
+        base_lr: f64,
+        gamma: f64,
+        #[cfg_attr(feature = "serde", serde(default = "one"))]
+        decay_steps: usize,
+        #[cfg_attr(feature = "serde", serde(default))]
+        staircase: bool,
+    },
+    /// Parameters of [`CosineAnnealingLR`], constructed by `decay_once` if `decay_once` is true,
+    /// with the argument of `with_min_fraction` if any. Both are absent by default.
+    CosineAnnealing {
+        eta_0: f64,
+        eta_1: f64,
+        t_max: usize,
+        #[cfg_attr(feature = "serde", serde(default))]
+        decay_once: bool,
+        #[cfg_attr(feature = "serde", serde(default))]
+        min_fraction: Option<f64>,
+    },
+    /// Parameters of [`CosineAnnealingWarmRestarts`] with the arguments of `with_epoch_alignment` and `with_semantics`,
+    /// which are 1 and `Semantics::Native` by default.
+    CosineAnnealingWarmRestarts {
+        eta_0: f64,
+        eta_1: f64,
+        t_0: usize,
+        t_mult: usize,
+        #[cfg_attr(feature = "serde", serde(default = "one"))]
+        steps_per_epoch: usize,
+        #[cfg_attr(feature = "serde", serde(default))]
+        semantics: Semantics,
+    },
+}
+
+impl SchedulerConfig {
+    /// Constructs the configured scheduler starting from `init_step`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use lr_schedulers::config::SchedulerConfig;
+    /// let config = SchedulerConfig::Exponential { base_lr: 2.0, gamma: 0.5, decay_steps: 1, staircase: false };
+    /// let scheduler = config.build(0);
+    /// assert_eq!(scheduler.lr_at(2), 0.5);
+    /// ```
+    pub fn build(&self, init_step: usize) -> Box<dyn ClosedForm + Send + Sync> {
+        match *self {
+            SchedulerConfig::Constant { base_lr, factor, total_iters } => {
+                Box::new(ConstantLR::new(base_lr, factor, total_iters, init_step))
+            }
+            SchedulerConfig::Linear { base_lr, start_factor, end_factor, total_iters } => {
+                Box::new(LinearLR::new(base_lr, start_factor, end_factor, total_iters, init_step))
+            }
+            SchedulerConfig::Exponential { base_lr, gamma, decay_steps, staircase } => {
+                Box::new(ExponentialLR::new(base_lr, gamma, init_step).with_decay_steps(decay_steps, staircase))
+            }
+            SchedulerConfig::CosineAnnealing { eta_0, eta_1, t_max, decay_once, min_fraction } => {
+                Box::new(cosine_annealing(eta_0, eta_1, t_max, decay_once, min_fraction, init_step))
+            }
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, eta_1, t_0, t_mult, steps_per_epoch, semantics } => {
+                Box::new(
+                    CosineAnnealingWarmRestarts::new(eta_0, eta_1, t_0, t_mult, init_step)
+                        .with_epoch_alignment(steps_per_epoch)
+                        .with_semantics(semantics),
+                )
+            }
+        }
+    }
+
+    /// Constructs the configured scheduler starting from `init_step` for static dispatch.
+    /// 
+    /// This is the allocation-free counterpart of `build`.
+    pub fn build_any(&self, init_step: usize) -> AnyScheduler {
+        match *self {
+            SchedulerConfig::Constant { base_lr, factor, total_iters } => {
+                ConstantLR::new(base_lr, factor, total_iters, init_step).into()
+            }
+            SchedulerConfig::Linear { base_lr, start_factor, end_factor, total_iters } => {
+                LinearLR::new(base_lr, start_factor, end_factor, total_iters, init_step).into()
+            }
+            SchedulerConfig::Exponential { base_lr, gamma, decay_steps, staircase } => {
+                ExponentialLR::new(base_lr, gamma, init_step).with_decay_steps(decay_steps, staircase).into()
+            }
+            SchedulerConfig::CosineAnnealing { eta_0, eta_1, t_max, decay_once, min_fraction } => {
+                cosine_annealing(eta_0, eta_1, t_max, decay_once, min_fraction, init_step).into()
+            }
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, eta_1, t_0, t_mult, steps_per_epoch, semantics } => {
+                CosineAnnealingWarmRestarts::new(eta_0, eta_1, t_0, t_mult, init_step)
+                    .with_epoch_alignment(steps_per_epoch)
+                    .with_semantics(semantics)
+                    .into()
+            }
+        }
+    }
+
+    /// Returns the configuration whose learning rates are multiplied by `factor` at every step.
+    /// 
+    /// The learning rate parameters, `base_lr` or both of `eta_0` and `eta_1`, are multiplied, and the shape is kept.
+    /// A `min_fraction` is relative to `eta_0`, so it is kept as it is.
+    pub fn scale_lr(&self, factor: f64) -> Self {
+        match *self {
+            SchedulerConfig::Constant { base_lr, factor: f, total_iters } => {
+                SchedulerConfig::Constant { base_lr: base_lr * factor, factor: f, total_iters }
+            }
+            SchedulerConfig::Linear { base_lr, start_factor, end_factor, total_iters } => {
+                SchedulerConfig::Linear { base_lr: base_lr * factor, start_factor, end_factor, total_iters }
+            }
+            SchedulerConfig::Exponential { base_lr, gamma, decay_steps, staircase } => {
+                SchedulerConfig::Exponential { base_lr: base_lr * factor, gamma, decay_steps, staircase }
+            }
+            SchedulerConfig::CosineAnnealing { eta_0, eta_1, t_max, decay_once, min_fraction } => {
+                SchedulerConfig::CosineAnnealing { eta_0: eta_0 * factor, eta_1: eta_1 * factor, t_max, decay_once, min_fraction }
+            }
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, eta_1, t_0, t_mult, steps_per_epoch, semantics } => {
+                SchedulerConfig::CosineAnnealingWarmRestarts {
+                    eta_0: eta_0 * factor,
+                    eta_1: eta_1 * factor,
+                    t_0,
+                    t_mult,
+                    steps_per_epoch,
+                    semantics,
+                }
+            }
+        }
+    }
+}
+
+impl SchedulerConfig {
+    /// Returns the base learning rate, which is `base_lr` or `eta_0`.
+    pub fn base_lr(&self) -> f64 {
+        match *self {
+            SchedulerConfig::Constant { base_lr, .. }
+            | SchedulerConfig::Linear { base_lr, .. }
+            | SchedulerConfig::Exponential { base_lr, .. } => base_lr,
+            SchedulerConfig::CosineAnnealing { eta_0, .. } | SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, .. } => eta_0,
+        }
+    }
+
+    /// Returns the fingerprint of the canonical form of the configuration. See [`CanonicalConfig::fingerprint`].
+    pub fn fingerprint(&self) -> u64 {
+        canonicalize(self).fingerprint()
+    }
+
+    /// Splits the configuration into its base learning rate and the configuration of factors relative to it.
+    /// 
+    /// The factor configuration has a base learning rate of 1, and the original learning rates are reproduced by
+    /// [`Applied`](crate::applied::Applied) with the base learning rate. Returns None if the base learning rate is zero or not finite.
+    /// 
+    /// ```
+    /// # use lr_schedulers::config::SchedulerConfig;
+    /// let config = SchedulerConfig::CosineAnnealing { eta_0: 0.5, eta_1: 0.05, t_max: 10, decay_once: false, min_fraction: None };
+    /// let (base_lr, factors) = config.normalized().unwrap();
+    /// assert_eq!(base_lr, 0.5);
+    /// assert_eq!(factors, SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 10, decay_once: false, min_fraction: None });
+    /// ```
+    pub fn normalized(&self) -> Option<(f64, Self)> {
+        let base_lr = self.base_lr();
+        if base_lr == 0.0 || !base_lr.is_finite() {
+            return None;
+        }
+        Some((base_lr, self.scale_lr(1.0 / base_lr)))
+    }
+}
+
+/// Canonical form of a [`SchedulerConfig`], shared by mathematically equivalent parameterizations.
+/// 
+/// Two configurations with the same canonical form generate the same learning rates at every step, up to rounding errors
+/// of the normalized factors, so canonical forms can be compared and hashed to diff and deduplicate configurations.
+/// Floats are compared by value, with `-0.0` equal to `0.0` and NaN equal to NaN.
+/// See [`canonicalize`] for the normalization rules.
+#[derive(Debug, Clone)]
+pub struct CanonicalConfig(SchedulerConfig);
+
+impl CanonicalConfig {
+    /// Returns the canonical configuration.
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.0
+    }
+
+    /// Returns the canonical configuration, consuming self.
+    pub fn into_inner(self) -> SchedulerConfig {
+        self.0
+    }
+
+    /// Returns a fingerprint of the canonical form, e.g. to deduplicate runs with identical schedules in an experiment tracker.
+    /// 
+    /// The fingerprint is part of the API contract and stays the same across processes, platforms and releases
+    /// as long as [`SEMANTICS_VERSION`] does. It is the 64-bit FNV-1a hash of the little-endian bytes of `SEMANTICS_VERSION` (4 bytes),
+    /// the index of the variant in the declaration of [`SchedulerConfig`] (1 byte), and the parameters of the canonical form as words of 8 bytes.
+    /// A float is its bit pattern, an integer is its value, a boolean is 0 or 1, and a [`Semantics`] is the index of its variant.
+    /// The words of each variant are:
+    /// 
+    /// | Index | Variant | Words |
+    /// |---|---|---|
+    /// | 0 | `Constant` | `base_lr`, `factor`, `total_iters`, 0 |
+    /// | 1 | `Linear` | `base_lr`, `start_factor`, `end_factor`, `total_iters` |
+    /// | 2 | `Exponential` | `base_lr`, `gamma`, `decay_steps` or 0 if it is 1, `staircase` |
+    /// | 3 | `CosineAnnealing` | `eta_0`, `eta_1`, `t_max`, `decay_once` |
+    /// | 4 | `CosineAnnealingWarmRestarts` | `eta_0`, `eta_1`, `t_0`, `t_mult`, followed by `steps_per_epoch` and `semantics` unless they are 1 and `Native` |
+    /// 
+    /// So every variant has 4 words except `CosineAnnealingWarmRestarts` with options, which has 6.
+    /// `min_fraction` is not a word, since the canonical form replaces it with `eta_1`.
+    /// 
+    /// ```
+    /// # use lr_schedulers::config::{canonicalize, SchedulerConfig};
+    /// let a = SchedulerConfig::Exponential { base_lr: 0.1, gamma: 1.0, decay_steps: 1, staircase: false };
+    /// let b = SchedulerConfig::Constant { base_lr: 0.1, factor: 1.0, total_iters: 5 };
+    /// assert_eq!(canonicalize(&a).fingerprint(), b.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        let (variant, words) = self.key();
+        let bytes = SEMANTICS_VERSION
+            .to_le_bytes()
+            .into_iter()
+            .chain([variant])
+            .chain(words.into_iter().flat_map(u64::to_le_bytes));
+        bytes.fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+    }
+
+    /// Returns the bit patterns of the float parameters and the integer parameters, which identify the canonical form.
+    fn key(&self) -> (u8, Vec<u64>) {
+        let bits = |x: f64| x.to_bits();
+        match self.0 {
+            SchedulerConfig::Constant { base_lr, factor, total_iters } => {
+                (0, vec![bits(base_lr), bits(factor), total_iters as u64, 0])
+            }
+            SchedulerConfig::Linear { base_lr, start_factor, end_factor, total_iters } => {
+                (1, vec![bits(base_lr), bits(start_factor), bits(end_factor), total_iters as u64])
+            }
+            SchedulerConfig::Exponential { base_lr, gamma, decay_steps, staircase } => {
+                let decay_steps = if decay_steps == 1 { 0 } else { decay_steps as u64 };
+                (2, vec![bits(base_lr), bits(gamma), decay_steps, staircase as u64])
+            }
+            SchedulerConfig::CosineAnnealing { eta_0, eta_1, t_max, decay_once, .. } => {
+                (3, vec![bits(eta_0), bits(eta_1), t_max as u64, decay_once as u64])
+            }
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, eta_1, t_0, t_mult, steps_per_epoch, semantics } => {
+                let mut words = vec![bits(eta_0), bits(eta_1), t_0 as u64, t_mult as u64];
+                if steps_per_epoch != 1 || semantics != Semantics::Native {
+                    words.extend([steps_per_epoch as u64, semantics as u64]);
+                }
+                (4, words)
+            }
+        }
+    }
+}
+
+impl PartialEq for CanonicalConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for CanonicalConfig {}
+
+impl Hash for CanonicalConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl From<&SchedulerConfig> for CanonicalConfig {
+    fn from(config: &SchedulerConfig) -> Self {
+        canonicalize(config)
+    }
+}
+
+/// Returns the canonical form of `config`.
+/// 
+/// The normalization rules are:
+/// 
+/// * Integer parameters are clamped as the constructors do, e.g. `t_mult = 0` becomes 1.
+/// * A configuration whose learning rate never changes becomes `Constant { base_lr: lr, factor: 1.0, total_iters: 0 }`,
+///   e.g. `Constant` with `factor` 1, `Exponential` with `gamma` 1, or cosine schedules with `eta_0 == eta_1`.
+/// * `Linear` is rescaled to `end_factor` 1 (or `start_factor` 1 if `end_factor` is 0), and `Linear` of a single step,
+///   which jumps from the start to the end, becomes the equivalent `Constant`.
+/// * `Exponential` with a `decay_steps` of 1 does not use `staircase`, which becomes false, and `min_fraction`
+///   is replaced with the `eta_1` it gives.
+/// * `-0.0` becomes `0.0` and every NaN becomes the same NaN.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::config::{canonicalize, SchedulerConfig};
+/// let a = SchedulerConfig::Constant { base_lr: 0.1, factor: 1.0, total_iters: 5 };
+/// let b = SchedulerConfig::Exponential { base_lr: 0.1, gamma: 1.0, decay_steps: 1, staircase: false };
+/// let c = SchedulerConfig::Linear { base_lr: 0.05, start_factor: 2.0, end_factor: 2.0, total_iters: 10 };
+/// assert_eq!(canonicalize(&a), canonicalize(&b));
+/// assert_eq!(canonicalize(&a), canonicalize(&c));
+/// ```
+pub fn canonicalize(config: &SchedulerConfig) -> CanonicalConfig {
+    let constant = |lr: f64| SchedulerConfig::Constant { base_lr: lr, factor: 1.0, total_iters: 0 };
+    let canonical = match *config {
+        SchedulerConfig::Constant { base_lr, factor, total_iters } => {
+            if total_iters == 0 || factor == 1.0 || base_lr == 0.0 {
+                constant(base_lr)
+            } else {
+                SchedulerConfig::Constant { base_lr, factor, total_iters }
+            }
+        }
+        SchedulerConfig::Linear { base_lr, start_factor, end_factor, total_iters } => {
+            let (start_lr, end_lr) = (base_lr * start_factor, base_lr * end_factor);
+            if total_iters == 0 || start_factor == end_factor || base_lr == 0.0 {
+                constant(end_lr)
+            } else if end_factor == 0.0 {
+                SchedulerConfig::Linear { base_lr: start_lr, start_factor: 1.0, end_factor: 0.0, total_iters }
+            } else if total_iters == 1 {
+                SchedulerConfig::Constant { base_lr: end_lr, factor: start_factor / end_factor, total_iters }
+            } else {
+                SchedulerConfig::Linear { base_lr: end_lr, start_factor: start_factor / end_factor, end_factor: 1.0, total_iters }
+            }
+        }
+        SchedulerConfig::Exponential { base_lr, gamma, decay_steps, staircase } => {
+            if gamma == 1.0 || base_lr == 0.0 {
+                constant(base_lr)
+            } else if decay_steps <= 1 {
+                SchedulerConfig::Exponential { base_lr, gamma, decay_steps: 1, staircase: false }
+            } else {
+                SchedulerConfig::Exponential { base_lr, gamma, decay_steps, staircase }
+            }
+        }
+        SchedulerConfig::CosineAnnealing { eta_0, eta_1, t_max, decay_once, min_fraction } => {
+            let eta_1 = min_fraction.map_or(eta_1, |min_fraction| min_fraction * eta_0);
+            if eta_0 == eta_1 {
+                constant(eta_0)
+            } else {
+                SchedulerConfig::CosineAnnealing { eta_0, eta_1, t_max: t_max.clamp(1, usize::MAX / 2), decay_once, min_fraction: None }
+            }
+        }
+        SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, eta_1, t_0, t_mult, steps_per_epoch, semantics } => {
+            if eta_0 == eta_1 {
+                constant(eta_0)
+            } else {
+                SchedulerConfig::CosineAnnealingWarmRestarts {
+                    eta_0,
+                    eta_1,
+                    t_0: t_0.clamp(1, MAX_T),
+                    t_mult: t_mult.max(1),
+                    steps_per_epoch: steps_per_epoch.max(1),
+                    semantics,
+                }
+            }
+        }
+    };
+    CanonicalConfig(canonical.map_floats(|x| if x.is_nan() { f64::NAN } else { x + 0.0 }))
+}
+
+impl SchedulerConfig {
+    /// Returns the configuration whose float parameters are replaced with `f` of them.
+    fn map_floats(&self, f: impl Fn(f64) -> f64) -> Self {
+        match *self {
+            SchedulerConfig::Constant { base_lr, factor, total_iters } => {
+                SchedulerConfig::Constant { base_lr: f(base_lr), factor: f(factor), total_iters }
+            }
+            SchedulerConfig::Linear { base_lr, start_factor, end_factor, total_iters } => {
+                SchedulerConfig::Linear { base_lr: f(base_lr), start_factor: f(start_factor), end_factor: f(end_factor), total_iters }
+            }
+            SchedulerConfig::Exponential { base_lr, gamma, decay_steps, staircase } => {
+                SchedulerConfig::Exponential { base_lr: f(base_lr), gamma: f(gamma), decay_steps, staircase }
+            }
+            SchedulerConfig::CosineAnnealing { eta_0, eta_1, t_max, decay_once, min_fraction } => {
+                SchedulerConfig::CosineAnnealing { eta_0: f(eta_0), eta_1: f(eta_1), t_max, decay_once, min_fraction: min_fraction.map(&f) }
+            }
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, eta_1, t_0, t_mult, steps_per_epoch, semantics } => {
+                SchedulerConfig::CosineAnnealingWarmRestarts {
+                    eta_0: f(eta_0),
+                    eta_1: f(eta_1),
+                    t_0,
+                    t_mult,
+                    steps_per_epoch,
+                    semantics,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn one() -> usize {
+    1
+}
+
+/// Constructs a CosineAnnealingLR instance with the parameters of [`SchedulerConfig::CosineAnnealing`].
+fn cosine_annealing(eta_0: f64, eta_1: f64, t_max: usize, decay_once: bool, min_fraction: Option<f64>, init_step: usize) -> CosineAnnealingLR {
+    let scheduler = if decay_once {
+        CosineAnnealingLR::decay_once(eta_0, eta_1, t_max, init_step)
+    } else {
+        CosineAnnealingLR::new(eta_0, eta_1, t_max, init_step)
+    };
+    match min_fraction {
+        Some(min_fraction) => scheduler.with_min_fraction(min_fraction),
+        None => scheduler,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::Scheduler;
+    use super::*;
+
+    #[test]
+    fn build_matches_constructor() {
+        let config = SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.1, t_0: 3, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native };
+        let mut built = config.build(2);
+        let mut reference = CosineAnnealingWarmRestarts::new(1.0, 0.1, 3, 2, 2);
+        for i in 0 .. 20 {
+            assert_eq!(built.get_lr(0.0), reference.get_lr(0.0), "Step {}", i);
+            // Proceed a step with dummy loss.
+            built.step(0.0);
+            reference.step(0.0);
+        }
+    }
+
+    #[test]
+    fn canonical_forms_are_equivalent() {
+        let configs = [
+            SchedulerConfig::Constant { base_lr: 0.5, factor: 1.0, total_iters: 3 },
+            SchedulerConfig::Exponential { base_lr: 0.5, gamma: 1.0, decay_steps: 1, staircase: false },
+            SchedulerConfig::CosineAnnealing { eta_0: 0.5, eta_1: 0.5, t_max: 0, decay_once: false, min_fraction: None },
+            SchedulerConfig::Linear { base_lr: 1.0, start_factor: 0.1, end_factor: 0.5, total_iters: 0 },
+            SchedulerConfig::Linear { base_lr: 1.0, start_factor: 0.25, end_factor: 0.5, total_iters: 4 },
+            SchedulerConfig::Linear { base_lr: 0.5, start_factor: 0.5, end_factor: 1.0, total_iters: 4 },
+            SchedulerConfig::Linear { base_lr: 2.0, start_factor: 0.5, end_factor: 0.0, total_iters: 2 },
+            SchedulerConfig::Linear { base_lr: 2.0, start_factor: 0.5, end_factor: 0.25, total_iters: 1 },
+            SchedulerConfig::Constant { base_lr: 0.5, factor: 2.0, total_iters: 1 },
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: -0.0, t_0: 0, t_mult: 0, steps_per_epoch: 1, semantics: Semantics::Native },
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.0, t_0: 1, t_mult: 1, steps_per_epoch: 1, semantics: Semantics::Native },
+        ];
+        for config in &configs {
+            let canonical = canonicalize(config);
+            let (mut a, mut b) = (config.build(0), canonical.config().build(0));
+            for i in 0 .. 10 {
+                assert_eq!(a.get_lr(0.0), b.get_lr(0.0), "{:?}: Step {}", config, i);
+                a.step(0.0);
+                b.step(0.0);
+            }
+        }
+        let distinct: HashSet<_> = configs.iter().map(CanonicalConfig::from).collect();
+        assert_eq!(distinct.len(), 5);
+        let nan = SchedulerConfig::Exponential { base_lr: f64::NAN, gamma: 0.5, decay_steps: 1, staircase: false };
+        assert_eq!(canonicalize(&nan), canonicalize(&nan));
+    }
+
+    #[test]
+    fn canonical_options() {
+        let periodic = SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 10, decay_once: false, min_fraction: None };
+        let options = |decay_once, min_fraction| SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.5, t_max: 10, decay_once, min_fraction };
+        assert_eq!(canonicalize(&options(false, Some(0.1))), canonicalize(&periodic));
+        let absolute = SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 10, decay_once: true, min_fraction: None };
+        assert_eq!(canonicalize(&options(true, Some(0.1))), canonicalize(&absolute));
+        assert_ne!(options(true, Some(0.1)).fingerprint(), periodic.fingerprint());
+        let exponential = SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.5, decay_steps: 1, staircase: false };
+        let decay_every = |decay_steps| SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.5, decay_steps, staircase: true };
+        assert_eq!(decay_every(1).fingerprint(), exponential.fingerprint());
+        assert_ne!(decay_every(2).fingerprint(), exponential.fingerprint());
+        let restarts = SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.0, t_0: 0, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native };
+        let aligned = |steps_per_epoch, semantics| SchedulerConfig::CosineAnnealingWarmRestarts {
+            eta_0: 1.0, eta_1: 0.0, t_0: 0, t_mult: 2, steps_per_epoch, semantics
+        };
+        assert_eq!(aligned(0, Semantics::Native).fingerprint(), restarts.fingerprint());
+        assert_ne!(aligned(10, Semantics::Native).fingerprint(), restarts.fingerprint());
+        assert_ne!(aligned(1, Semantics::PyTorchParity).fingerprint(), restarts.fingerprint());
+        assert_eq!(aligned(0, Semantics::PyTorchParity).fingerprint(), aligned(1, Semantics::PyTorchParity).fingerprint());
+    }
+
+    #[test]
+    fn stable_fingerprint() {
+        // Changing these values breaks the fingerprints stored by users, which requires a new SEMANTICS_VERSION.
+        let config = SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 100, decay_once: false, min_fraction: None };
+        assert_eq!(config.fingerprint(), 11105279603628470081);
+        let config = SchedulerConfig::Exponential { base_lr: 0.1, gamma: 1.0, decay_steps: 1, staircase: false };
+        assert_eq!(config.fingerprint(), 11769542304763122620);
+        assert_eq!(config.fingerprint(), SchedulerConfig::Constant { base_lr: 0.1, factor: 1.0, total_iters: 5 }.fingerprint());
+        assert_ne!(config.fingerprint(), SchedulerConfig::Constant { base_lr: 0.2, factor: 1.0, total_iters: 5 }.fingerprint());
+    }
+}
\ No newline at end of file
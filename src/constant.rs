@@ -1,4 +1,5 @@
-use crate::Scheduler;
+use crate::pbt::Perturbable;
+use crate::{progress_at, Bounded, ClosedForm, PiecewiseMonotone, Scheduler};
 
 /// Decays the learning rate by a constant factor until the number of steps reaches a given number.
 /// 
@@ -52,6 +53,7 @@ use crate::Scheduler;
 pub struct ConstantLR {
     lr: f64,
     base_lr: f64,
+    factor: f64,
     step: usize,
     total_iters: usize,
 }
@@ -61,32 +63,119 @@ impl ConstantLR {
     /// 
     /// This scheduler returns `factor * base_lr` before the number of steps is less than `total_iters`, otherwise, returns `base_lr`.
     /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
-    pub fn new(base_lr: f64, factor: f64, total_iters: usize, init_step: usize) -> Self {
-        let lr = if init_step < total_iters {
-            factor * base_lr
-        } else {
-            base_lr
-        };
-        ConstantLR {
-            lr,
+    /// 
+    /// This is a `const fn`, so the scheduler can be constructed in `const` and `static` items.
+    pub const fn new(base_lr: f64, factor: f64, total_iters: usize, init_step: usize) -> Self {
+        let mut scheduler = ConstantLR {
+            lr: base_lr,
             base_lr,
+            factor,
             step: init_step,
             total_iters,
+        };
+        scheduler.lr = scheduler.value_at(init_step);
+        scheduler
+    }
+
+    /// Returns the learning rates of the first `N` steps.
+    /// 
+    /// This is a `const fn`, so short schedules can be baked into static arrays at compile time.
+    /// 
+    /// ```
+    /// # use lr_schedulers::constant::ConstantLR;
+    /// const LRS: [f64; 4] = ConstantLR::new(1.0, 0.5, 2, 0).table::<4>();
+    /// assert_eq!(LRS, [0.5, 0.5, 1.0, 1.0]);
+    /// ```
+    pub const fn table<const N: usize>(&self) -> [f64; N] {
+        let mut lrs = [0.0; N];
+        let mut i = 0;
+        while i < N {
+            lrs[i] = self.value_at(i);
+            i += 1;
         }
+        lrs
+    }
+
+    /// Changes `base_lr` without resetting the step.
+    pub fn set_base_lr(&mut self, base_lr: f64) {
+        self.base_lr = base_lr;
+        self.lr = self.value_at(self.step);
+    }
+
+    /// Changes `factor` without resetting the step.
+    pub fn set_factor(&mut self, factor: f64) {
+        self.factor = factor;
+        self.lr = self.value_at(self.step);
+    }
+
+    const fn value_at(&self, step: usize) -> f64 {
+        if step < self.total_iters {
+            self.factor * self.base_lr
+        } else {
+            self.base_lr
+        }
+    }
+
+    /// Returns the current step, counted from 0 in the same way as `init_step`.
+    pub(crate) fn current_step(&self) -> usize {
+        self.step
     }
 }
 
 impl Scheduler for ConstantLR {
     fn step(&mut self, _loss: f64) {
-        self.step += 1;
-        if self.step == self.total_iters {
-            self.lr = self.base_lr
-        }
+        self.step = self.step.saturating_add(1);
+        self.lr = self.lr_at(self.step);
     }
 
     fn get_lr(&self, _loss: f64) -> f64 {
         self.lr
     }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        Some(self.total_iters.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        Some(progress_at(self.step, self.total_iters))
+    }
+}
+
+impl ClosedForm for ConstantLR {
+    fn lr_at(&self, step: usize) -> f64 {
+        self.value_at(step)
+    }
+
+    fn horizon(&self) -> Option<usize> {
+        Some(self.total_iters)
+    }
+}
+
+impl PiecewiseMonotone for ConstantLR {
+    fn monotone_pieces(&self) -> Vec<(usize, usize)> {
+        vec![(0, self.total_iters)]
+    }
+}
+
+impl Bounded for ConstantLR {
+    fn bounds(&self) -> (f64, f64) {
+        if self.total_iters == 0 {
+            return (self.base_lr, self.base_lr);
+        }
+        let lr = self.factor * self.base_lr;
+        (lr.min(self.base_lr), lr.max(self.base_lr))
+    }
+}
+
+impl Perturbable for ConstantLR {
+    fn perturb(&mut self, factor: f64) {
+        self.base_lr *= factor;
+        self.lr *= factor;
+    }
 }
 
 #[cfg(test)]
@@ -284,4 +373,24 @@ mod tests {
             scheduler.step(0.0);
         }
     }
+
+    #[test]
+    fn random_access() {
+        let mut scheduler = ConstantLR::new(0.5, 0.1, 3, 0);
+        let reference = scheduler.clone();
+        for i in 0 .. 6 {
+            assert_eq!(reference.lr_at(i), scheduler.get_lr(0.0), "Step {}", i);
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn const_table() {
+        const SCHEDULER: ConstantLR = ConstantLR::new(0.5, 0.1, 2, 1);
+        static LRS: [f64; 4] = SCHEDULER.table::<4>();
+        for (i, lr) in LRS.iter().enumerate() {
+            assert_eq!(*lr, SCHEDULER.lr_at(i), "Step {}", i);
+        }
+        assert_eq!(SCHEDULER.get_lr(0.0), 0.5 * 0.1);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,143 @@
+use crate::{progress_at, Scheduler};
+
+/// Takes the learning rate linearly to zero at the end of training, whatever the wrapped schedule.
+/// 
+/// Until `finish` is called, the learning rate of the wrapped scheduler is returned as is.
+/// After `finish(remaining_steps)`, the learning rate decreases linearly from the current one to zero
+/// over `remaining_steps` steps and stays at zero afterwards, as the terminal cooldown of LLM training.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::cooldown::LinearCooldown;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = LinearCooldown::new(ConstantLR::new(1.0, 1.0, 0, 0));
+/// scheduler.finish(4);
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 6 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(learning_rates, [1.0, 0.75, 0.5, 0.25, 0.0, 0.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LinearCooldown<S> {
+    scheduler: S,
+    cooldown: Option<Cooldown>,
+    last_lr: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Cooldown {
+    start_lr: f64,
+    total_steps: usize,
+    steps: usize,
+}
+
+impl<S: Scheduler> LinearCooldown<S> {
+    /// Constructs a LinearCooldown instance, which follows `scheduler` until `finish` is called.
+    pub fn new(scheduler: S) -> Self {
+        LinearCooldown { scheduler, cooldown: None, last_lr: None }
+    }
+
+    /// Starts the cooldown from the current learning rate to zero over `remaining_steps` steps.
+    /// 
+    /// With `remaining_steps` of 0, the learning rate is zero from the current step.
+    /// Calling this again during a cooldown restarts it from the current learning rate.
+    pub fn finish(&mut self, remaining_steps: usize) {
+        // Evaluated before replacing the cooldown, so a restarted cooldown starts from the cooled learning rate.
+        let start_lr = self.get_lr(0.0);
+        self.cooldown = Some(Cooldown { start_lr, total_steps: remaining_steps, steps: 0 });
+    }
+
+    /// Returns true if `finish` has been called.
+    pub fn is_cooling_down(&self) -> bool {
+        self.cooldown.is_some()
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for LinearCooldown<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.scheduler.step(loss);
+        if let Some(cooldown) = &mut self.cooldown {
+            cooldown.steps = cooldown.steps.saturating_add(1);
+        }
+    }
+
+    /// Returns the learning rate of the wrapped scheduler, or of the cooldown after `finish`.
+    /// 
+    /// Since the loss of a metric-driven scheduler is only seen by its own `get_lr`, the cooldown starts from
+    /// the learning rate evaluated with a loss of 0.0.
+    fn get_lr(&self, loss: f64) -> f64 {
+        match self.cooldown {
+            None => self.scheduler.get_lr(loss),
+            Some(Cooldown { total_steps, steps, .. }) if steps >= total_steps => 0.0,
+            Some(Cooldown { start_lr, total_steps, steps }) => start_lr * (1.0 - steps as f64 / total_steps as f64),
+        }
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the number of steps until the learning rate reaches zero after `finish`,
+    /// or the remaining steps of the wrapped scheduler before it.
+    fn remaining_steps(&self) -> Option<usize> {
+        match self.cooldown {
+            Some(Cooldown { total_steps, steps, .. }) => Some(total_steps.saturating_sub(steps)),
+            None => self.scheduler.remaining_steps(),
+        }
+    }
+
+    /// Returns the completed fraction of the cooldown after `finish`, or the progress of the wrapped scheduler before it.
+    fn progress(&self) -> Option<f64> {
+        match self.cooldown {
+            Some(Cooldown { total_steps, steps, .. }) => Some(progress_at(steps, total_steps)),
+            None => self.scheduler.progress(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exponential::ExponentialLR;
+    use super::*;
+
+    #[test]
+    fn cool_down_from_current_lr() {
+        let mut scheduler = LinearCooldown::new(ExponentialLR::new(8.0, 0.5, 0));
+        scheduler.step(0.0);
+        assert!(!scheduler.is_cooling_down());
+        assert_eq!(scheduler.remaining_steps(), None);
+        scheduler.finish(2);
+        let expected_lrs = [4.0, 2.0, 0.0, 0.0];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            assert_eq!(scheduler.get_lr(0.0), *exp_lr, "Step {}", i);
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.remaining_steps(), Some(0));
+        assert_eq!(scheduler.get_last_lr(), Some(0.0));
+    }
+
+    #[test]
+    fn restart_cooldown() {
+        let mut scheduler = LinearCooldown::new(ExponentialLR::new(1.0, 1.0, 0));
+        scheduler.finish(4);
+        scheduler.step(0.0);
+        scheduler.step(0.0);
+        scheduler.finish(1);
+        assert_eq!(scheduler.get_lr(0.0), 0.5);
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.0);
+        scheduler.finish(0);
+        assert_eq!(scheduler.get_lr(0.0), 0.0);
+    }
+}
\ No newline at end of file
@@ -1,4 +1,6 @@
-use crate::Scheduler;
+use crate::pbt::Perturbable;
+use crate::rotation::Rotation;
+use crate::{epochs_to_steps, progress_at, Bounded, ClosedForm, Cyclical, PiecewiseMonotone, Scheduler};
 
 const PI: f64 = std::f64::consts::PI;
 
@@ -43,6 +45,24 @@ const PI: f64 = std::f64::consts::PI;
 /// }
 /// ```
 /// 
+/// Use `decay_once` to decay the learning rate once and hold `eta_1` afterwards:
+/// 
+/// ```
+/// # use lr_schedulers::cosine_annealing::CosineAnnealingLR;
+/// # use lr_schedulers::Scheduler;
+/// # use std::iter::zip;
+/// let mut scheduler = CosineAnnealingLR::decay_once(1.0, 0.0, 2, 0);
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 5 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// for (target, expected) in zip(learning_rates, [1.0, 0.5, 0.0, 0.0, 0.0]) {
+///     assert!((target - expected).abs() < 1e-10);
+/// }
+/// ```
+/// 
 /// The `get_lr` method returns the same value unless the `step` method is invoked.
 /// 
 /// ```no_run
@@ -63,6 +83,10 @@ pub struct CosineAnnealingLR {
     eta_1: f64,
     step: usize,
     t_max: usize,
+    rotation: Rotation,
+    last_lr: Option<f64>,
+    hold: bool,
+    min_fraction: Option<f64>,
 }
 
 impl CosineAnnealingLR {
@@ -70,48 +94,268 @@ impl CosineAnnealingLR {
     /// 
     /// This scheduler returns learning rate that oscillates between `eta_0` and `eta_1` with a period of `2*t_max`.
     /// The parameter `t_max` must be larger than 0. When 0 is provided, its value is replaced with 1.
+    /// A `t_max` larger than `usize::MAX / 2` is replaced with `usize::MAX / 2`, so that a period fits in `usize`.
     /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
+    /// 
+    /// The cosine is updated incrementally at every `step` and recomputed periodically,
+    /// so the learning rate differs from `lr_at` by less than `1e-13 * |eta_0 - eta_1|`.
     pub fn new(
         eta_0: f64,
         eta_1: f64,
         t_max: usize,
         init_step: usize,
     ) -> Self {
-        let t_max = t_max.max(1);
-        let lr = if init_step == 0 {
-            eta_0
-        } else {
-            let periodic_factor = periodic_factor(init_step, t_max);
-            (eta_0 - eta_1).mul_add(periodic_factor, eta_1)
-        };
-        CosineAnnealingLR { lr, eta_0, eta_1, step: init_step, t_max }
+        let t_max = t_max.clamp(1, usize::MAX / 2);
+        let rotation = Rotation::new(phase(init_step, t_max), PI / (t_max as f64));
+        let mut scheduler = CosineAnnealingLR { lr: eta_0, eta_0, eta_1, step: init_step, t_max, rotation, last_lr: None, hold: false, min_fraction: None };
+        scheduler.lr = scheduler.lr_at(init_step);
+        scheduler.last_lr = init_step.checked_sub(1).map(|step| scheduler.lr_at(step));
+        scheduler
+    }
+
+    /// Constructs a CosineAnnealingLR instance whose `t_max` is specified in epochs.
+    /// 
+    /// The parameter `t_max_epochs` may be fractional and is converted to the nearest number of steps with `steps_per_epoch`.
+    /// The other parameters are the same as `new`, and `init_step` is still counted in steps.
+    /// 
+    /// ```
+    /// # use lr_schedulers::cosine_annealing::CosineAnnealingLR;
+    /// # use lr_schedulers::Cyclical;
+    /// let scheduler = CosineAnnealingLR::from_epochs(1.0, 0.0, 2.5, 100, 0);
+    /// assert_eq!(scheduler.cycle_length(), 500);
+    /// ```
+    pub fn from_epochs(
+        eta_0: f64,
+        eta_1: f64,
+        t_max_epochs: f64,
+        steps_per_epoch: usize,
+        init_step: usize,
+    ) -> Self {
+        Self::new(eta_0, eta_1, epochs_to_steps(t_max_epochs, steps_per_epoch), init_step)
+    }
+
+    /// Constructs a CosineAnnealingLR instance that decays only once.
+    /// 
+    /// This scheduler returns learning rate that changes from `eta_0` to `eta_1` over `t_max` steps along a half cosine,
+    /// and then holds `eta_1`, as PyTorch's CosineAnnealingLR with `T_max` set to the total number of steps.
+    /// The other parameters are the same as `new`.
+    pub fn decay_once(
+        eta_0: f64,
+        eta_1: f64,
+        t_max: usize,
+        init_step: usize,
+    ) -> Self {
+        let mut scheduler = CosineAnnealingLR { hold: true, ..Self::new(eta_0, eta_1, t_max, init_step) };
+        scheduler.lr = scheduler.lr_at(init_step);
+        scheduler.last_lr = init_step.checked_sub(1).map(|step| scheduler.lr_at(step));
+        scheduler
+    }
+
+    /// Specifies `eta_1` as a fraction of `eta_0`, e.g. 0.1 to decay to 10% of the peak.
+    /// 
+    /// The `eta_1` given to the constructor is replaced with `min_fraction * eta_0`,
+    /// and it follows `eta_0` when `eta_0` is changed by `set_eta_0`.
+    /// 
+    /// ```
+    /// # use lr_schedulers::cosine_annealing::CosineAnnealingLR;
+    /// # use lr_schedulers::ClosedForm;
+    /// let mut scheduler = CosineAnnealingLR::decay_once(1.0, 0.0, 10, 0).with_min_fraction(0.1);
+    /// assert_eq!(scheduler.lr_at(10), 0.1);
+    /// scheduler.set_eta_0(3.0);
+    /// assert!((scheduler.lr_at(10) - 0.3).abs() < 1e-15);
+    /// ```
+    pub fn with_min_fraction(self, min_fraction: f64) -> Self {
+        let mut scheduler = CosineAnnealingLR { min_fraction: Some(min_fraction), ..self };
+        scheduler.set_eta_0(scheduler.eta_0);
+        scheduler.last_lr = scheduler.step.checked_sub(1).map(|step| scheduler.lr_at(step));
+        scheduler
+    }
+
+    /// Changes `eta_0` without resetting the step.
+    /// 
+    /// If `eta_1` is specified as a fraction of `eta_0` by `with_min_fraction`, it is changed accordingly.
+    pub fn set_eta_0(&mut self, eta_0: f64) {
+        self.eta_0 = eta_0;
+        if let Some(min_fraction) = self.min_fraction {
+            self.eta_1 = min_fraction * eta_0;
+        }
+        self.lr = self.lr_at(self.step);
+    }
+
+    /// Changes `eta_0` and `eta_1` without resetting the step.
+    /// 
+    /// This sets `eta_1` to an absolute value, so `eta_1` no longer follows `eta_0` after `with_min_fraction`.
+    pub fn set_eta(&mut self, eta_0: f64, eta_1: f64) {
+        self.eta_0 = eta_0;
+        self.eta_1 = eta_1;
+        self.min_fraction = None;
+        self.lr = self.lr_at(self.step);
+    }
+
+    /// Returns the fraction of `eta_0` given by `with_min_fraction`, or None if `eta_1` is an absolute value.
+    pub fn min_fraction(&self) -> Option<f64> {
+        self.min_fraction
+    }
+
+    /// Returns true if this scheduler holds `eta_1` after `t_max` steps instead of rising again.
+    pub fn is_decay_once(&self) -> bool {
+        self.hold
+    }
+
+    /// Returns the current step, counted from 0 in the same way as `init_step`.
+    pub(crate) fn current_step(&self) -> usize {
+        self.step
     }
 }
 
 impl Scheduler for CosineAnnealingLR {
     fn step(&mut self, _loss: f64) {
-        self.step += 1;
-        let periodic_factor = periodic_factor(self.step, self.t_max);
+        self.last_lr = Some(self.lr);
+        self.step = self.step.saturating_add(1);
+        if self.hold && self.step >= self.t_max {
+            self.lr = self.eta_1;
+            return;
+        }
+        let phase = phase(self.step, self.t_max);
+        if phase == 0.0 || self.rotation.needs_resync() {
+            self.rotation.resync(phase);
+        } else {
+            self.rotation.advance();
+        }
+        let periodic_factor = 0.5 * (1.0 + self.rotation.cos());
         self.lr = (self.eta_0 - self.eta_1).mul_add(periodic_factor, self.eta_1);
     }
 
     fn get_lr(&self, _loss: f64) -> f64 {
         self.lr
     }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the steps until `t_max` for a scheduler constructed with `decay_once`, otherwise None.
+    fn remaining_steps(&self) -> Option<usize> {
+        self.horizon().map(|horizon| horizon.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.horizon().map(|horizon| progress_at(self.step, horizon))
+    }
 }
 
-fn periodic_factor(t: usize, t_max: usize) -> f64 {
+impl ClosedForm for CosineAnnealingLR {
+    fn lr_at(&self, step: usize) -> f64 {
+        if step == 0 {
+            return self.eta_0;
+        }
+        if self.hold && step >= self.t_max {
+            return self.eta_1;
+        }
+        let periodic_factor = periodic_factor(step, self.t_max);
+        (self.eta_0 - self.eta_1).mul_add(periodic_factor, self.eta_1)
+    }
+
+    /// Evaluates the cosine at `t`.
+    fn lr_at_f64(&self, t: f64) -> f64 {
+        let t = t.max(0.0);
+        if self.hold && t >= self.t_max as f64 {
+            return self.eta_1;
+        }
+        let phase = t.rem_euclid(2.0 * self.t_max as f64) * PI / (self.t_max as f64);
+        (self.eta_0 - self.eta_1).mul_add(0.5 * (1.0 + phase.cos()), self.eta_1)
+    }
+
+    /// Returns `t_max` for a scheduler constructed with `decay_once`, otherwise None.
+    fn horizon(&self) -> Option<usize> {
+        self.hold.then_some(self.t_max)
+    }
+}
+
+/// The first decay takes every learning rate of the schedule, including those of later periods.
+impl PiecewiseMonotone for CosineAnnealingLR {
+    fn monotone_pieces(&self) -> Vec<(usize, usize)> {
+        vec![(0, self.t_max)]
+    }
+}
+
+/// A scheduler constructed with `decay_once` has a single cycle of `t_max + 1` steps, whose last step is held forever.
+impl Cyclical for CosineAnnealingLR {
+    /// Returns the index of the current period of `2*t_max` steps.
+    fn current_cycle(&self) -> usize {
+        if self.hold {
+            return 0;
+        }
+        self.step / (2*self.t_max)
+    }
+
+    fn steps_into_cycle(&self) -> usize {
+        if self.hold {
+            return self.step.min(self.t_max);
+        }
+        self.step % (2*self.t_max)
+    }
+
+    fn cycle_length(&self) -> usize {
+        if self.hold {
+            return self.t_max + 1;
+        }
+        2*self.t_max
+    }
+
+    /// Returns true at the middle of a period, where the learning rate reaches `eta_1`,
+    /// or only at `t_max` for a scheduler constructed with `decay_once`.
+    fn at_cycle_minimum(&self) -> bool {
+        if self.hold {
+            return self.step == self.t_max;
+        }
+        self.step % (2*self.t_max) == self.t_max
+    }
+}
+
+/// Returns `eta_0` and `eta_1`, which are reached in every cycle.
+impl Bounded for CosineAnnealingLR {
+    fn bounds(&self) -> (f64, f64) {
+        (self.eta_0.min(self.eta_1), self.eta_0.max(self.eta_1))
+    }
+}
+
+impl Perturbable for CosineAnnealingLR {
+    fn perturb(&mut self, factor: f64) {
+        self.eta_0 *= factor;
+        self.eta_1 *= factor;
+        self.lr *= factor;
+    }
+}
+
+fn phase(t: usize, t_max: usize) -> f64 {
     let r = t.rem_euclid(2*t_max);
-    let phase = (r as f64) * PI / (t_max as f64);
-    0.5 * (1.0 + phase.cos())
+    (r as f64) * PI / (t_max as f64)
+}
+
+fn periodic_factor(t: usize, t_max: usize) -> f64 {
+    0.5 * (1.0 + phase(t, t_max).cos())
 }
 
 #[cfg(test)]
 mod tests {
     use approx::relative_eq;
+    use crate::snapshot::SnapshotSchedule;
     use crate::Scheduler;
     use super::*;
 
+    #[test]
+    fn fractional_steps() {
+        for scheduler in [CosineAnnealingLR::new(1.0, 0.1, 4, 0), CosineAnnealingLR::decay_once(1.0, 0.1, 4, 0)] {
+            for step in 0 .. 12 {
+                assert!(relative_eq!(scheduler.lr_at_f64(step as f64), scheduler.lr_at(step), epsilon = 1e-15), "Step {}", step);
+            }
+            assert!(relative_eq!(scheduler.lr_at_f64(2.0 / 3.0), 0.1 + 0.9 * 0.5 * (1.0 + (PI / 6.0).cos()), epsilon = 1e-15));
+        }
+        assert!(relative_eq!(CosineAnnealingLR::new(1.0, 0.0, 4, 0).lr_at_f64(6.0), 0.5, epsilon = 1e-15));
+        assert_eq!(CosineAnnealingLR::decay_once(1.0, 0.0, 4, 0).lr_at_f64(6.5), 0.0);
+    }
+
     #[test]
     fn decrease_first_lr() {
         let eta_0 = 1.0;
@@ -184,4 +428,95 @@ mod tests {
             scheduler.step(0.0);
         }
     }
+
+    #[test]
+    fn random_access() {
+        let mut scheduler = CosineAnnealingLR::new(1.0, 0.1, 3, 0);
+        let reference = scheduler.clone();
+        for i in 0 .. 20 {
+            let lr = scheduler.get_lr(0.0);
+            assert!(relative_eq!(lr, reference.lr_at(i), epsilon = 1e-12), "Step {}: left: {}, right: {}", i, lr, reference.lr_at(i));
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let mut scheduler = CosineAnnealingLR::new(1.0, 0.0, 2, 3);
+        let expected = [(0, 3), (1, 0), (1, 1), (1, 2), (1, 3), (2, 0)];
+        for (i, (cycle, steps_into_cycle)) in expected.iter().enumerate() {
+            assert_eq!(scheduler.current_cycle(), *cycle, "Step {}", i);
+            assert_eq!(scheduler.steps_into_cycle(), *steps_into_cycle, "Step {}", i);
+            assert_eq!(scheduler.cycle_length(), 4, "Step {}", i);
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn decay_once() {
+        let mut scheduler = CosineAnnealingLR::decay_once(1.0, 0.1, 4, 1);
+        let reference = scheduler.clone();
+        let expected_lrs = [0.1 + 0.9 * 0.5 * (1.0 + (PI / 4.0).cos()), 0.55, 0.1 + 0.9 * 0.5 * (1.0 + (3.0 * PI / 4.0).cos()), 0.1, 0.1, 0.1];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            let lr = scheduler.get_lr(0.0);
+            assert!(relative_eq!(lr, *exp_lr, epsilon = 1e-12), "Step {}: left: {}, right: {}", i, lr, *exp_lr);
+            assert!(relative_eq!(lr, reference.lr_at(i + 1), epsilon = 1e-12), "Step {}", i);
+            assert_eq!(scheduler.current_cycle(), 0, "Step {}", i);
+            assert_eq!(scheduler.at_cycle_minimum(), i == 3, "Step {}", i);
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.steps_into_cycle(), 4);
+        assert!(scheduler.is_decay_once());
+    }
+
+    #[test]
+    fn decay_once_reaches_minimum_once() {
+        let mut snapshots = SnapshotSchedule::new(CosineAnnealingLR::decay_once(1.0, 0.0, 4, 0));
+        let saved: Vec<_> = (0 .. 20).filter_map(|_| snapshots.step_with_snapshot(0.0)).map(|point| point.step).collect();
+        assert_eq!(saved, [4]);
+    }
+
+    #[test]
+    fn incremental_drift() {
+        let mut scheduler = CosineAnnealingLR::new(1.0, 0.0, 100_003, 0);
+        let reference = scheduler.clone();
+        for i in 0 .. 300_000 {
+            let error = (scheduler.get_lr(0.0) - reference.lr_at(i)).abs();
+            assert!(error < 1e-13, "Step {}: error {}", i, error);
+            scheduler.step(0.0);
+        }
+    }
+
+    // Steps 10M times, far more than the other tests. Run with `cargo test -- --ignored`.
+    #[test]
+    #[ignore]
+    fn incremental_drift_over_ten_million_steps() {
+        let mut scheduler = CosineAnnealingLR::new(1.0, 0.0, 100_003, 0);
+        let reference = scheduler.clone();
+        for i in 0 .. 10_000_000 {
+            if i % 1_009 == 0 {
+                let error = (scheduler.get_lr(0.0) - reference.lr_at(i)).abs();
+                assert!(error < 1e-13, "Step {}: error {}", i, error);
+            }
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn min_fraction_follows_eta_0() {
+        let mut scheduler = CosineAnnealingLR::new(2.0, 0.0, 2, 1).with_min_fraction(0.5);
+        assert_eq!(scheduler.get_last_lr(), Some(2.0));
+        assert!(relative_eq!(scheduler.get_lr(0.0), 1.5));
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 1.0);
+        scheduler.set_eta_0(4.0);
+        assert_eq!(scheduler.get_lr(0.0), 2.0);
+        scheduler.perturb(0.5);
+        scheduler.set_eta_0(1.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.5);
+        scheduler.set_eta(1.0, 0.1);
+        scheduler.set_eta_0(2.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.1);
+        assert_eq!(scheduler.min_fraction(), None);
+    }
 }
\ No newline at end of file
@@ -1,7 +1,13 @@
-use crate::Scheduler;
+use crate::pbt::Perturbable;
+use crate::rotation::Rotation;
+use crate::semantics::Semantics;
+use crate::{epochs_to_steps, Bounded, ClosedForm, Cyclical, Scheduler};
 
 const PI: f64 = std::f64::consts::PI;
 
+/// Upper bound of `t_max`, which keeps the cycle length and the step counts within a cycle from overflowing.
+pub const MAX_T: usize = usize::MAX / 2;
+
 /// Changes the learning rate periodically with warmups.
 /// 
 /// # Examples
@@ -72,8 +78,16 @@ pub struct CosineAnnealingWarmRestarts {
     eta_0: f64,
     eta_1: f64,
     step_cur: usize,
+    cycle: usize,
+    // First period before the epoch alignment, which the builders realign from.
+    unaligned_t_0: usize,
+    t_0: usize,
     t_max: usize,
     t_mult: usize,
+    steps_per_epoch: usize,
+    rotation: Rotation,
+    last_lr: Option<f64>,
+    semantics: Semantics,
 }
 
 impl CosineAnnealingWarmRestarts {
@@ -83,7 +97,11 @@ impl CosineAnnealingWarmRestarts {
     /// The learning rate is reset to `eta_0` at the beginning of period, hence the name warm restarts.
     /// The length of period is given by `t_0`, and the period is multiplied by `t_mult` after every warm restarts.
     /// The parameters `t_0` and `t_mult` must be larger than 0. When 0 is provided, their values are replaced with 1.
+    /// The length of a period stops growing at `MAX_T`, so that it fits in `usize` however many restarts occur.
     /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
+    /// 
+    /// The cosine is updated incrementally at every `step` and recomputed periodically,
+    /// so the learning rate differs from `lr_at` by less than `1e-13 * |eta_0 - eta_1|`.
     pub fn new(
         eta_0: f64,
         eta_1: f64,
@@ -94,43 +112,260 @@ impl CosineAnnealingWarmRestarts {
         // When t_mult = 0 is given, replace it to 1 to prevent infinite loop.
         let t_mult = t_mult.max(1);
         // Aboid t_0 = 0 for the same reason as above.
-        let t_0 = t_0.max(1);
-        let (lr, step_cur, t_max) = if init_step == 0 {
-            (eta_0, 0, t_0)
-        } else {
-            let mut step = init_step;
-            let mut t_max = t_0;
-            while step > t_max {
-                step -= t_max + 1;
-                t_max *= t_mult;
-            }
-            let periodic_factor = periodic_factor(step, t_max);
-            let lr = (eta_0 - eta_1).mul_add(periodic_factor, eta_1);
-            (lr, step, t_max)
+        let t_0 = t_0.clamp(1, MAX_T);
+        Self::with_alignment(eta_0, eta_1, t_0, t_mult, 1, Semantics::Native, init_step)
+    }
+
+    fn with_alignment(
+        eta_0: f64,
+        eta_1: f64,
+        t_0: usize,
+        t_mult: usize,
+        steps_per_epoch: usize,
+        semantics: Semantics,
+        init_step: usize,
+    ) -> Self {
+        let unaligned_t_0 = t_0;
+        let t_0 = align(t_0, steps_per_epoch, semantics);
+        let (cycle, step_cur, t_max) = cycle_position(init_step, t_0, t_mult, steps_per_epoch, semantics);
+        let rotation = Rotation::new(phase(step_cur, t_max), phase(1, t_max));
+        let mut scheduler = CosineAnnealingWarmRestarts {
+            lr: eta_0, eta_0, eta_1, step_cur, cycle, unaligned_t_0, t_0, t_max, t_mult, steps_per_epoch, rotation, last_lr: None, semantics
         };
-        CosineAnnealingWarmRestarts { lr, eta_0, eta_1, step_cur, t_max, t_mult }
+        scheduler.lr = scheduler.lr_in_cycle(step_cur, t_max);
+        scheduler.last_lr = init_step.checked_sub(1).map(|step| scheduler.lr_at(step));
+        scheduler
+    }
+
+    /// Constructs a CosineAnnealingWarmRestarts instance whose first period is specified in epochs.
+    /// 
+    /// The parameter `t_0_epochs` may be fractional and is converted to the nearest number of steps with `steps_per_epoch`,
+    /// so later periods are exactly `t_mult` times as long in steps.
+    /// The other parameters are the same as `new`, and `init_step` is still counted in steps.
+    /// 
+    /// ```
+    /// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    /// # use lr_schedulers::ClosedForm;
+    /// // A period of 1.5 epochs of 10 steps, i.e. 15 steps.
+    /// let from_epochs = CosineAnnealingWarmRestarts::from_epochs(1.0, 0.0, 1.5, 2, 10, 0);
+    /// let from_steps = CosineAnnealingWarmRestarts::new(1.0, 0.0, 15, 2, 0);
+    /// for step in 0 .. 100 {
+    ///     assert_eq!(from_epochs.lr_at(step), from_steps.lr_at(step));
+    /// }
+    /// ```
+    pub fn from_epochs(
+        eta_0: f64,
+        eta_1: f64,
+        t_0_epochs: f64,
+        t_mult: usize,
+        steps_per_epoch: usize,
+        init_step: usize,
+    ) -> Self {
+        Self::new(eta_0, eta_1, epochs_to_steps(t_0_epochs, steps_per_epoch), t_mult, init_step)
+    }
+
+    /// Rounds the length of every cycle to a whole number of epochs of `steps_per_epoch` steps, keeping the step.
+    /// 
+    /// Each cycle, including the first one, is as long as the nearest multiple of `steps_per_epoch` to its length
+    /// without alignment, and at least one epoch, so that warm restarts coincide with epoch boundaries, e.g. validation points.
+    /// The length of the next cycle is computed from the rounded length of the current one.
+    /// A `steps_per_epoch` of 0 or 1 leaves the cycles as they are.
+    /// 
+    /// ```
+    /// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    /// # use lr_schedulers::{Cyclical, Scheduler};
+    /// // Cycles of 15 and 29 steps become 20 and 40 steps, i.e. 2 and 4 epochs of 10 steps.
+    /// let mut scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 14, 2, 0).with_epoch_alignment(10);
+    /// let mut restarts = Vec::new();
+    /// for step in 0 .. 100 {
+    ///     if step > 0 && scheduler.steps_into_cycle() == 0 {
+    ///         restarts.push(step);
+    ///     }
+    ///     scheduler.step(0.01);
+    /// }
+    /// assert_eq!(restarts, [20, 60]);
+    /// ```
+    pub fn with_epoch_alignment(self, steps_per_epoch: usize) -> Self {
+        let step = self.global_step();
+        let mut scheduler = Self::with_alignment(self.eta_0, self.eta_1, self.unaligned_t_0, self.t_mult, steps_per_epoch.max(1), self.semantics, step);
+        scheduler.last_lr = step.checked_sub(1).map(|step| scheduler.lr_at(step));
+        scheduler
+    }
+
+    /// Selects where cycles end, keeping the step. See [`Semantics`].
+    /// 
+    /// With `Semantics::PyTorchParity`, a cycle of `t_max` is `t_max` steps long and restarts before reaching `eta_1`,
+    /// as in PyTorch's CosineAnnealingWarmRestarts with `T_0` set to `t_0`.
+    /// 
+    /// ```
+    /// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    /// # use lr_schedulers::semantics::Semantics;
+    /// # use lr_schedulers::Cyclical;
+    /// let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 10, 2, 0).with_semantics(Semantics::PyTorchParity);
+    /// assert_eq!(scheduler.cycle_length(), 10);
+    /// ```
+    pub fn with_semantics(self, semantics: Semantics) -> Self {
+        let step = self.global_step();
+        let mut scheduler = Self::with_alignment(self.eta_0, self.eta_1, self.unaligned_t_0, self.t_mult, self.steps_per_epoch, semantics, step);
+        scheduler.last_lr = step.checked_sub(1).map(|step| scheduler.lr_at(step));
+        scheduler
+    }
+
+    /// Changes `eta_0` and `eta_1` without resetting the step.
+    pub fn set_eta(&mut self, eta_0: f64, eta_1: f64) {
+        self.eta_0 = eta_0;
+        self.eta_1 = eta_1;
+        self.lr = self.lr_in_cycle(self.step_cur, self.t_max);
+    }
+
+    /// Returns the number of steps taken from the beginning, saturating at `usize::MAX`.
+    fn global_step(&self) -> usize {
+        if self.t_mult == 1 {
+            return self.cycle.saturating_mul(span(self.t_0, self.semantics)).saturating_add(self.step_cur);
+        }
+        let mut step = self.step_cur;
+        let mut t_max = self.t_0;
+        for _ in 0 .. self.cycle {
+            step = step.saturating_add(span(t_max, self.semantics));
+            t_max = next_t_max(t_max, self.t_mult, self.steps_per_epoch, self.semantics);
+        }
+        step
+    }
+
+    fn lr_in_cycle(&self, step_cur: usize, t_max: usize) -> f64 {
+        if step_cur == 0 {
+            return self.eta_0;
+        }
+        let periodic_factor = periodic_factor(step_cur, t_max);
+        (self.eta_0 - self.eta_1).mul_add(periodic_factor, self.eta_1)
     }
 }
 
 impl Scheduler for CosineAnnealingWarmRestarts {
     fn step(&mut self, _loss: f64) {
+        self.last_lr = Some(self.lr);
         self.step_cur += 1;
-        while self.step_cur > self.t_max {
-            self.step_cur -= self.t_max + 1;
-            self.t_max *= self.t_mult;
+        if self.step_cur >= span(self.t_max, self.semantics) {
+            while self.step_cur >= span(self.t_max, self.semantics) {
+                self.step_cur -= span(self.t_max, self.semantics);
+                self.t_max = next_t_max(self.t_max, self.t_mult, self.steps_per_epoch, self.semantics);
+                self.cycle += 1;
+            }
+            self.rotation = Rotation::new(phase(self.step_cur, self.t_max), phase(1, self.t_max));
+        } else if self.rotation.needs_resync() {
+            self.rotation.resync(phase(self.step_cur, self.t_max));
+        } else {
+            self.rotation.advance();
         }
-        let periodic_factor = periodic_factor(self.step_cur, self.t_max);
-        self.lr = (self.eta_0 - self.eta_1).mul_add(periodic_factor, self.eta_1);
+        self.lr = if self.step_cur == 0 {
+            self.eta_0
+        } else {
+            let periodic_factor = 0.5 * (1.0 + self.rotation.cos());
+            (self.eta_0 - self.eta_1).mul_add(periodic_factor, self.eta_1)
+        };
     }
 
     fn get_lr(&self, _loss: f64) -> f64 {
         self.lr
     }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+}
+
+impl ClosedForm for CosineAnnealingWarmRestarts {
+    fn lr_at(&self, step: usize) -> f64 {
+        let (_, step_cur, t_max) = cycle_position(step, self.t_0, self.t_mult, self.steps_per_epoch, self.semantics);
+        self.lr_in_cycle(step_cur, t_max)
+    }
+}
+
+impl Cyclical for CosineAnnealingWarmRestarts {
+    fn current_cycle(&self) -> usize {
+        self.cycle
+    }
+
+    fn steps_into_cycle(&self) -> usize {
+        self.step_cur
+    }
+
+    /// Returns `t_max + 1`, since a cycle runs from `eta_0` at its first step to `eta_1` at its last step.
+    /// 
+    /// Returns `t_max` with `Semantics::PyTorchParity`.
+    fn cycle_length(&self) -> usize {
+        span(self.t_max, self.semantics)
+    }
+}
+
+/// Returns the number of steps in a cycle of `t_max`.
+fn span(t_max: usize, semantics: Semantics) -> usize {
+    match semantics {
+        Semantics::PyTorchParity => t_max,
+        Semantics::Native => t_max + 1,
+    }
+}
+
+/// Returns `t_max` of the cycle following a cycle of `t_max`.
+fn next_t_max(t_max: usize, t_mult: usize, steps_per_epoch: usize, semantics: Semantics) -> usize {
+    align(t_max.saturating_mul(t_mult).min(MAX_T), steps_per_epoch, semantics)
+}
+
+/// Rounds the cycle length of `t_max` to the nearest positive multiple of `steps_per_epoch`, within `MAX_T`.
+fn align(t_max: usize, steps_per_epoch: usize, semantics: Semantics) -> usize {
+    if steps_per_epoch <= 1 {
+        return t_max;
+    }
+    let extra = span(t_max, semantics) - t_max;
+    let epochs = (span(t_max, semantics).saturating_add(steps_per_epoch / 2) / steps_per_epoch).max(1);
+    (epochs.saturating_mul(steps_per_epoch) - extra).clamp(1, MAX_T)
+}
+
+/// Returns the index of the cycle containing `step`, the position in the cycle, and `t_max` of the cycle.
+fn cycle_position(step: usize, t_0: usize, t_mult: usize, steps_per_epoch: usize, semantics: Semantics) -> (usize, usize, usize) {
+    if t_mult == 1 {
+        // All cycles have the same length, so the position is found without iterating over them.
+        return (step / span(t_0, semantics), step % span(t_0, semantics), t_0);
+    }
+    let mut step = step;
+    let mut t_max = t_0;
+    let mut cycle = 0;
+    while step >= span(t_max, semantics) {
+        step -= span(t_max, semantics);
+        t_max = next_t_max(t_max, t_mult, steps_per_epoch, semantics);
+        cycle += 1;
+    }
+    (cycle, step, t_max)
+}
+
+/// Returns `eta_0` and `eta_1`, which are reached in every cycle.
+/// 
+/// With `Semantics::PyTorchParity`, cycles end before `eta_1`, which is only approached by growing cycles.
+/// Cycles of a fixed length give the learning rate at their last step instead.
+impl Bounded for CosineAnnealingWarmRestarts {
+    fn bounds(&self) -> (f64, f64) {
+        let last = match (self.semantics, self.t_mult) {
+            (Semantics::PyTorchParity, 1) => self.lr_in_cycle(self.t_0 - 1, self.t_0),
+            _ => self.eta_1,
+        };
+        (self.eta_0.min(last), self.eta_0.max(last))
+    }
+}
+
+impl Perturbable for CosineAnnealingWarmRestarts {
+    fn perturb(&mut self, factor: f64) {
+        self.eta_0 *= factor;
+        self.eta_1 *= factor;
+        self.lr *= factor;
+    }
+}
+
+fn phase(t: usize, t_max: usize) -> f64 {
+    (t as f64) * PI / (t_max as f64)
 }
 
 fn periodic_factor(t: usize, t_max: usize) -> f64 {
-    let phase = (t as f64) * PI / (t_max as f64);
-    0.5 * (1.0 + phase.cos())
+    0.5 * (1.0 + phase(t, t_max).cos())
 }
 
 #[cfg(test)]
@@ -204,4 +439,134 @@ mod tests {
             scheduler.step(0.0);
         }
     }
+
+    #[test]
+    fn random_access() {
+        for t_mult in [1, 2, 3] {
+            let mut scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.1, 2, t_mult, 0);
+            let reference = scheduler.clone();
+            for i in 0 .. 40 {
+                let lr = scheduler.get_lr(0.0);
+                assert!(relative_eq!(lr, reference.lr_at(i), epsilon = 1e-12), "t_mult {}, Step {}", t_mult, i);
+                scheduler.step(0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        for init_step in [0, 4] {
+            let mut scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 1, 2, init_step);
+            // Cycles have 2, 3 and 5 steps.
+            let expected = [(0, 0, 2), (0, 1, 2), (1, 0, 3), (1, 1, 3), (1, 2, 3), (2, 0, 5), (2, 1, 5)];
+            for (i, (cycle, steps_into_cycle, cycle_length)) in expected.iter().enumerate().skip(init_step) {
+                assert_eq!(scheduler.current_cycle(), *cycle, "Step {}", i);
+                assert_eq!(scheduler.restarts_so_far(), *cycle, "Step {}", i);
+                assert_eq!(scheduler.steps_into_cycle(), *steps_into_cycle, "Step {}", i);
+                assert_eq!(scheduler.cycle_length(), *cycle_length, "Step {}", i);
+                scheduler.step(0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_drift() {
+        let mut scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 1_001, 3, 0);
+        let reference = scheduler.clone();
+        for i in 0 .. 300_000 {
+            let error = (scheduler.get_lr(0.0) - reference.lr_at(i)).abs();
+            assert!(error < 1e-13, "Step {}: error {}", i, error);
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn align_to_epochs() {
+        for init_step in [0, 25, 70] {
+            let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.1, 10, 3, init_step).with_epoch_alignment(4);
+            let reference = CosineAnnealingWarmRestarts::new(1.0, 0.1, 11, 3, 0).with_epoch_alignment(4);
+            // Cycles have 12, 36 and 108 steps.
+            let (cycle, steps_into_cycle, cycle_length) = match init_step {
+                0 => (0, 0, 12),
+                25 => (1, 13, 36),
+                _ => (2, 22, 108),
+            };
+            assert_eq!(scheduler.current_cycle(), cycle, "Step {}", init_step);
+            assert_eq!(scheduler.steps_into_cycle(), steps_into_cycle, "Step {}", init_step);
+            assert_eq!(scheduler.cycle_length(), cycle_length, "Step {}", init_step);
+            assert_eq!(scheduler.get_last_lr(), init_step.checked_sub(1).map(|step| reference.lr_at(step)));
+            let mut scheduler = scheduler;
+            for i in init_step .. 200 {
+                let lr = scheduler.get_lr(0.0);
+                assert!(relative_eq!(lr, reference.lr_at(i), epsilon = 1e-12), "Step {}: left: {}, right: {}", i, lr, reference.lr_at(i));
+                assert_eq!(scheduler.cycle_length() % 4, 0, "Step {}", i);
+                scheduler.step(0.0);
+            }
+        }
+        // Short cycles are at least an epoch long.
+        assert_eq!(CosineAnnealingWarmRestarts::new(1.0, 0.1, 1, 1, 0).with_epoch_alignment(8).cycle_length(), 8);
+    }
+
+    #[test]
+    fn pytorch_parity() {
+        for (t_mult, init_step) in [(1, 0), (2, 0), (2, 5), (3, 11)] {
+            let mut scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.1, 3, t_mult, init_step).with_semantics(Semantics::PyTorchParity);
+            // PyTorch restarts when T_cur reaches T_i.
+            let (mut t_cur, mut t_i) = (0, 3);
+            for i in 0 .. 60 {
+                if i >= init_step {
+                    let expected = 0.1 + 0.9 * 0.5 * (1.0 + (PI * t_cur as f64 / t_i as f64).cos());
+                    let lr = scheduler.get_lr(0.0);
+                    assert!(relative_eq!(lr, expected, epsilon = 1e-12), "t_mult {}, Step {}: left: {}, right: {}", t_mult, i, lr, expected);
+                    assert!(relative_eq!(lr, scheduler.lr_at(i), epsilon = 1e-12), "t_mult {}, Step {}", t_mult, i);
+                    assert_eq!((scheduler.steps_into_cycle(), scheduler.cycle_length()), (t_cur, t_i), "t_mult {}, Step {}", t_mult, i);
+                    scheduler.step(0.0);
+                }
+                t_cur += 1;
+                if t_cur == t_i {
+                    t_cur = 0;
+                    t_i *= t_mult;
+                }
+            }
+        }
+        let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 2, 1, 0).with_semantics(Semantics::PyTorchParity);
+        assert!(relative_eq!(scheduler.bounds().0, 0.5) && scheduler.bounds().1 == 1.0);
+        let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 10, 1, 0).with_semantics(Semantics::PyTorchParity).with_epoch_alignment(4);
+        assert_eq!(scheduler.cycle_length(), 12);
+    }
+
+    #[test]
+    fn builders_in_any_order() {
+        for init_step in [0, 7, 30] {
+            let aligned_first = CosineAnnealingWarmRestarts::new(1.0, 0.0, 5, 2, init_step)
+                .with_epoch_alignment(4)
+                .with_semantics(Semantics::PyTorchParity);
+            let semantics_first = CosineAnnealingWarmRestarts::new(1.0, 0.0, 5, 2, init_step)
+                .with_semantics(Semantics::PyTorchParity)
+                .with_epoch_alignment(4);
+            assert_eq!(aligned_first.cycle_length(), semantics_first.cycle_length(), "Step {}", init_step);
+            for i in 0 .. 100 {
+                assert_eq!(aligned_first.lr_at(i), semantics_first.lr_at(i), "Step {}", i);
+            }
+        }
+        assert_eq!(CosineAnnealingWarmRestarts::new(1.0, 0.0, 5, 1, 0).with_epoch_alignment(4).with_semantics(Semantics::PyTorchParity).cycle_length(), 4);
+        // Realigning starts from the cycles without alignment.
+        let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 14, 1, 0).with_epoch_alignment(10).with_epoch_alignment(1);
+        assert_eq!(scheduler.cycle_length(), CosineAnnealingWarmRestarts::new(1.0, 0.0, 14, 1, 0).cycle_length());
+    }
+
+    #[test]
+    fn set_eta_keeps_step() {
+        let mut scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 4, 2, 0);
+        for _ in 0 .. 2 {
+            scheduler.step(0.0);
+        }
+        scheduler.set_eta(2.0, 1.0);
+        let reference = CosineAnnealingWarmRestarts::new(2.0, 1.0, 4, 2, 0);
+        for i in 2 .. 20 {
+            let lr = scheduler.get_lr(0.0);
+            assert!(relative_eq!(lr, reference.lr_at(i), epsilon = 1e-12), "Step {}: left: {}, right: {}", i, lr, reference.lr_at(i));
+            scheduler.step(0.0);
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,248 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::Scheduler;
+
+/// Rank whose scheduler drives the learning rate of every rank.
+pub const MAIN_RANK: usize = 0;
+
+/// Communication primitives required by [`DistributedScheduler`].
+/// 
+/// Implement this trait on top of the collective operations of your training framework (e.g. NCCL or MPI).
+/// Both methods are collective: every rank must call them in the same order.
+pub trait Transport {
+    /// Returns the rank of this process.
+    fn rank(&self) -> usize;
+    /// Sends `value` from the main rank to all ranks and returns the value received.
+    /// 
+    /// The argument is ignored on ranks other than the main rank.
+    fn broadcast(&mut self, value: f64) -> f64;
+    /// Collects `value` from all ranks, ordered by rank.
+    fn all_gather(&mut self, value: u64) -> Vec<u64>;
+}
+
+/// Keeps a scheduler consistent across the ranks of a distributed training job.
+/// 
+/// Only the main rank advances the wrapped scheduler, so metric-driven schedulers make a single decision from the main rank's loss.
+/// The resulting learning rate is broadcast to the other ranks at every `step`.
+/// 
+/// # Examples
+/// 
+/// A transport for a single process simply returns its inputs:
+/// 
+/// ```
+/// # use lr_schedulers::distributed::{DistributedScheduler, Transport};
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::Scheduler;
+/// struct SingleProcess;
+/// 
+/// impl Transport for SingleProcess {
+///     fn rank(&self) -> usize { 0 }
+///     fn broadcast(&mut self, value: f64) -> f64 { value }
+///     fn all_gather(&mut self, value: u64) -> Vec<u64> { vec![value] }
+/// }
+/// 
+/// let mut scheduler = DistributedScheduler::new(ExponentialLR::new(2.0, 0.5, 0), SingleProcess);
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 1.0);
+/// assert!(scheduler.verify_steps().is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct DistributedScheduler<S, T> {
+    scheduler: S,
+    transport: T,
+    lr: f64,
+    last_lr: Option<f64>,
+    step: u64,
+}
+
+impl<S: Scheduler, T: Transport> DistributedScheduler<S, T> {
+    /// Constructs a DistributedScheduler instance.
+    /// 
+    /// Every rank must construct `scheduler` with the same parameters, since the initial learning rate is not communicated.
+    pub fn new(scheduler: S, transport: T) -> Self {
+        let lr = scheduler.get_lr(0.0);
+        DistributedScheduler { scheduler, transport, lr, last_lr: None, step: 0 }
+    }
+
+    /// Returns true if this process is the main rank.
+    pub fn is_main(&self) -> bool {
+        self.transport.rank() == MAIN_RANK
+    }
+
+    /// Returns the number of steps processed on this rank.
+    pub fn steps(&self) -> u64 {
+        self.step
+    }
+
+    /// Returns the wrapped scheduler.
+    /// 
+    /// The scheduler is only advanced on the main rank.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+
+    /// Checks that all ranks have processed the same number of steps.
+    /// 
+    /// This is a collective operation.
+    pub fn verify_steps(&mut self) -> Result<(), StepMismatch> {
+        let steps = self.transport.all_gather(self.step);
+        if steps.iter().all(|&s| s == self.step) {
+            Ok(())
+        } else {
+            Err(StepMismatch { rank: self.transport.rank(), steps })
+        }
+    }
+}
+
+impl<S: Scheduler, T: Transport> Scheduler for DistributedScheduler<S, T> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.lr);
+        self.step += 1;
+        let lr = if self.is_main() {
+            self.scheduler.step(loss);
+            self.scheduler.get_lr(loss)
+        } else {
+            self.lr
+        };
+        self.lr = self.transport.broadcast(lr);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the remaining steps of the wrapped scheduler on the main rank, or None on the other ranks,
+    /// where it is not advanced.
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps().filter(|_| self.is_main())
+    }
+
+    /// Returns the progress of the wrapped scheduler on the main rank, or None on the other ranks.
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress().filter(|_| self.is_main())
+    }
+}
+
+/// Error returned when ranks disagree on the number of processed steps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepMismatch {
+    /// Rank that detected the mismatch.
+    pub rank: usize,
+    /// Step counts of all ranks, ordered by rank.
+    pub steps: Vec<u64>,
+}
+
+impl fmt::Display for StepMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step counts differ across ranks (seen from rank {}): {:?}", self.rank, self.steps)
+    }
+}
+
+impl Error for StepMismatch {}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier, Mutex};
+    use std::thread;
+    use crate::exponential::ExponentialLR;
+    use crate::Scheduler;
+    use super::*;
+
+    /// Transport between threads of a single process.
+    struct ThreadTransport {
+        rank: usize,
+        barrier: Arc<Barrier>,
+        lr: Arc<Mutex<f64>>,
+        steps: Arc<Mutex<Vec<u64>>>,
+    }
+
+    fn thread_group(size: usize) -> Vec<ThreadTransport> {
+        let barrier = Arc::new(Barrier::new(size));
+        let lr = Arc::new(Mutex::new(0.0));
+        let steps = Arc::new(Mutex::new(vec![0; size]));
+        (0 .. size)
+            .map(|rank| ThreadTransport {
+                rank,
+                barrier: Arc::clone(&barrier),
+                lr: Arc::clone(&lr),
+                steps: Arc::clone(&steps),
+            })
+            .collect()
+    }
+
+    impl Transport for ThreadTransport {
+        fn rank(&self) -> usize {
+            self.rank
+        }
+
+        fn broadcast(&mut self, value: f64) -> f64 {
+            if self.rank == MAIN_RANK {
+                *self.lr.lock().unwrap() = value;
+            }
+            self.barrier.wait();
+            let value = *self.lr.lock().unwrap();
+            self.barrier.wait();
+            value
+        }
+
+        fn all_gather(&mut self, value: u64) -> Vec<u64> {
+            self.steps.lock().unwrap()[self.rank] = value;
+            self.barrier.wait();
+            let steps = self.steps.lock().unwrap().clone();
+            self.barrier.wait();
+            steps
+        }
+    }
+
+    #[test]
+    fn lr_follows_main_rank() {
+        let handles: Vec<_> = thread_group(3)
+            .into_iter()
+            .map(|transport| {
+                thread::spawn(move || {
+                    // Only the main rank has the expected parameters.
+                    let gamma = if transport.rank == MAIN_RANK { 0.5 } else { 0.1 };
+                    let mut scheduler = DistributedScheduler::new(ExponentialLR::new(2.0, gamma, 0), transport);
+                    let mut learning_rates = Vec::new();
+                    for _ in 0 .. 4 {
+                        scheduler.step(0.0);
+                        learning_rates.push(scheduler.get_lr(0.0));
+                    }
+                    assert!(scheduler.verify_steps().is_ok());
+                    learning_rates
+                })
+            })
+            .collect();
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), [1.0, 0.5, 0.25, 0.125]);
+        }
+    }
+
+    #[test]
+    fn detect_step_mismatch() {
+        let handles: Vec<_> = thread_group(2)
+            .into_iter()
+            .map(|transport| {
+                thread::spawn(move || {
+                    let rank = transport.rank;
+                    let mut scheduler = DistributedScheduler::new(ExponentialLR::new(2.0, 0.5, 0), transport);
+                    scheduler.step(0.0);
+                    // Simulate a rank that skipped a step by bumping the counter locally.
+                    if rank == 1 {
+                        scheduler.step += 1;
+                    }
+                    scheduler.verify_steps()
+                })
+            })
+            .collect();
+        for (rank, handle) in handles.into_iter().enumerate() {
+            let err = handle.join().unwrap().unwrap_err();
+            assert_eq!(err, StepMismatch { rank, steps: vec![1, 2] });
+        }
+    }
+}
\ No newline at end of file
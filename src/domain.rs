@@ -0,0 +1,214 @@
+use crate::{ClosedForm, Scheduler};
+
+/// Unit in which training progress is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Domain {
+    /// Optimizer steps.
+    Steps,
+    /// Training samples.
+    Samples,
+    /// Tokens.
+    Tokens,
+}
+
+/// Drives a step-indexed scheduler by the number of samples or tokens processed.
+/// 
+/// One step of the wrapped scheduler corresponds to `units_per_step` units of the domain.
+/// The wrapped scheduler is advanced once for every full `units_per_step` units reported by `step_by`,
+/// and the remainder is carried over to the next call, so variable batch sizes do not shift the schedule.
+/// 
+/// A [`ClosedForm`] scheduler can instead be driven continuously with `continuous`, so the learning rate also changes
+/// within a step, e.g. for a token budget much finer than the batch size.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::domain::{Domain, DomainScheduler};
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::Scheduler;
+/// // Halve the learning rate every 1000 tokens.
+/// let mut scheduler = DomainScheduler::new(ExponentialLR::new(1.0, 0.5, 0), Domain::Tokens, 1000);
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step_by(600, 0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 1.0);
+/// scheduler.step_by(600, 0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 0.5);
+/// assert_eq!(scheduler.fractional_step(), 1.2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DomainScheduler<S> {
+    scheduler: S,
+    domain: Domain,
+    units_per_step: u64,
+    units: u64,
+    steps: u64,
+    continuous: Option<fn(&S, f64) -> f64>,
+    horizon: Option<usize>,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> DomainScheduler<S> {
+    /// Constructs a DomainScheduler instance.
+    /// 
+    /// The parameter `units_per_step` must be larger than 0. When 0 is provided, its value is replaced with 1.
+    pub fn new(scheduler: S, domain: Domain, units_per_step: u64) -> Self {
+        DomainScheduler {
+            scheduler,
+            domain,
+            units_per_step: units_per_step.max(1),
+            units: 0,
+            steps: 0,
+            continuous: None,
+            horizon: None,
+            last_lr: None,
+        }
+    }
+
+    /// Reports that `units` units have been processed and advances the wrapped scheduler accordingly.
+    pub fn step_by(&mut self, units: u64, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.units = self.units.saturating_add(units);
+        let target = self.units / self.units_per_step;
+        if self.continuous.is_some() {
+            self.steps = target;
+        }
+        while self.steps < target {
+            self.scheduler.step(loss);
+            self.steps += 1;
+        }
+    }
+
+    /// Returns the domain of this scheduler.
+    pub fn domain(&self) -> Domain {
+        self.domain
+    }
+
+    /// Returns the number of units processed so far.
+    pub fn units(&self) -> u64 {
+        self.units
+    }
+
+    /// Returns the progress measured in steps of the wrapped scheduler, including the fraction of the current step.
+    pub fn fractional_step(&self) -> f64 {
+        self.units as f64 / self.units_per_step as f64
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: ClosedForm> DomainScheduler<S> {
+    /// Constructs a DomainScheduler instance whose learning rate is `scheduler.lr_at_f64(fractional_step)`.
+    /// 
+    /// The wrapped scheduler is never advanced, so any number of units is processed in constant time,
+    /// and the steps are counted from 0 regardless of the step of `scheduler`.
+    /// 
+    /// ```
+    /// # use lr_schedulers::domain::{Domain, DomainScheduler};
+    /// # use lr_schedulers::exponential::ExponentialLR;
+    /// # use lr_schedulers::Scheduler;
+    /// let mut scheduler = DomainScheduler::continuous(ExponentialLR::new(1.0, 0.25, 0), Domain::Tokens, 1000);
+    /// // Note: loss value is not used in this scheduler.
+    /// scheduler.step_by(500, 0.01);
+    /// assert_eq!(scheduler.get_lr(0.01), 0.5);
+    /// ```
+    pub fn continuous(scheduler: S, domain: Domain, units_per_step: u64) -> Self {
+        DomainScheduler {
+            continuous: Some(|scheduler: &S, t| scheduler.lr_at_f64(t)),
+            horizon: scheduler.horizon(),
+            ..DomainScheduler::new(scheduler, domain, units_per_step)
+        }
+    }
+}
+
+impl<S: Scheduler> Scheduler for DomainScheduler<S> {
+    /// Proceeds the progress by `units_per_step` units, i.e. by exactly one step of the wrapped scheduler.
+    fn step(&mut self, loss: f64) {
+        self.step_by(self.units_per_step, loss);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        match self.continuous {
+            Some(lr_at_f64) => lr_at_f64(&self.scheduler, self.fractional_step()),
+            None => self.scheduler.get_lr(loss),
+        }
+    }
+
+    /// Returns the learning rate before the most recent `step` or `step_by`.
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the number of full steps left in the wrapped scheduler.
+    fn remaining_steps(&self) -> Option<usize> {
+        match self.continuous {
+            Some(_) => self.horizon.map(|horizon| horizon.saturating_sub(usize::try_from(self.steps).unwrap_or(usize::MAX))),
+            None => self.scheduler.remaining_steps(),
+        }
+    }
+
+    /// Returns the completed fraction of the wrapped scheduler, including the fraction of the current step when driven continuously.
+    fn progress(&self) -> Option<f64> {
+        match self.continuous {
+            Some(_) => self.horizon.map(|horizon| if horizon == 0 { 1.0 } else { (self.fractional_step() / horizon as f64).min(1.0) }),
+            None => self.scheduler.progress(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use crate::Scheduler;
+    use super::*;
+
+    #[test]
+    fn variable_batch_sizes() {
+        let mut scheduler = DomainScheduler::new(
+            LinearLR::new(1.0, 0.0, 1.0, 4, 0), Domain::Samples, 32
+        );
+        let batch_sizes = [16, 16, 48, 8, 8, 64];
+        let expected_lrs = [0.0, 0.25, 0.5, 0.5, 0.75, 1.0];
+        for (i, (batch_size, exp_lr)) in batch_sizes.iter().zip(expected_lrs).enumerate() {
+            let prev_lr = scheduler.get_lr(0.0);
+            // Proceed with dummy loss.
+            scheduler.step_by(*batch_size, 0.0);
+            let lr = scheduler.get_lr(0.0);
+            assert_eq!(lr, exp_lr, "Batch {}", i);
+            assert_eq!(scheduler.get_last_lr(), Some(prev_lr), "Batch {}", i);
+        }
+        assert_eq!(scheduler.units(), 160);
+    }
+
+    #[test]
+    fn plain_step_matches_inner() {
+        let mut scheduler = DomainScheduler::new(
+            LinearLR::new(1.0, 0.0, 1.0, 4, 0), Domain::Tokens, 1024
+        );
+        let mut reference = LinearLR::new(1.0, 0.0, 1.0, 4, 0);
+        for i in 0 .. 6 {
+            assert_eq!(scheduler.get_lr(0.0), reference.get_lr(0.0), "Step {}", i);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+            reference.step(0.0);
+        }
+        assert_eq!(scheduler.fractional_step(), 6.0);
+    }
+
+    #[test]
+    fn continuous_progress() {
+        let mut scheduler = DomainScheduler::continuous(LinearLR::new(1.0, 0.0, 1.0, 4, 0), Domain::Tokens, 1000);
+        let units = [250, 250, 1500, u64::MAX / 2];
+        let expected_lrs = [0.0625, 0.125, 0.5, 1.0];
+        for (i, (unit, exp_lr)) in units.iter().zip(expected_lrs).enumerate() {
+            let prev_lr = scheduler.get_lr(0.0);
+            scheduler.step_by(*unit, 0.0);
+            assert_eq!(scheduler.get_lr(0.0), exp_lr, "Call {}", i);
+            assert_eq!(scheduler.get_last_lr(), Some(prev_lr), "Call {}", i);
+        }
+        // The wrapped scheduler is not advanced one step at a time.
+        assert_eq!(scheduler.inner().get_lr(0.0), 0.0);
+    }
+}
\ No newline at end of file
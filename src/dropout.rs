@@ -0,0 +1,118 @@
+use crate::rng::SchedulerRng;
+use crate::Scheduler;
+
+/// Learning rate dropout: replaces the scheduled learning rate with a reduced one at random steps.
+/// 
+/// At each step, with probability `p`, the learning rate is the scheduled one multiplied by `factor`, e.g. 0 to skip the update.
+/// Whether a step is dropped depends only on `seed` and the step, so a run resumed with the same `seed` and `init_step`
+/// reproduces the same mask without storing any random state.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::dropout::LrDropout;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = LrDropout::new(ConstantLR::new(1.0, 1.0, 0, 0), 0.5, 0.0, 42, 0);
+/// let mut resumed = LrDropout::new(ConstantLR::new(1.0, 1.0, 0, 0), 0.5, 0.0, 42, 10);
+/// for _ in 0 .. 10 {
+///     // Note: loss value is not used in this scheduler.
+///     scheduler.step(0.01);
+/// }
+/// for _ in 0 .. 10 {
+///     assert_eq!(scheduler.get_lr(0.01), resumed.get_lr(0.01));
+///     scheduler.step(0.01);
+///     resumed.step(0.01);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LrDropout<S> {
+    scheduler: S,
+    p: f64,
+    factor: f64,
+    rng: SchedulerRng,
+    step: usize,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> LrDropout<S> {
+    /// Constructs a LrDropout instance.
+    /// 
+    /// The parameter `init_step` is the number of steps already taken by `scheduler`, which determines the mask of the following steps.
+    pub fn new(scheduler: S, p: f64, factor: f64, seed: u64, init_step: usize) -> Self {
+        LrDropout { scheduler, p, factor, rng: SchedulerRng::new(seed), step: init_step, last_lr: None }
+    }
+
+    /// Returns true if the learning rate of `step` is reduced.
+    pub fn is_dropped(&self, step: usize) -> bool {
+        self.rng.unit_at(step as u64) < self.p
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for LrDropout<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.scheduler.step(loss);
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        let lr = self.scheduler.get_lr(loss);
+        if self.is_dropped(self.step) {
+            self.factor * lr
+        } else {
+            lr
+        }
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant::ConstantLR;
+    use super::*;
+
+    #[test]
+    fn drop_rate() {
+        let mut scheduler = LrDropout::new(ConstantLR::new(1.0, 1.0, 0, 0), 0.3, 0.1, 7, 0);
+        let steps = 10_000;
+        let mut dropped = 0;
+        for _ in 0 .. steps {
+            let lr = scheduler.get_lr(0.0);
+            if lr == 0.1 {
+                dropped += 1;
+            } else {
+                assert_eq!(lr, 1.0);
+            }
+            scheduler.step(0.0);
+        }
+        let rate = dropped as f64 / steps as f64;
+        assert!((rate - 0.3).abs() < 0.02, "rate: {}", rate);
+    }
+
+    #[test]
+    fn extreme_probabilities() {
+        let never = LrDropout::new(ConstantLR::new(1.0, 1.0, 0, 0), 0.0, 0.0, 1, 0);
+        let always = LrDropout::new(ConstantLR::new(1.0, 1.0, 0, 0), 1.0, 0.0, 1, 0);
+        for step in 0 .. 100 {
+            assert!(!never.is_dropped(step));
+            assert!(always.is_dropped(step));
+        }
+    }
+}
\ No newline at end of file
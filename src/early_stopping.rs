@@ -0,0 +1,108 @@
+use crate::plateau::{Mode, Plateau, ThresholdMode};
+use crate::semantics::Semantics;
+
+/// Signals when training should stop because the monitored metric stopped improving.
+/// 
+/// The improvement and patience semantics are identical to those of [`Plateau`]:
+/// training should stop once the number of consecutive epochs without improvement exceeds `patience`.
+/// Once triggered, the signal stays on.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::early_stopping::EarlyStopping;
+/// # use lr_schedulers::plateau::{Mode, ThresholdMode};
+/// let mut early_stopping = EarlyStopping::new(Mode::Min, 2, 1e-4, ThresholdMode::Rel);
+/// let mut stopped_at = None;
+/// for (epoch, loss) in [1.0, 0.8, 0.8, 0.9, 0.85, 0.7].into_iter().enumerate() {
+///     early_stopping.step(loss);
+///     if early_stopping.should_stop() {
+///         stopped_at = Some(epoch);
+///         break;
+///     }
+/// }
+/// assert_eq!(stopped_at, Some(4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EarlyStopping {
+    plateau: Plateau,
+    stopped: bool,
+}
+
+impl EarlyStopping {
+    /// Constructs an EarlyStopping instance.
+    pub fn new(mode: Mode, patience: usize, threshold: f64, threshold_mode: ThresholdMode) -> Self {
+        EarlyStopping {
+            plateau: Plateau::new(mode, patience, threshold, threshold_mode),
+            stopped: false,
+        }
+    }
+
+    /// Ignores the first `warmup_epochs` metrics entirely. See [`Plateau::with_warmup`].
+    pub fn with_warmup(self, warmup_epochs: usize) -> Self {
+        EarlyStopping { plateau: self.plateau.with_warmup(warmup_epochs), ..self }
+    }
+
+    /// Selects how a relative threshold treats a negative best value. See [`Plateau::with_semantics`].
+    pub fn with_semantics(self, semantics: Semantics) -> Self {
+        EarlyStopping { plateau: self.plateau.with_semantics(semantics), ..self }
+    }
+
+    /// Replays the metrics of past epochs, e.g. to resume from a checkpoint that stores only the metric history.
+    /// 
+    /// Metrics after the signal are ignored, as in `step`.
+    pub fn with_history(mut self, metrics: &[f64]) -> Self {
+        for &metric in metrics {
+            self.step(metric);
+        }
+        self
+    }
+
+    /// Records the metric of an epoch.
+    pub fn step(&mut self, metric: f64) {
+        if !self.stopped {
+            self.stopped = self.plateau.update(metric);
+        }
+    }
+
+    /// Returns true if training should stop.
+    pub fn should_stop(&self) -> bool {
+        self.stopped
+    }
+
+    /// Returns the underlying plateau detector.
+    pub fn plateau(&self) -> &Plateau {
+        &self.plateau
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stop_after_patience() {
+        let mut early_stopping = EarlyStopping::new(Mode::Min, 1, 0.0, ThresholdMode::Abs);
+        let losses = [1.0, 0.5, 0.6, 0.4, 0.4, 0.4, 0.1];
+        let expected = [false, false, false, false, false, true, true];
+        for (i, (loss, exp)) in losses.iter().zip(expected).enumerate() {
+            early_stopping.step(*loss);
+            assert_eq!(early_stopping.should_stop(), exp, "Epoch {}", i);
+        }
+        // Improvements after stopping are ignored.
+        assert_eq!(early_stopping.plateau().best(), 0.4);
+        let resumed = EarlyStopping::new(Mode::Min, 1, 0.0, ThresholdMode::Abs).with_history(&losses);
+        assert!(resumed.should_stop());
+        assert_eq!(resumed.plateau().best(), 0.4);
+    }
+
+    #[test]
+    fn accuracy_mode() {
+        let mut early_stopping = EarlyStopping::new(Mode::Max, 0, 0.01, ThresholdMode::Rel);
+        early_stopping.step(0.5);
+        early_stopping.step(0.6);
+        assert!(!early_stopping.should_stop());
+        early_stopping.step(0.603);
+        assert!(early_stopping.should_stop());
+    }
+}
\ No newline at end of file
@@ -1,4 +1,5 @@
-use crate::Scheduler;
+use crate::pbt::Perturbable;
+use crate::{Bounded, ClosedForm, PiecewiseMonotone, Scheduler};
 
 /// Changes the learning rate geometrically.
 /// 
@@ -50,8 +51,12 @@ use crate::Scheduler;
 /// ```
 #[derive(Debug, Clone)]
 pub struct ExponentialLR {
-    lr: f64,
+    base_lr: f64,
     gamma: f64,
+    step: usize,
+    decay_steps: usize,
+    staircase: bool,
+    gamma_per_step: f64,
 }
 
 impl ExponentialLR {
@@ -61,24 +66,170 @@ impl ExponentialLR {
     /// lr_i = `gamma` * lr_{i-1}.
     /// 
     /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
-    pub fn new(base_lr: f64, gamma: f64, init_step: usize) -> Self {
-        let lr = base_lr * gamma.powi(init_step as i32);
-        ExponentialLR { lr, gamma }
+    /// 
+    /// The learning rate is not accumulated by repeated multiplication, which drifts from the exact value after many steps.
+    /// Instead, it is evaluated as `base_lr * gamma.powf(i)` from the step count,
+    /// so the relative error stays within a few ulps (about 1e-15) however long the training runs.
+    /// 
+    /// This is a `const fn`, so the scheduler can be constructed in `const` and `static` items.
+    pub const fn new(base_lr: f64, gamma: f64, init_step: usize) -> Self {
+        ExponentialLR { base_lr, gamma, step: init_step, decay_steps: 1, staircase: false, gamma_per_step: gamma }
+    }
+
+    /// Applies the decay by `gamma` every `decay_steps` steps, as TensorFlow's ExponentialDecay.
+    /// 
+    /// The learning rate at step i is `base_lr * gamma^(i / decay_steps)`. If `staircase` is true, `i / decay_steps`
+    /// is an integer division, so the learning rate changes every `decay_steps` steps; otherwise it decays continuously.
+    /// A `decay_steps` of 0 is treated as 1. A negative `gamma` gives NaN for the continuous decay between the multiples of `decay_steps`.
+    /// 
+    /// ```
+    /// # use lr_schedulers::exponential::ExponentialLR;
+    /// # use lr_schedulers::ClosedForm;
+    /// let staircase = ExponentialLR::new(1.0, 0.5, 0).with_decay_steps(2, true);
+    /// assert_eq!([0, 1, 2, 3, 4].map(|step| staircase.lr_at(step)), [1.0, 1.0, 0.5, 0.5, 0.25]);
+    /// let continuous = ExponentialLR::new(1.0, 0.25, 0).with_decay_steps(2, false);
+    /// assert_eq!([0, 1, 2].map(|step| continuous.lr_at(step)), [1.0, 0.5, 0.25]);
+    /// ```
+    pub fn with_decay_steps(self, decay_steps: usize, staircase: bool) -> Self {
+        let mut scheduler = ExponentialLR { decay_steps: decay_steps.max(1), staircase, ..self };
+        scheduler.set_gamma(scheduler.gamma);
+        scheduler
+    }
+
+    /// Returns the learning rates of the first `N` steps.
+    /// 
+    /// This is a `const fn`, so short schedules can be baked into static arrays at compile time.
+    /// Since `powf` is not available in `const fn`, the powers are computed by repeated squaring,
+    /// whose relative error is about `i * 1e-16` at step i, or `i / decay_steps * 1e-16` with the staircase decay.
+    /// 
+    /// ```
+    /// # use lr_schedulers::exponential::ExponentialLR;
+    /// const LRS: [f64; 4] = ExponentialLR::new(1.0, 0.5, 0).table::<4>();
+    /// assert_eq!(LRS, [1.0, 0.5, 0.25, 0.125]);
+    /// ```
+    pub const fn table<const N: usize>(&self) -> [f64; N] {
+        let mut lrs = [0.0; N];
+        let mut i = 0;
+        while i < N {
+            lrs[i] = if self.staircase {
+                self.base_lr * pow(self.gamma, i / self.decay_steps)
+            } else {
+                self.base_lr * pow(self.gamma_per_step, i)
+            };
+            i += 1;
+        }
+        lrs
+    }
+
+    /// Changes `base_lr` without resetting the step.
+    pub fn set_base_lr(&mut self, base_lr: f64) {
+        self.base_lr = base_lr;
+    }
+
+    /// Changes `gamma` without resetting the step.
+    /// 
+    /// The learning rate of every step, including the past ones, is `base_lr * gamma^i` with the new `gamma`.
+    pub fn set_gamma(&mut self, gamma: f64) {
+        self.gamma = gamma;
+        self.gamma_per_step = if self.decay_steps == 1 { gamma } else { gamma.powf(1.0 / self.decay_steps as f64) };
+    }
+
+    /// Returns the current step, counted from 0 in the same way as `init_step`.
+    pub(crate) fn current_step(&self) -> usize {
+        self.step
     }
 }
 
+/// Computes `base^exp` by repeated squaring.
+/// 
+/// Unlike `f64::powi`, this is usable in `const fn` and accepts any `usize` exponent.
+const fn pow(base: f64, exp: usize) -> f64 {
+    let mut result = 1.0;
+    let mut base = base;
+    let mut exp = exp;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exp >>= 1;
+    }
+    result
+}
+
 impl Scheduler for ExponentialLR {
     fn step(&mut self, _loss: f64) {
-        self.lr *= self.gamma;
+        self.step = self.step.saturating_add(1);
     }
 
     fn get_lr(&self, _loss: f64) -> f64 {
-        self.lr
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+}
+
+impl ClosedForm for ExponentialLR {
+    fn lr_at(&self, step: usize) -> f64 {
+        let exponent = if self.staircase {
+            (step / self.decay_steps) as f64
+        } else {
+            step as f64 / self.decay_steps as f64
+        };
+        self.base_lr * self.gamma.powf(exponent)
+    }
+
+    /// Evaluates `base_lr * gamma^(t / decay_steps)`, whose exponent is rounded down with the staircase decay.
+    fn lr_at_f64(&self, t: f64) -> f64 {
+        let exponent = t.max(0.0) / self.decay_steps as f64;
+        let exponent = if self.staircase { exponent.floor() } else { exponent };
+        self.base_lr * self.gamma.powf(exponent)
+    }
+}
+
+/// A negative `gamma` alternates the sign, so the pieces are the first decay, which covers the later learning rates for `gamma >= -1`.
+impl PiecewiseMonotone for ExponentialLR {
+    fn monotone_pieces(&self) -> Vec<(usize, usize)> {
+        if self.gamma < 0.0 {
+            vec![(0, self.decay_steps)]
+        } else {
+            vec![(0, usize::MAX)]
+        }
+    }
+}
+
+impl Bounded for ExponentialLR {
+    fn bounds(&self) -> (f64, f64) {
+        let (base_lr, gamma) = (self.base_lr, self.gamma);
+        let end = if base_lr == 0.0 || gamma == 1.0 {
+            base_lr
+        } else if (0.0 .. 1.0).contains(&gamma) {
+            // Decays towards 0 without reaching it unless gamma is 0.
+            0.0
+        } else if gamma > 1.0 {
+            base_lr * f64::INFINITY
+        } else if (-1.0 .. 0.0).contains(&gamma) {
+            // Alternates in sign, so the extremes are the first two steps.
+            base_lr * gamma
+        } else {
+            // The sign alternates with a growing magnitude for gamma < -1.
+            return (f64::NEG_INFINITY, f64::INFINITY);
+        };
+        (base_lr.min(end), base_lr.max(end))
+    }
+}
+
+impl Perturbable for ExponentialLR {
+    fn perturb(&mut self, factor: f64) {
+        self.base_lr *= factor;
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use approx::relative_eq;
     use crate::Scheduler;
     use super::*;
 
@@ -99,6 +250,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fractional_steps() {
+        let scheduler = ExponentialLR::new(2.0, 0.25, 0);
+        assert_eq!(scheduler.lr_at_f64(0.5), 1.0);
+        assert_eq!(scheduler.lr_at_f64(-1.0), 2.0);
+        let staircase = ExponentialLR::new(1.0, 0.5, 0).with_decay_steps(2, true);
+        for (t, expected) in [(1.5, 1.0), (2.0, 0.5), (3.9, 0.5), (4.0, 0.25)] {
+            assert_eq!(staircase.lr_at_f64(t), expected, "t {}", t);
+        }
+    }
+
     #[test]
     fn start_step_midway() {
         let base_lr = 2.0;
@@ -115,4 +277,88 @@ mod tests {
             scheduler.step(0.0);
         }
     }
+
+    #[test]
+    fn random_access() {
+        let mut scheduler = ExponentialLR::new(1.0, 0.9, 0);
+        let reference = scheduler.clone();
+        for i in 0 .. 10 {
+            assert_eq!(reference.lr_at(i), scheduler.get_lr(0.0), "Step {}", i);
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn const_table() {
+        const SCHEDULER: ExponentialLR = ExponentialLR::new(1.0, 0.9, 2);
+        static LRS: [f64; 5] = SCHEDULER.table::<5>();
+        for (i, lr) in LRS.iter().enumerate() {
+            assert!((*lr - SCHEDULER.lr_at(i)).abs() < 1e-15, "Step {}", i);
+        }
+    }
+
+    #[test]
+    fn no_drift() {
+        // gamma = 1 - 2^-20 is exact, and the reference is accurate to a few ulps.
+        let gamma = 1.0 - 2.0f64.powi(-20);
+        let steps = 1_000_000;
+        let mut scheduler = ExponentialLR::new(1.0, gamma, 0);
+        let mut naive = 1.0;
+        for _ in 0 .. steps {
+            scheduler.step(0.0);
+            naive *= gamma;
+        }
+        let reference = ((steps as f64) * (-2.0f64.powi(-20)).ln_1p()).exp();
+        let error = (scheduler.get_lr(0.0) - reference).abs() / reference;
+        let naive_error = (naive - reference).abs() / reference;
+        assert!(error < 1e-14, "error: {}", error);
+        assert!(naive_error > 100.0 * error, "error: {}, naive error: {}", error, naive_error);
+    }
+
+    #[test]
+    fn set_params_keeps_step() {
+        let mut scheduler = ExponentialLR::new(1.0, 0.5, 0);
+        scheduler.step(0.0);
+        scheduler.step(0.0);
+        scheduler.set_base_lr(4.0);
+        assert_eq!(scheduler.get_lr(0.0), 1.0);
+        scheduler.set_gamma(0.25);
+        assert_eq!(scheduler.get_lr(0.0), 0.25);
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.0625);
+    }
+
+    #[test]
+    fn bounds_by_gamma() {
+        let gammas = [0.5, 1.0, 2.0, -0.5, -2.0];
+        let expected = [
+            (0.0, 2.0),
+            (2.0, 2.0),
+            (2.0, f64::INFINITY),
+            (-1.0, 2.0),
+            (f64::NEG_INFINITY, f64::INFINITY),
+        ];
+        for (gamma, exp) in gammas.iter().zip(expected) {
+            assert_eq!(ExponentialLR::new(2.0, *gamma, 0).bounds(), exp, "gamma {}", gamma);
+        }
+        assert_eq!(ExponentialLR::new(-2.0, 0.5, 0).bounds(), (-2.0, 0.0));
+    }
+
+    #[test]
+    fn decay_every_n_steps() {
+        let scheduler = ExponentialLR::new(2.0, 0.5, 0).with_decay_steps(3, true);
+        const TABLE: [f64; 7] = ExponentialLR::new(2.0, 0.5, 0).table::<7>();
+        let expected_lrs = [2.0, 2.0, 2.0, 1.0, 1.0, 1.0, 0.5];
+        assert_eq!(scheduler.table::<7>(), expected_lrs);
+        assert_ne!(TABLE, expected_lrs);
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            assert_eq!(scheduler.lr_at(i), *exp_lr, "Step {}", i);
+        }
+        let mut continuous = ExponentialLR::new(1.0, 0.5, 0).with_decay_steps(4, false);
+        assert!(relative_eq!(continuous.lr_at(2), 0.5f64.sqrt()));
+        assert!(relative_eq!(continuous.table::<3>()[2], 0.5f64.sqrt(), epsilon = 1e-15));
+        continuous.set_gamma(0.25);
+        assert_eq!(continuous.lr_at(8), 0.0625);
+        assert!(relative_eq!(continuous.table::<3>()[2], 0.5, epsilon = 1e-15));
+    }
 }
\ No newline at end of file
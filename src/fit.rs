@@ -0,0 +1,184 @@
+use crate::config::SchedulerConfig;
+use crate::constant::ConstantLR;
+use crate::cosine_annealing::CosineAnnealingLR;
+use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+use crate::linear::LinearLR;
+use crate::semantics::Semantics;
+use crate::ClosedForm;
+
+/// Largest range of an integer parameter searched exhaustively.
+const EXHAUSTIVE_RANGE: usize = 1024;
+
+/// Number of evaluations per round of the search for an integer parameter in a larger range.
+const SEARCH_POINTS: usize = 64;
+
+/// Values of `t_mult` tried for [`SchedulerConfig::CosineAnnealingWarmRestarts`].
+const T_MULTS: [usize; 3] = [1, 2, 3];
+
+/// Fits every scheduler family of [`SchedulerConfig`] to an observed learning rate trace, e.g. parsed from a training log.
+/// 
+/// Each point is a pair of a step and the learning rate observed at the step, in any order.
+/// The learning rate parameters of a family are fitted by least squares, and its integer parameter, such as `t_max`,
+/// by a coarse-to-fine search, so the fit is approximate for noisy traces.
+/// [`SchedulerConfig::Exponential`] is fitted on the logarithm of the learning rates and skipped unless all of them are positive.
+/// 
+/// Returns the fitted configurations with their root mean square errors over the points, from the best fit.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::config::SchedulerConfig;
+/// # use lr_schedulers::fit::fit_schedule;
+/// let points: Vec<(u64, f64)> = (0 .. 100).map(|step| (step, 0.1 * 0.97f64.powi(step as i32))).collect();
+/// let fits = fit_schedule(&points);
+/// let (best, error) = &fits[0];
+/// assert!(matches!(best, SchedulerConfig::Exponential { .. }));
+/// assert!(*error < 1e-12);
+/// ```
+pub fn fit_schedule(points: &[(u64, f64)]) -> Vec<(SchedulerConfig, f64)> {
+    let Some(max_step) = points.iter().map(|&(step, _)| to_usize(step)).max() else {
+        return Vec::new();
+    };
+    let max_step = max_step.max(1);
+    let mut configs = vec![
+        fit_family(points, 1, max_step, fit_constant),
+        fit_family(points, 1, max_step, fit_linear),
+        fit_family(points, 1, max_step.saturating_mul(2), fit_cosine_annealing),
+    ];
+    configs.extend(T_MULTS.iter().map(|&t_mult| fit_family(points, 1, max_step, |points, t_0| fit_warm_restarts(points, t_0, t_mult))));
+    configs.extend(fit_exponential(points));
+    let mut fits: Vec<_> = configs.into_iter().map(|config| (rmse(&config, points), config)).collect();
+    fits.sort_by(|a, b| a.0.total_cmp(&b.0));
+    fits.into_iter().map(|(error, config)| (config, error)).collect()
+}
+
+/// Returns the root mean square error of `config` over `points`.
+fn rmse(config: &SchedulerConfig, points: &[(u64, f64)]) -> f64 {
+    let scheduler = config.build_any(0);
+    let sse: f64 = points.iter().map(|&(step, lr)| (scheduler.lr_at(to_usize(step)) - lr).powi(2)).sum();
+    (sse / points.len() as f64).sqrt()
+}
+
+/// Fits `fit(points, t)` for every integer parameter `t` in `[low, high]` and returns the best one.
+/// 
+/// A range of up to `EXHAUSTIVE_RANGE` values is searched exhaustively. A larger range is narrowed around the best of
+/// `SEARCH_POINTS` evenly spaced values until it can be, which finds the minimum if the error is roughly unimodal in `t`.
+fn fit_family<F>(points: &[(u64, f64)], low: usize, high: usize, fit: F) -> SchedulerConfig
+where
+    F: Fn(&[(u64, f64)], usize) -> SchedulerConfig,
+{
+    let best = |candidates: &mut dyn Iterator<Item = usize>| {
+        candidates
+            .map(|t| {
+                let config = fit(points, t);
+                (rmse(&config, points), t, config)
+            })
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap()
+    };
+    let (mut low, mut high) = (low, high);
+    while high - low > EXHAUSTIVE_RANGE {
+        let stride = (high - low) / SEARCH_POINTS;
+        let (_, t, _) = best(&mut (0 ..= SEARCH_POINTS).map(|i| low + i * stride));
+        (low, high) = (t.saturating_sub(stride).max(low), t.saturating_add(stride).min(high));
+    }
+    best(&mut (low ..= high)).2
+}
+
+/// Fits `y = a + b * x` by least squares and returns `(a, b)`. If all `x` are equal, `b` is 0.
+fn least_squares(pairs: impl Iterator<Item = (f64, f64)> + Clone) -> (f64, f64) {
+    let n = pairs.clone().count() as f64;
+    let (sum_x, sum_y) = pairs.clone().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+    let (mean_x, mean_y) = (sum_x / n, sum_y / n);
+    let (sxx, sxy) = pairs.fold((0.0, 0.0), |(sxx, sxy), (x, y)| {
+        (sxx + (x - mean_x).powi(2), sxy + (x - mean_x) * (y - mean_y))
+    });
+    let b = if sxx > 0.0 { sxy / sxx } else { 0.0 };
+    (b.mul_add(-mean_x, mean_y), b)
+}
+
+/// Fits `lr = a + b * basis.lr_at(step)` by least squares.
+fn fit_basis<B: ClosedForm>(points: &[(u64, f64)], basis: &B) -> (f64, f64) {
+    least_squares(points.iter().map(|&(step, lr)| (basis.lr_at(to_usize(step)), lr)))
+}
+
+fn fit_constant(points: &[(u64, f64)], total_iters: usize) -> SchedulerConfig {
+    // The basis is 0 before `total_iters` and 1 afterwards.
+    let (a, b) = fit_basis(points, &ConstantLR::new(1.0, 0.0, total_iters, 0));
+    let base_lr = a + b;
+    let factor = if base_lr != 0.0 { a / base_lr } else { 1.0 };
+    SchedulerConfig::Constant { base_lr, factor, total_iters }
+}
+
+fn fit_linear(points: &[(u64, f64)], total_iters: usize) -> SchedulerConfig {
+    // The basis rises from 0 to 1 over `total_iters` steps.
+    let (start, slope) = fit_basis(points, &LinearLR::new(1.0, 0.0, 1.0, total_iters, 0));
+    let end = start + slope;
+    let base_lr = if start.abs() > end.abs() { start } else { end };
+    let (start_factor, end_factor) = if base_lr != 0.0 { (start / base_lr, end / base_lr) } else { (1.0, 1.0) };
+    SchedulerConfig::Linear { base_lr, start_factor, end_factor, total_iters }
+}
+
+fn fit_cosine_annealing(points: &[(u64, f64)], t_max: usize) -> SchedulerConfig {
+    // The basis oscillates between 1 and 0.
+    let (eta_1, amplitude) = fit_basis(points, &CosineAnnealingLR::new(1.0, 0.0, t_max, 0));
+    SchedulerConfig::CosineAnnealing { eta_0: eta_1 + amplitude, eta_1, t_max, decay_once: false, min_fraction: None }
+}
+
+fn fit_warm_restarts(points: &[(u64, f64)], t_0: usize, t_mult: usize) -> SchedulerConfig {
+    let (eta_1, amplitude) = fit_basis(points, &CosineAnnealingWarmRestarts::new(1.0, 0.0, t_0, t_mult, 0));
+    SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: eta_1 + amplitude, eta_1, t_0, t_mult, steps_per_epoch: 1, semantics: Semantics::Native }
+}
+
+fn fit_exponential(points: &[(u64, f64)]) -> Option<SchedulerConfig> {
+    if !points.iter().all(|&(_, lr)| lr > 0.0) {
+        return None;
+    }
+    let (log_base_lr, log_gamma) = least_squares(points.iter().map(|&(step, lr)| (step as f64, lr.ln())));
+    Some(SchedulerConfig::Exponential { base_lr: log_base_lr.exp(), gamma: log_gamma.exp(), decay_steps: 1, staircase: false })
+}
+
+fn to_usize(step: u64) -> usize {
+    usize::try_from(step).unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::relative_eq;
+    use super::*;
+
+    fn trace(config: &SchedulerConfig, steps: u64) -> Vec<(u64, f64)> {
+        let scheduler = config.build_any(0);
+        (0 .. steps).map(|step| (step, scheduler.lr_at(step as usize))).collect()
+    }
+
+    #[test]
+    fn recover_each_family() {
+        let configs = [
+            SchedulerConfig::Constant { base_lr: 0.1, factor: 0.25, total_iters: 37 },
+            SchedulerConfig::Linear { base_lr: 0.1, start_factor: 0.1, end_factor: 1.0, total_iters: 150 },
+            SchedulerConfig::CosineAnnealing { eta_0: 0.1, eta_1: 0.001, t_max: 123, decay_once: false, min_fraction: None },
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 0.1, eta_1: 0.01, t_0: 29, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native },
+        ];
+        for config in configs {
+            let fits = fit_schedule(&trace(&config, 400));
+            let (best, error) = &fits[0];
+            assert!(*error < 1e-10, "{:?}: {:?} ({})", config, best, error);
+            assert!(fits.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+            let fitted = trace(best, 400);
+            let expected = trace(&config, 400);
+            for (i, (a, b)) in fitted.iter().zip(&expected).enumerate() {
+                assert!(relative_eq!(a.1, b.1, epsilon = 1e-10), "{:?}: Step {}", config, i);
+            }
+        }
+    }
+
+    #[test]
+    fn skip_exponential_for_non_positive() {
+        let points = [(0, 1.0), (5, 0.0), (3, 0.5)];
+        let fits = fit_schedule(&points);
+        assert_eq!(fits.len(), 6);
+        assert!(!fits.iter().any(|(config, _)| matches!(config, SchedulerConfig::Exponential { .. })));
+        assert!(fit_schedule(&[]).is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,129 @@
+use crate::Scheduler;
+
+/// Coordinates the learning rates of the generator and the discriminator of a GAN.
+/// 
+/// Both schedulers are stepped together by `step` with their own losses, and two rules couple them:
+/// 
+/// * With `with_ttur_ratio`, the discriminator learns at least `ratio` times as fast as the generator,
+///   as in the two time-scale update rule (TTUR), whatever the two schedules do.
+/// * With `with_freeze_threshold`, the discriminator is frozen, i.e. its learning rate is 0, from the step after
+///   its loss falls below the threshold until its loss reaches the threshold again, so that it does not overpower the generator.
+///   Its schedule keeps advancing while frozen.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::gan::DualScheduler;
+/// let generator = ConstantLR::new(1e-4, 1.0, 0, 0);
+/// let discriminator = ConstantLR::new(1e-4, 1.0, 0, 0);
+/// let mut scheduler = DualScheduler::new(generator, discriminator).with_ttur_ratio(4.0).with_freeze_threshold(0.1);
+/// assert_eq!(scheduler.get_lrs(1.0, 0.5), (1e-4, 4e-4));
+/// scheduler.step(1.0, 0.05);
+/// assert!(scheduler.is_discriminator_frozen());
+/// assert_eq!(scheduler.get_lrs(1.0, 0.05), (1e-4, 0.0));
+/// scheduler.step(1.0, 0.2);
+/// assert_eq!(scheduler.get_lrs(1.0, 0.2), (1e-4, 4e-4));
+/// assert_eq!(scheduler.frozen_steps(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DualScheduler<G, D> {
+    generator: G,
+    discriminator: D,
+    ttur_ratio: Option<f64>,
+    freeze_threshold: Option<f64>,
+    frozen: bool,
+    frozen_steps: usize,
+    last_lrs: Option<(f64, f64)>,
+}
+
+impl<G: Scheduler, D: Scheduler> DualScheduler<G, D> {
+    /// Constructs a DualScheduler instance without coordination rules.
+    pub fn new(generator: G, discriminator: D) -> Self {
+        DualScheduler { generator, discriminator, ttur_ratio: None, freeze_threshold: None, frozen: false, frozen_steps: 0, last_lrs: None }
+    }
+
+    /// Keeps the learning rate of the discriminator at least `ratio` times that of the generator.
+    pub fn with_ttur_ratio(self, ratio: f64) -> Self {
+        DualScheduler { ttur_ratio: Some(ratio), ..self }
+    }
+
+    /// Freezes the discriminator while its loss is below `threshold`.
+    pub fn with_freeze_threshold(self, threshold: f64) -> Self {
+        DualScheduler { freeze_threshold: Some(threshold), ..self }
+    }
+
+    /// Proceeds both schedulers with their losses, and updates the freeze of the discriminator.
+    /// 
+    /// A NaN discriminator loss keeps the freeze as is.
+    pub fn step(&mut self, generator_loss: f64, discriminator_loss: f64) {
+        self.last_lrs = Some(self.get_lrs(generator_loss, discriminator_loss));
+        if self.frozen {
+            self.frozen_steps += 1;
+        }
+        self.generator.step(generator_loss);
+        self.discriminator.step(discriminator_loss);
+        if let Some(threshold) = self.freeze_threshold.filter(|_| !discriminator_loss.is_nan()) {
+            self.frozen = discriminator_loss < threshold;
+        }
+    }
+
+    /// Returns the learning rates of the generator and the discriminator for the current step.
+    pub fn get_lrs(&self, generator_loss: f64, discriminator_loss: f64) -> (f64, f64) {
+        let generator_lr = self.generator.get_lr(generator_loss);
+        let discriminator_lr = if self.frozen {
+            0.0
+        } else {
+            let lr = self.discriminator.get_lr(discriminator_loss);
+            self.ttur_ratio.map_or(lr, |ratio| lr.max(ratio * generator_lr))
+        };
+        (generator_lr, discriminator_lr)
+    }
+
+    /// Returns the learning rates of the most recent step.
+    pub fn get_last_lrs(&self) -> Option<(f64, f64)> {
+        self.last_lrs
+    }
+
+    /// Returns true if the discriminator is frozen at the current step.
+    pub fn is_discriminator_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Returns the total number of steps taken with the discriminator frozen.
+    pub fn frozen_steps(&self) -> usize {
+        self.frozen_steps
+    }
+
+    /// Returns the scheduler of the generator.
+    pub fn generator(&self) -> &G {
+        &self.generator
+    }
+
+    /// Returns the scheduler of the discriminator.
+    pub fn discriminator(&self) -> &D {
+        &self.discriminator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::exponential::ExponentialLR;
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn coordinate_schedules() {
+        let generator = LinearLR::new(1.0, 1.0, 0.0, 4, 0);
+        let discriminator = ExponentialLR::new(1.0, 0.5, 0);
+        let mut scheduler = DualScheduler::new(generator, discriminator).with_ttur_ratio(1.0).with_freeze_threshold(0.5);
+        let discriminator_losses = [1.0, 0.25, f64::NAN, 1.0, 1.0];
+        let expected_lrs = [(0.75, 0.75), (0.5, 0.0), (0.25, 0.0), (0.0, 0.0625), (0.0, 0.03125)];
+        for (i, (loss, exp_lrs)) in discriminator_losses.iter().zip(expected_lrs).enumerate() {
+            scheduler.step(0.0, *loss);
+            assert_eq!(scheduler.get_lrs(0.0, 0.0), exp_lrs, "Step {}", i);
+        }
+        assert_eq!(scheduler.frozen_steps(), 2);
+        assert_eq!(scheduler.get_last_lrs(), Some((0.0, 0.0625)));
+    }
+}
@@ -0,0 +1,197 @@
+use crate::Scheduler;
+
+/// Condition on a metric that opens the gate of [`GatedWarmup`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gate {
+    /// Opens when the metric falls below the threshold, e.g. the loss.
+    Below(f64),
+    /// Opens when the metric rises above the threshold, e.g. an accuracy.
+    Above(f64),
+    /// Opens when each of `steps` consecutive metrics differs from the previous one by at most `tolerance` relative to it,
+    /// e.g. a gradient norm settling down.
+    Stable {
+        /// Largest relative change regarded as stable.
+        tolerance: f64,
+        /// Number of consecutive stable metrics required.
+        steps: usize,
+    },
+}
+
+/// Holds a low learning rate until a metric passes a gate, and then starts the wrapped scheduler, i.e. an event-triggered warmup.
+/// 
+/// Metrics are passed to `step_with_metric`, and `step` takes the loss as the metric. Metrics that are not finite are ignored.
+/// The wrapped scheduler is not advanced while holding, so it starts from its first step at the step following the one
+/// whose metric opened the gate. With `with_max_hold`, the gate opens after the given number of steps regardless of the metric.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::gated::{Gate, GatedWarmup};
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = GatedWarmup::new(ExponentialLR::new(1.0, 0.5, 0), 0.01, Gate::Below(2.0));
+/// for loss in [4.0, 3.0, 2.5] {
+///     scheduler.step(loss);
+/// }
+/// assert_eq!(scheduler.get_lr(2.5), 0.01);
+/// scheduler.step(1.5);
+/// assert_eq!(scheduler.opened_at(), Some(4));
+/// assert_eq!(scheduler.get_lr(1.5), 1.0);
+/// scheduler.step(1.5);
+/// assert_eq!(scheduler.get_lr(1.5), 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GatedWarmup<S> {
+    scheduler: S,
+    hold_lr: f64,
+    gate: Gate,
+    max_hold: Option<usize>,
+    step: usize,
+    opened_at: Option<usize>,
+    previous_metric: Option<f64>,
+    stable_steps: usize,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> GatedWarmup<S> {
+    /// Constructs a GatedWarmup instance holding `hold_lr` until `gate` opens.
+    pub fn new(scheduler: S, hold_lr: f64, gate: Gate) -> Self {
+        GatedWarmup {
+            scheduler,
+            hold_lr,
+            gate,
+            max_hold: None,
+            step: 0,
+            opened_at: None,
+            previous_metric: None,
+            stable_steps: 0,
+            last_lr: None,
+        }
+    }
+
+    /// Opens the gate after `steps` steps at the latest.
+    pub fn with_max_hold(self, steps: usize) -> Self {
+        let mut scheduler = GatedWarmup { max_hold: Some(steps), ..self };
+        scheduler.open_if_expired();
+        scheduler
+    }
+
+    /// Constructs a GatedWarmup instance restored from a checkpoint whose gate opened at `opened_at`.
+    /// 
+    /// The parameter `scheduler` must be at its state of the current step.
+    pub fn opened(scheduler: S, hold_lr: f64, gate: Gate, opened_at: usize) -> Self {
+        GatedWarmup { opened_at: Some(opened_at), ..GatedWarmup::new(scheduler, hold_lr, gate) }
+    }
+
+    /// Records `metric`, if any, and proceeds the step. The gate opens when the metric passes it.
+    pub fn step_with_metric(&mut self, loss: f64, metric: Option<f64>) {
+        self.last_lr = Some(self.get_lr(loss));
+        if self.is_open() {
+            self.scheduler.step(loss);
+        }
+        self.step = self.step.saturating_add(1);
+        if let Some(metric) = metric.filter(|m| m.is_finite()) {
+            if !self.is_open() && self.passes(metric) {
+                self.opened_at = Some(self.step);
+            }
+            self.previous_metric = Some(metric);
+        }
+        self.open_if_expired();
+    }
+
+    /// Opens the gate from the next step on, e.g. on an event not expressed by a metric.
+    pub fn open(&mut self) {
+        self.opened_at.get_or_insert(self.step);
+    }
+
+    /// Returns true if the wrapped scheduler has started.
+    pub fn is_open(&self) -> bool {
+        self.opened_at.is_some()
+    }
+
+    /// Returns the number of steps processed before the wrapped scheduler started, or None while holding.
+    pub fn opened_at(&self) -> Option<usize> {
+        self.opened_at
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+
+    fn passes(&mut self, metric: f64) -> bool {
+        match self.gate {
+            Gate::Below(threshold) => metric < threshold,
+            Gate::Above(threshold) => metric > threshold,
+            Gate::Stable { tolerance, steps } => {
+                let stable = self.previous_metric.is_some_and(|previous| (metric - previous).abs() <= tolerance * previous.abs());
+                self.stable_steps = if stable { self.stable_steps + 1 } else { 0 };
+                self.stable_steps >= steps.max(1)
+            }
+        }
+    }
+
+    fn open_if_expired(&mut self) {
+        if self.max_hold.is_some_and(|max_hold| self.step >= max_hold) {
+            self.open();
+        }
+    }
+}
+
+impl<S: Scheduler> Scheduler for GatedWarmup<S> {
+    /// Proceeds the step with the loss as the metric.
+    fn step(&mut self, loss: f64) {
+        self.step_with_metric(loss, Some(loss));
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        if self.is_open() {
+            self.scheduler.get_lr(loss)
+        } else {
+            self.hold_lr
+        }
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the remaining steps of the wrapped scheduler, which has not started while holding.
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn open_on_stable_metric() {
+        let mut scheduler = GatedWarmup::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 0.1, Gate::Stable { tolerance: 0.1, steps: 2 });
+        let metrics = [Some(10.0), Some(5.0), Some(4.8), None, Some(f64::NAN), Some(4.7), Some(1.0), None];
+        let expected_lrs = [0.1, 0.1, 0.1, 0.1, 0.1, 1.0, 0.75, 0.5];
+        for (i, (metric, exp_lr)) in metrics.iter().zip(expected_lrs).enumerate() {
+            scheduler.step_with_metric(0.0, *metric);
+            assert_eq!(scheduler.get_lr(0.0), exp_lr, "Step {}", i);
+        }
+        assert_eq!(scheduler.opened_at(), Some(6));
+        assert_eq!(scheduler.get_last_lr(), Some(0.75));
+    }
+
+    #[test]
+    fn open_after_max_hold() {
+        let mut scheduler = GatedWarmup::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 0.1, Gate::Above(0.9)).with_max_hold(2);
+        for _ in 0 .. 3 {
+            scheduler.step_with_metric(0.0, Some(0.5));
+        }
+        assert_eq!(scheduler.opened_at(), Some(2));
+        assert_eq!(scheduler.get_lr(0.0), 0.75);
+        assert!(GatedWarmup::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 0.1, Gate::Below(0.0)).with_max_hold(0).is_open());
+    }
+}
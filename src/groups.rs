@@ -0,0 +1,300 @@
+use std::collections::BTreeMap;
+
+use crate::any::AnyScheduler;
+use crate::config::SchedulerConfig;
+use crate::plateau::Plateau;
+use crate::Scheduler;
+
+/// How plateaus reduce the learning rate of a group in [`GroupScheduler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlateauScope {
+    /// The group is reduced together with the other shared groups when the common metric plateaus.
+    Shared,
+    /// The group is reduced alone when its own metric plateaus, e.g. the validation loss of a task head.
+    Independent,
+}
+
+#[derive(Debug, Clone)]
+struct GroupPlateaus {
+    shared: Plateau,
+    /// Detector of each group, which is None for a shared group.
+    groups: Vec<Option<Plateau>>,
+    factor: f64,
+    scales: Vec<f64>,
+}
+
+/// Schedules the learning rates of several parameter groups with a common schedule.
+/// 
+/// The learning rate of group i is the learning rate of the wrapped scheduler multiplied by the multiplier of the group.
+/// Multipliers can be overridden by tags such as `"embeddings"` or `"head"`, which take precedence over the group when the learning rate is looked up with a tag.
+/// The [`Scheduler`] implementation reports the unscaled learning rate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::groups::GroupScheduler;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = GroupScheduler::new(ExponentialLR::new(1.0, 0.5, 0), vec![1.0, 0.1]);
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_lrs(0.01), [0.5, 0.05]);
+/// ```
+/// 
+/// Tags override the multiplier of the group:
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::groups::GroupScheduler;
+/// let scheduler = GroupScheduler::new(ExponentialLR::new(1.0, 0.5, 0), vec![1.0, 0.1])
+///     .with_override("head", 10.0);
+/// assert_eq!(scheduler.get_lr_for(1, Some("head"), 0.01), Some(10.0));
+/// assert_eq!(scheduler.get_lr_for(1, Some("no_decay"), 0.01), Some(0.1));
+/// ```
+/// 
+/// Plateaus reduce the groups as in PyTorch's ReduceLROnPlateau, either together or one by one:
+/// 
+/// ```
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::groups::{GroupScheduler, PlateauScope};
+/// # use lr_schedulers::plateau::{Mode, Plateau, ThresholdMode};
+/// let plateau = Plateau::new(Mode::Min, 0, 0.0, ThresholdMode::Abs);
+/// let mut scheduler = GroupScheduler::new(ConstantLR::new(1.0, 1.0, 0, 0), vec![1.0, 1.0])
+///     .with_plateau(plateau, 0.5, &[PlateauScope::Shared, PlateauScope::Independent]);
+/// // The shared metric improves, and the metric of group 1 does not.
+/// scheduler.update_plateaus(1.0, &[f64::NAN, 1.0]);
+/// assert_eq!(scheduler.update_plateaus(0.9, &[f64::NAN, 1.0]), [1]);
+/// assert_eq!(scheduler.get_lrs(0.01), [1.0, 0.5]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GroupScheduler<S> {
+    scheduler: S,
+    multipliers: Vec<f64>,
+    overrides: BTreeMap<String, f64>,
+    plateaus: Option<GroupPlateaus>,
+}
+
+impl<S: Scheduler> GroupScheduler<S> {
+    /// Constructs a GroupScheduler instance with a multiplier for each group.
+    pub fn new(scheduler: S, multipliers: Vec<f64>) -> Self {
+        GroupScheduler { scheduler, multipliers, overrides: BTreeMap::new(), plateaus: None }
+    }
+
+    /// Multiplies the learning rate of groups by `factor` when a metric plateaus, as detected by `plateau`.
+    /// 
+    /// The scope of group i is `scopes[i]`, and groups without a scope are shared.
+    /// Shared groups are reduced together by the common metric, and each independent group by its own metric
+    /// with its own copy of `plateau`. See `update_plateaus`.
+    pub fn with_plateau(self, plateau: Plateau, factor: f64, scopes: &[PlateauScope]) -> Self {
+        let groups = (0 .. self.multipliers.len())
+            .map(|group| match scopes.get(group) {
+                Some(PlateauScope::Independent) => Some(plateau.clone()),
+                _ => None,
+            })
+            .collect();
+        let scales = vec![1.0; self.multipliers.len()];
+        GroupScheduler { plateaus: Some(GroupPlateaus { shared: plateau, groups, factor, scales }), ..self }
+    }
+
+    /// Records the metrics of an epoch and returns the groups reduced by a plateau, in order.
+    /// 
+    /// The shared groups are reduced when `metric` plateaus, and independent group i when `group_metrics[i]` plateaus.
+    /// An independent group without a metric in `group_metrics` skips the epoch. The detector is reset after each reduction,
+    /// so a group is reduced again only after another `patience + 1` bad epochs. Does nothing without `with_plateau`.
+    pub fn update_plateaus(&mut self, metric: f64, group_metrics: &[f64]) -> Vec<usize> {
+        let Some(plateaus) = &mut self.plateaus else {
+            return Vec::new();
+        };
+        let shared = plateaus.shared.update(metric);
+        if shared {
+            plateaus.shared.reset_bad_epochs();
+        }
+        let mut reduced = Vec::new();
+        for (group, plateau) in plateaus.groups.iter_mut().enumerate() {
+            let plateaued = match plateau {
+                Some(plateau) => match group_metrics.get(group) {
+                    Some(&metric) if plateau.update(metric) => {
+                        plateau.reset_bad_epochs();
+                        true
+                    }
+                    _ => false,
+                },
+                None => shared,
+            };
+            if plateaued {
+                plateaus.scales[group] *= plateaus.factor;
+                reduced.push(group);
+            }
+        }
+        reduced
+    }
+
+    /// Returns the product of the plateau reductions applied to `group`, which is 1 before any reduction.
+    pub fn plateau_scale(&self, group: usize) -> f64 {
+        self.plateaus.as_ref().and_then(|plateaus| plateaus.scales.get(group).copied()).unwrap_or(1.0)
+    }
+
+    /// Registers `multiplier` for `tag`, replacing the multiplier of the group for parameters with the tag.
+    pub fn with_override(mut self, tag: &str, multiplier: f64) -> Self {
+        self.set_override(tag, multiplier);
+        self
+    }
+
+    /// Registers `multiplier` for `tag`, replacing any previous override of the tag.
+    pub fn set_override(&mut self, tag: &str, multiplier: f64) {
+        self.overrides.insert(tag.to_owned(), multiplier);
+    }
+
+    /// Removes the override of `tag` and returns its multiplier.
+    pub fn remove_override(&mut self, tag: &str) -> Option<f64> {
+        self.overrides.remove(tag)
+    }
+
+    /// Returns the multiplier registered for `tag`.
+    pub fn override_for(&self, tag: &str) -> Option<f64> {
+        self.overrides.get(tag).copied()
+    }
+
+    /// Returns the registered tags and their multipliers in the order of tags.
+    pub fn overrides(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.overrides.iter().map(|(tag, multiplier)| (tag.as_str(), *multiplier))
+    }
+
+    /// Returns the learning rate of parameters in `group` with an optional `tag` for the current step.
+    /// 
+    /// The override of the tag is used if registered, otherwise the multiplier of the group.
+    /// The plateau reductions of the group apply to both.
+    /// Returns None if neither the override nor the group exists.
+    pub fn get_lr_for(&self, group: usize, tag: Option<&str>, loss: f64) -> Option<f64> {
+        let multiplier = tag
+            .and_then(|tag| self.override_for(tag))
+            .or_else(|| self.multipliers.get(group).copied())?;
+        Some(self.scheduler.get_lr(loss) * multiplier * self.plateau_scale(group))
+    }
+
+    /// Returns the learning rates of all groups for the current step.
+    pub fn get_lrs(&self, loss: f64) -> Vec<f64> {
+        let lr = self.scheduler.get_lr(loss);
+        self.multipliers.iter().enumerate().map(|(group, multiplier)| lr * multiplier * self.plateau_scale(group)).collect()
+    }
+
+    /// Returns the learning rate of `group` for the current step, or None if the group does not exist.
+    pub fn get_group_lr(&self, group: usize, loss: f64) -> Option<f64> {
+        self.multipliers.get(group).map(|multiplier| self.scheduler.get_lr(loss) * multiplier * self.plateau_scale(group))
+    }
+
+    /// Returns the multipliers of the groups.
+    pub fn multipliers(&self) -> &[f64] {
+        &self.multipliers
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for GroupScheduler<S> {
+    fn step(&mut self, loss: f64) {
+        self.scheduler.step(loss);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.scheduler.get_last_lr()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+/// Builds a layer-wise learning rate decay, as used for fine-tuning transformers such as BEiT.
+/// 
+/// There are `num_layers + 1` groups: group 0 is the embeddings, group i is the i-th layer, and the multiplier of group i is
+/// `decay^(num_layers - i)`, so the top layer is trained with the learning rate of `config` and lower layers with smaller ones.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::config::SchedulerConfig;
+/// # use lr_schedulers::groups::layerwise_decay;
+/// let config = SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.9, decay_steps: 1, staircase: false };
+/// let scheduler = layerwise_decay(&config, 2, 0.5);
+/// assert_eq!(scheduler.get_lrs(0.01), [0.25, 0.5, 1.0]);
+/// ```
+pub fn layerwise_decay(config: &SchedulerConfig, num_layers: usize, decay: f64) -> GroupScheduler<AnyScheduler> {
+    let multipliers = (0 ..= num_layers).map(|i| decay.powf((num_layers - i) as f64)).collect();
+    GroupScheduler::new(config.build_any(0), multipliers)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::plateau::{Mode, ThresholdMode};
+    use super::*;
+
+    #[test]
+    fn groups_follow_schedule() {
+        let config = SchedulerConfig::Linear { base_lr: 1.0, start_factor: 1.0, end_factor: 0.0, total_iters: 2 };
+        let mut scheduler = layerwise_decay(&config, 3, 0.1);
+        scheduler.step(0.0);
+        let expected_lrs = [0.0005, 0.005, 0.05, 0.5];
+        for (group, exp_lr) in expected_lrs.iter().enumerate() {
+            let lr = scheduler.get_group_lr(group, 0.0).unwrap();
+            assert!((lr - exp_lr).abs() < 1e-15, "Group {}: left: {}, right: {}", group, lr, exp_lr);
+        }
+        assert_eq!(scheduler.get_group_lr(4, 0.0), None);
+        assert_eq!(scheduler.get_lr(0.0), 0.5);
+    }
+
+    #[test]
+    fn tag_overrides() {
+        let config = SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.5, decay_steps: 1, staircase: false };
+        let mut scheduler = layerwise_decay(&config, 1, 0.5)
+            .with_override("embeddings", 0.0)
+            .with_override("head", 2.0);
+        scheduler.set_override("head", 4.0);
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr_for(0, Some("embeddings"), 0.0), Some(0.0));
+        assert_eq!(scheduler.get_lr_for(1, Some("head"), 0.0), Some(2.0));
+        assert_eq!(scheduler.get_lr_for(0, None, 0.0), Some(0.25));
+        assert_eq!(scheduler.get_lr_for(5, Some("head"), 0.0), Some(2.0));
+        assert_eq!(scheduler.get_lr_for(5, None, 0.0), None);
+        assert_eq!(scheduler.overrides().collect::<Vec<_>>(), [("embeddings", 0.0), ("head", 4.0)]);
+        assert_eq!(scheduler.remove_override("head"), Some(4.0));
+        assert_eq!(scheduler.get_lr_for(1, Some("head"), 0.0), Some(0.5));
+    }
+
+    #[test]
+    fn shared_and_independent_plateaus() {
+        let config = SchedulerConfig::Constant { base_lr: 1.0, factor: 1.0, total_iters: 0 };
+        let plateau = Plateau::new(Mode::Min, 1, 0.0, ThresholdMode::Abs);
+        let scopes = [PlateauScope::Shared, PlateauScope::Independent, PlateauScope::Independent];
+        let mut scheduler = GroupScheduler::new(config.build_any(0), vec![1.0; 4]).with_plateau(plateau, 0.1, &scopes);
+        let epochs = [
+            (1.0, [f64::NAN, 1.0, 1.0], vec![]),
+            (1.0, [f64::NAN, 0.9, 1.0], vec![]),
+            (1.0, [f64::NAN, 0.8, 1.0], vec![0, 2, 3]),
+            (1.0, [f64::NAN, 0.8, 1.0], vec![]),
+            (0.5, [f64::NAN, 0.8, 1.0], vec![1, 2]),
+        ];
+        for (i, (metric, group_metrics, expected)) in epochs.iter().enumerate() {
+            assert_eq!(&scheduler.update_plateaus(*metric, group_metrics.as_slice()), expected, "Epoch {}", i);
+        }
+        assert!(scheduler.update_plateaus(0.5, &[]).is_empty());
+        let lrs = scheduler.get_lrs(0.0);
+        let expected_lrs = [0.1, 0.1, 0.01, 0.1];
+        for (group, exp_lr) in expected_lrs.iter().enumerate() {
+            assert!((lrs[group] - exp_lr).abs() < 1e-15, "Group {}: left: {}, right: {}", group, lrs[group], exp_lr);
+        }
+        assert_eq!(scheduler.get_lr(0.0), 1.0);
+        assert_eq!(scheduler.plateau_scale(4), 1.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::Scheduler;
+
+/// Backs off the learning rate when the loss diverges from its moving baseline.
+/// 
+/// The baseline is an exponential moving average of the losses passed to `step`, updated as
+/// `baseline = smoothing * baseline + (1 - smoothing) * loss` and initialized with the first loss.
+/// A loss larger than `threshold * baseline`, or NaN, trips the guard: the learning rate of the wrapped scheduler
+/// is multiplied by `backoff` for the next `backoff_steps` steps, during which the wrapped scheduler is paused.
+/// The wrapped schedule then resumes from where it was paused.
+/// A divergent loss during the backoff restarts the backoff, and divergent losses are excluded from the baseline.
+/// Losses are expected to be positive.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::guard::DivergenceGuard;
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = DivergenceGuard::new(LinearLR::new(1.0, 1.0, 0.5, 10, 0), 2.0, 0.9, 0.1, 2);
+/// scheduler.step(1.0);
+/// assert_eq!(scheduler.get_lr(1.0), 0.95);
+/// // The loss jumps above twice the baseline.
+/// scheduler.step(3.0);
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 3 {
+///     learning_rates.push(scheduler.get_lr(1.0));
+///     scheduler.step(1.0);
+/// }
+/// assert_eq!(learning_rates, [0.095, 0.095, 0.95]);
+/// assert_eq!(scheduler.trips(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DivergenceGuard<S> {
+    scheduler: S,
+    threshold: f64,
+    smoothing: f64,
+    backoff: f64,
+    backoff_steps: usize,
+    baseline: Option<f64>,
+    remaining_backoff_steps: usize,
+    trips: usize,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> DivergenceGuard<S> {
+    /// Constructs a DivergenceGuard instance.
+    /// 
+    /// The parameter `smoothing` must be in `[0, 1)`. Values outside the range are clamped.
+    pub fn new(scheduler: S, threshold: f64, smoothing: f64, backoff: f64, backoff_steps: usize) -> Self {
+        DivergenceGuard {
+            scheduler,
+            threshold,
+            smoothing: smoothing.clamp(0.0, 1.0 - f64::EPSILON),
+            backoff,
+            backoff_steps,
+            baseline: None,
+            remaining_backoff_steps: 0,
+            trips: 0,
+            last_lr: None,
+        }
+    }
+
+    /// Returns true if `loss` diverges from the current baseline.
+    pub fn is_divergent(&self, loss: f64) -> bool {
+        match self.baseline {
+            Some(baseline) => loss > self.threshold * baseline || loss.is_nan(),
+            None => loss.is_nan(),
+        }
+    }
+
+    /// Returns true while the learning rate is backed off.
+    pub fn is_backing_off(&self) -> bool {
+        self.remaining_backoff_steps > 0
+    }
+
+    /// Returns the number of steps until the wrapped schedule resumes.
+    pub fn remaining_backoff_steps(&self) -> usize {
+        self.remaining_backoff_steps
+    }
+
+    /// Returns the moving baseline of the loss, or None before the first finite loss.
+    pub fn baseline(&self) -> Option<f64> {
+        self.baseline
+    }
+
+    /// Returns the number of times the guard has tripped so far.
+    pub fn trips(&self) -> usize {
+        self.trips
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for DivergenceGuard<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        if self.is_divergent(loss) {
+            self.remaining_backoff_steps = self.backoff_steps;
+            self.trips += 1;
+            return;
+        }
+        self.baseline = Some(match self.baseline {
+            Some(baseline) => self.smoothing.mul_add(baseline, (1.0 - self.smoothing) * loss),
+            None => loss,
+        });
+        if self.is_backing_off() {
+            self.remaining_backoff_steps -= 1;
+        } else {
+            self.scheduler.step(loss);
+        }
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        if self.is_backing_off() {
+            self.backoff * self.scheduler.get_lr(loss)
+        } else {
+            self.scheduler.get_lr(loss)
+        }
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn back_off_on_divergence() {
+        let mut scheduler = DivergenceGuard::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 1.5, 0.5, 0.5, 2);
+        let losses = [1.0, 2.0, 1.0, f64::NAN, 1.0, 1.0, 1.0, 1.0];
+        let expected_lrs = [0.75, 0.375, 0.375, 0.375, 0.375, 0.75, 0.5, 0.25];
+        let expected_trips = [0, 1, 1, 2, 2, 2, 2, 2];
+        for (i, loss) in losses.iter().enumerate() {
+            scheduler.step(*loss);
+            assert_eq!(scheduler.get_lr(0.0), expected_lrs[i], "Step {}", i);
+            assert_eq!(scheduler.trips(), expected_trips[i], "Step {}", i);
+        }
+        assert_eq!(scheduler.get_last_lr(), Some(0.5));
+        // Divergent losses are excluded from the baseline.
+        assert_eq!(scheduler.baseline(), Some(1.0));
+    }
+
+    #[test]
+    fn no_backoff_steps() {
+        let mut scheduler = DivergenceGuard::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 1.5, 0.5, 0.5, 0);
+        for loss in [1.0, 5.0, 1.0] {
+            scheduler.step(loss);
+            assert!(!scheduler.is_backing_off());
+        }
+        // The divergent step neither proceeds the schedule nor backs off.
+        assert_eq!(scheduler.get_lr(0.0), 0.5);
+        assert_eq!(scheduler.trips(), 1);
+    }
+}
\ No newline at end of file
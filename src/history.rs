@@ -0,0 +1,284 @@
+use std::collections::VecDeque;
+
+use crate::analysis::compensated_sum;
+use crate::Scheduler;
+
+/// A single step of training.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Record {
+    /// Index of the step.
+    pub step: usize,
+    /// Metric (e.g. loss) observed at the step.
+    pub metric: f64,
+    /// Learning rate used for the step.
+    pub lr: f64,
+}
+
+/// How many records a [`History`] keeps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Retention {
+    /// Keeps every record.
+    Full,
+    /// Keeps the given number of most recent records.
+    Ring(usize),
+}
+
+/// Summary statistics of recorded learning rates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    /// Smallest learning rate.
+    pub min_lr: f64,
+    /// Largest learning rate.
+    pub max_lr: f64,
+    /// Sum of learning rates over the steps, i.e. the area under the learning rate curve, accumulated by
+    /// [`crate::analysis::compensated_sum`].
+    pub area: f64,
+    /// Number of records whose learning rate is lower than that of the previous record.
+    pub decreases: usize,
+    /// Number of records whose learning rate is higher than that of the previous record, such as warm restarts.
+    pub increases: usize,
+}
+
+/// Stores the trajectory of training as (step, metric, lr) records.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::history::{History, Retention};
+/// let mut history = History::new(Retention::Ring(2));
+/// history.push(0, 1.0, 0.1);
+/// history.push(1, 0.9, 0.1);
+/// history.push(2, 0.8, 0.05);
+/// assert_eq!(history.len(), 2);
+/// let stats = history.stats().unwrap();
+/// assert_eq!(stats.min_lr, 0.05);
+/// assert_eq!(stats.decreases, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct History {
+    records: VecDeque<Record>,
+    retention: Retention,
+}
+
+impl History {
+    /// Constructs an empty History instance.
+    pub fn new(retention: Retention) -> Self {
+        History { records: VecDeque::new(), retention }
+    }
+
+    /// Appends a record, discarding the oldest one if the retention limit is reached.
+    pub fn push(&mut self, step: usize, metric: f64, lr: f64) {
+        if let Retention::Ring(capacity) = self.retention {
+            if capacity == 0 {
+                return;
+            }
+            if self.records.len() == capacity {
+                self.records.pop_front();
+            }
+        }
+        self.records.push_back(Record { step, metric, lr });
+    }
+
+    /// Returns the retained records from the oldest to the newest.
+    pub fn records(&self) -> impl Iterator<Item = &Record> {
+        self.records.iter()
+    }
+
+    /// Returns the newest record.
+    pub fn last(&self) -> Option<&Record> {
+        self.records.back()
+    }
+
+    /// Returns the number of retained records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true if no record is retained.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Removes all records.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Returns summary statistics of the retained records, or None if there is no record.
+    pub fn stats(&self) -> Option<Stats> {
+        let first = self.records.front()?;
+        let mut stats = Stats {
+            min_lr: first.lr,
+            max_lr: first.lr,
+            area: compensated_sum(self.records.iter().map(|record| record.lr)),
+            decreases: 0,
+            increases: 0,
+        };
+        let mut prev_lr = first.lr;
+        for record in &self.records {
+            stats.min_lr = stats.min_lr.min(record.lr);
+            stats.max_lr = stats.max_lr.max(record.lr);
+            if record.lr < prev_lr {
+                stats.decreases += 1;
+            } else if record.lr > prev_lr {
+                stats.increases += 1;
+            }
+            prev_lr = record.lr;
+        }
+        Some(stats)
+    }
+}
+
+/// Records the trajectory of a scheduler into a [`History`].
+/// 
+/// At every `step`, the loss passed and the learning rate used for the step are recorded before the wrapped scheduler proceeds.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::history::{Recorded, Retention};
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = Recorded::new(ExponentialLR::new(1.0, 0.5, 0), Retention::Full);
+/// for loss in [1.0, 0.5, 0.25] {
+///     scheduler.step(loss);
+/// }
+/// let lrs: Vec<f64> = scheduler.history().records().map(|r| r.lr).collect();
+/// assert_eq!(lrs, [1.0, 0.5, 0.25]);
+/// assert_eq!(scheduler.history().stats().unwrap().area, 1.75);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recorded<S> {
+    scheduler: S,
+    history: History,
+    step: usize,
+}
+
+impl<S: Scheduler> Recorded<S> {
+    /// Constructs a Recorded instance.
+    pub fn new(scheduler: S, retention: Retention) -> Self {
+        Recorded { scheduler, history: History::new(retention), step: 0 }
+    }
+
+    /// Returns the recorded history.
+    pub fn history(&self) -> &History {
+        &self.history
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for Recorded<S> {
+    fn step(&mut self, loss: f64) {
+        let lr = self.scheduler.get_lr(loss);
+        self.history.push(self.step, loss, lr);
+        self.scheduler.step(loss);
+        self.step += 1;
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.scheduler.get_last_lr()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+/// Reconstructs the state of a loss-driven scheduler by replaying a recorded loss curve.
+/// 
+/// `scheduler` is stepped once with each of `losses` in order, so that wrappers such as
+/// [`SpikeRestart`](crate::spike_restart::SpikeRestart) and [`DivergenceGuard`](crate::guard::DivergenceGuard)
+/// make the same decisions and end with the same learning rate and counters as in the recorded run.
+/// This resumes training from checkpoints that store only the loss curve. Pass a scheduler in its initial state.
+/// 
+/// ```
+/// # use lr_schedulers::history::fast_forward;
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::spike_restart::SpikeRestart;
+/// # use lr_schedulers::Scheduler;
+/// let losses = [1.0, 1.0, 3.0, 1.0];
+/// let mut scheduler = SpikeRestart::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 2.0, 0.9, 0);
+/// for loss in losses {
+///     scheduler.step(loss);
+/// }
+/// let resumed = fast_forward(SpikeRestart::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 2.0, 0.9, 0), &losses);
+/// assert_eq!(resumed.get_lr(1.0), scheduler.get_lr(1.0));
+/// assert_eq!(resumed.restarts(), 1);
+/// ```
+pub fn fast_forward<S: Scheduler>(mut scheduler: S, losses: &[f64]) -> S {
+    for &loss in losses {
+        scheduler.step(loss);
+    }
+    scheduler
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use crate::guard::DivergenceGuard;
+    use crate::linear::LinearLR;
+    use crate::Scheduler;
+    use super::*;
+
+    #[test]
+    fn count_restarts() {
+        let mut scheduler = Recorded::new(CosineAnnealingWarmRestarts::new(1.0, 0.0, 2, 1, 0), Retention::Full);
+        for _ in 0 .. 9 {
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+        }
+        let stats = scheduler.history().stats().unwrap();
+        assert_eq!(stats.min_lr, 0.0);
+        assert_eq!(stats.max_lr, 1.0);
+        assert_eq!(stats.increases, 2);
+        assert_eq!(stats.decreases, 6);
+        assert!((stats.area - 4.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn ring_buffer() {
+        let mut history = History::new(Retention::Ring(3));
+        for step in 0 .. 10 {
+            history.push(step, 0.0, step as f64);
+        }
+        let steps: Vec<usize> = history.records().map(|r| r.step).collect();
+        assert_eq!(steps, [7, 8, 9]);
+        assert_eq!(history.last().unwrap().lr, 9.0);
+    }
+
+    #[test]
+    fn fast_forward_guard() {
+        let losses = [1.0, 1.1, 5.0, 1.0, f64::NAN, 1.0, 0.9];
+        let new = || DivergenceGuard::new(LinearLR::new(1.0, 1.0, 0.0, 10, 0), 2.0, 0.5, 0.1, 3);
+        let mut scheduler = new();
+        for (i, loss) in losses.iter().enumerate() {
+            scheduler.step(*loss);
+            let resumed = fast_forward(new(), &losses[.. i + 1]);
+            assert_eq!(resumed.get_lr(0.0), scheduler.get_lr(0.0), "Step {}", i);
+            assert_eq!(resumed.get_last_lr(), scheduler.get_last_lr(), "Step {}", i);
+            assert_eq!(resumed.trips(), scheduler.trips(), "Step {}", i);
+            assert_eq!(resumed.remaining_backoff_steps(), scheduler.remaining_backoff_steps(), "Step {}", i);
+        }
+    }
+
+    #[test]
+    fn empty_history() {
+        let mut history = History::new(Retention::Ring(0));
+        history.push(0, 0.0, 1.0);
+        assert!(history.is_empty());
+        assert!(history.stats().is_none());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use crate::epochs_to_steps;
+
+/// Rounding of a division that does not come out even.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Drops the remainder, as a data loader with `drop_last`.
+    Floor,
+    /// Counts the remainder as a whole, as a data loader yielding a last partial batch.
+    Ceil,
+}
+
+impl Rounding {
+    fn divide(self, numerator: usize, denominator: usize) -> usize {
+        let denominator = denominator.max(1);
+        match self {
+            Rounding::Floor => numerator / denominator,
+            Rounding::Ceil => numerator.div_ceil(denominator),
+        }
+    }
+}
+
+/// Number of optimizer steps of a training run, derived from the size of the dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrainingHorizon {
+    /// Number of optimizer steps in an epoch.
+    pub steps_per_epoch: usize,
+    /// Number of optimizer steps in the whole training.
+    pub total_steps: usize,
+}
+
+impl TrainingHorizon {
+    /// Returns the number of steps of a warmup over `fraction` of the training, rounded to the nearest step.
+    /// 
+    /// The result is clamped to `[0, total_steps]`.
+    pub fn warmup_steps(&self, fraction: f64) -> usize {
+        ((fraction * self.total_steps as f64).round() as usize).min(self.total_steps)
+    }
+
+    /// Converts a number of epochs, possibly fractional, to the nearest number of steps. See [`epochs_to_steps`].
+    pub fn epochs_to_steps(&self, epochs: f64) -> usize {
+        epochs_to_steps(epochs, self.steps_per_epoch)
+    }
+}
+
+/// Computes the number of optimizer steps of training on `num_samples` samples for `epochs` epochs.
+/// 
+/// An epoch consists of `num_samples / batch_size` batches, and an optimizer step is taken every `grad_accum` batches.
+/// Both divisions are rounded by `rounding`: `Rounding::Ceil` counts a last partial batch and a last partial accumulation
+/// as whole ones, as PyTorch's DataLoader with `drop_last=False` followed by a step at the end of every epoch,
+/// while `Rounding::Floor` drops them. The total is `epochs * steps_per_epoch` rounded to the nearest step,
+/// so fractional epochs are allowed. A `batch_size` or `grad_accum` of 0 is treated as 1.
+/// 
+/// The result feeds the constructors, e.g. `t_max` of [`CosineAnnealingLR::decay_once`](crate::cosine_annealing::CosineAnnealingLR::decay_once).
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::horizon::{from_dataloader, Rounding};
+/// // 1000 samples in batches of 32: 31 full batches and a partial one.
+/// let horizon = from_dataloader(1000, 32, 3.0, 4, Rounding::Ceil);
+/// assert_eq!(horizon.steps_per_epoch, 8);
+/// assert_eq!(horizon.total_steps, 24);
+/// assert_eq!(horizon.warmup_steps(0.1), 2);
+/// 
+/// let horizon = from_dataloader(1000, 32, 3.0, 4, Rounding::Floor);
+/// assert_eq!(horizon.steps_per_epoch, 7);
+/// ```
+pub fn from_dataloader(num_samples: usize, batch_size: usize, epochs: f64, grad_accum: usize, rounding: Rounding) -> TrainingHorizon {
+    let batches_per_epoch = rounding.divide(num_samples, batch_size);
+    let steps_per_epoch = rounding.divide(batches_per_epoch, grad_accum);
+    TrainingHorizon { steps_per_epoch, total_steps: epochs_to_steps(epochs, steps_per_epoch) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_each_division() {
+        // 10 batches of 10 samples, accumulated by 3.
+        assert_eq!(from_dataloader(100, 10, 1.0, 3, Rounding::Ceil).steps_per_epoch, 4);
+        assert_eq!(from_dataloader(100, 10, 1.0, 3, Rounding::Floor).steps_per_epoch, 3);
+        // 10 full batches and a partial one, accumulated by 11.
+        assert_eq!(from_dataloader(101, 10, 1.0, 11, Rounding::Ceil).steps_per_epoch, 1);
+        assert_eq!(from_dataloader(101, 10, 1.0, 11, Rounding::Floor).steps_per_epoch, 0);
+        let horizon = from_dataloader(5, 0, 2.5, 0, Rounding::Floor);
+        assert_eq!(horizon, TrainingHorizon { steps_per_epoch: 5, total_steps: 13 });
+        assert_eq!(horizon.warmup_steps(2.0), 13);
+        assert_eq!(horizon.epochs_to_steps(0.5), 3);
+    }
+}
\ No newline at end of file
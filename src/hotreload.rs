@@ -0,0 +1,306 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::Scheduler;
+
+/// Default number of steps between checks of the file watched by [`FileOverride`].
+pub const DEFAULT_POLL_INTERVAL: usize = 100;
+
+/// An override read from the file watched by [`FileOverride`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Override {
+    /// Sets the multiplier of the learning rate, written as `multiplier = <value>`.
+    Multiplier(f64),
+    /// Multiplies the current multiplier by a factor once, written as `reduce = <factor>`.
+    Reduce(f64),
+}
+
+/// An override applied by [`FileOverride`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AppliedOverride {
+    /// Number of steps processed before the override.
+    pub step: usize,
+    /// The override read from the file.
+    pub command: Override,
+    /// Multiplier of the learning rate after the override.
+    pub multiplier: f64,
+}
+
+/// Error in an override file. The whole file is ignored when it has an error.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverrideError {
+    /// The file could not be read.
+    Io(String),
+    /// A line could not be parsed, with its line number starting from 1.
+    Parse(usize, String),
+}
+
+impl fmt::Display for OverrideError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverrideError::Io(message) => write!(f, "cannot read the override file: {}", message),
+            OverrideError::Parse(line, text) => write!(f, "invalid override at line {}: {}", line, text),
+        }
+    }
+}
+
+impl std::error::Error for OverrideError {}
+
+/// Parses the content of an override file.
+/// 
+/// Each non-empty line is `multiplier = <value>` or `reduce = <factor>`. Lines starting with `#` are comments.
+pub fn parse(content: &str) -> Result<Vec<Override>, OverrideError> {
+    let mut overrides = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = || OverrideError::Parse(i + 1, line.to_string());
+        let (key, value) = line.split_once('=').ok_or_else(invalid)?;
+        let value: f64 = value.trim().parse().map_err(|_| invalid())?;
+        if !value.is_finite() || value < 0.0 {
+            return Err(invalid());
+        }
+        match key.trim() {
+            "multiplier" => overrides.push(Override::Multiplier(value)),
+            "reduce" => overrides.push(Override::Reduce(value)),
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(overrides)
+}
+
+/// Reads the modification time and the content of a file, or returns None if it does not exist.
+fn read(path: &Path) -> io::Result<Option<(Option<SystemTime>, String)>> {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e),
+    };
+    Ok(Some((metadata.modified().ok(), fs::read_to_string(path)?)))
+}
+
+/// Applies overrides written to a file to the learning rate of a running scheduler.
+/// 
+/// The file is checked at every `poll_interval` steps ([`DEFAULT_POLL_INTERVAL`] by default), and its overrides are applied
+/// whenever it has been modified or touched since the last check.
+/// So `reduce` is applied once per modification, while `multiplier` sets the same value every time.
+/// The content of the file at construction is left over from an earlier run, and is ignored unless `with_existing_overrides` is set.
+/// A missing file leaves the current multiplier unchanged. Every applied override is recorded in `log`.
+/// 
+/// This lets an operator adjust a job on a cluster where files can be edited but a debugger cannot be attached.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::hotreload::{FileOverride, Override};
+/// # use lr_schedulers::Scheduler;
+/// let path = std::env::temp_dir().join(format!("lr-override-doc-{}", std::process::id()));
+/// let mut scheduler = FileOverride::new(ExponentialLR::new(1.0, 0.5, 0), &path).with_poll_interval(1);
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 0.5);
+/// 
+/// std::fs::write(&path, "reduce = 0.1\n").unwrap();
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 0.025);
+/// assert_eq!(scheduler.log()[0].command, Override::Reduce(0.1));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FileOverride<S> {
+    scheduler: S,
+    path: PathBuf,
+    poll_interval: usize,
+    multiplier: f64,
+    steps: usize,
+    seen: Option<(Option<SystemTime>, String)>,
+    log: Vec<AppliedOverride>,
+    last_error: Option<OverrideError>,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> FileOverride<S> {
+    /// Constructs a FileOverride instance watching `path`, ignoring the content of the file at construction.
+    pub fn new(scheduler: S, path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref().to_path_buf();
+        FileOverride {
+            scheduler,
+            seen: read(&path).ok().flatten(),
+            path,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            multiplier: 1.0,
+            steps: 0,
+            log: Vec::new(),
+            last_error: None,
+            last_lr: None,
+        }
+    }
+
+    /// Checks the file only every `poll_interval` steps to reduce file system access. An interval of 0 is treated as 1.
+    pub fn with_poll_interval(self, poll_interval: usize) -> Self {
+        FileOverride { poll_interval: poll_interval.max(1), ..self }
+    }
+
+    /// Applies the content of the file at construction at the first check, e.g. to restart a job with the overrides it was running with.
+    pub fn with_existing_overrides(self) -> Self {
+        FileOverride { seen: None, ..self }
+    }
+
+    /// Checks the file now and applies its overrides if it has changed. This is also done by `step`.
+    pub fn check(&mut self) {
+        let seen = match read(&self.path) {
+            Ok(Some(seen)) => Some(seen),
+            Ok(None) => return,
+            Err(e) => {
+                self.last_error = Some(OverrideError::Io(e.to_string()));
+                return;
+            }
+        };
+        if seen == self.seen {
+            return;
+        }
+        let overrides = parse(&seen.as_ref().unwrap().1);
+        self.seen = seen;
+        match overrides {
+            Ok(overrides) => {
+                self.last_error = None;
+                for command in overrides {
+                    match command {
+                        Override::Multiplier(multiplier) => self.multiplier = multiplier,
+                        Override::Reduce(factor) => self.multiplier *= factor,
+                    }
+                    self.log.push(AppliedOverride { step: self.steps, command, multiplier: self.multiplier });
+                }
+            }
+            Err(e) => self.last_error = Some(e),
+        }
+    }
+
+    /// Returns the current multiplier of the learning rate.
+    pub fn multiplier(&self) -> f64 {
+        self.multiplier
+    }
+
+    /// Returns every override applied so far, in order.
+    pub fn log(&self) -> &[AppliedOverride] {
+        &self.log
+    }
+
+    /// Returns the error of the last check, if any.
+    pub fn last_error(&self) -> Option<&OverrideError> {
+        self.last_error.as_ref()
+    }
+
+    /// Returns the watched path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for FileOverride<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.scheduler.step(loss);
+        self.steps += 1;
+        if self.steps.is_multiple_of(self.poll_interval) {
+            self.check();
+        }
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.multiplier * self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant::ConstantLR;
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("lr-override-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn parse_lines() {
+        let overrides = parse("# comment\n\nmultiplier = 0.5\n  reduce=0.1  \n").unwrap();
+        assert_eq!(overrides, [Override::Multiplier(0.5), Override::Reduce(0.1)]);
+        assert_eq!(parse("reduce 0.1"), Err(OverrideError::Parse(1, "reduce 0.1".to_string())));
+        assert!(parse("scale = 2").is_err());
+        assert!(parse("reduce = -1").is_err());
+        assert!(parse("multiplier = NaN").is_err());
+    }
+
+    #[test]
+    fn apply_changes_once() {
+        let path = temp_path("apply");
+        let _ = fs::remove_file(&path);
+        let mut scheduler = FileOverride::new(ConstantLR::new(1.0, 1.0, 0, 0), &path).with_poll_interval(2);
+        scheduler.step(0.0);
+        fs::write(&path, "multiplier = 0.5\n").unwrap();
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.5);
+        fs::write(&path, "reduce = 0.5\n").unwrap();
+        scheduler.step(0.0);
+        // Not checked at an odd step.
+        assert_eq!(scheduler.get_lr(0.0), 0.5);
+        for i in 0 .. 4 {
+            scheduler.step(0.0);
+            assert_eq!(scheduler.get_lr(0.0), 0.25, "Step {}", i);
+        }
+        fs::write(&path, "reduce = oops\n").unwrap();
+        scheduler.step(0.0);
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.25);
+        assert!(matches!(scheduler.last_error(), Some(OverrideError::Parse(1, _))));
+        fs::remove_file(&path).unwrap();
+        scheduler.step(0.0);
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.25);
+        let log = scheduler.log();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0], AppliedOverride { step: 2, command: Override::Multiplier(0.5), multiplier: 0.5 });
+        assert_eq!(log[1], AppliedOverride { step: 4, command: Override::Reduce(0.5), multiplier: 0.25 });
+    }
+
+    #[test]
+    fn existing_content() {
+        let path = temp_path("existing");
+        fs::write(&path, "reduce = 0.5\n").unwrap();
+        let mut scheduler = FileOverride::new(ConstantLR::new(1.0, 1.0, 0, 0), &path).with_poll_interval(1);
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 1.0);
+        assert!(scheduler.log().is_empty());
+        let mut scheduler = FileOverride::new(ConstantLR::new(1.0, 1.0, 0, 0), &path).with_existing_overrides();
+        for i in 1 .. DEFAULT_POLL_INTERVAL {
+            scheduler.step(0.0);
+            assert_eq!(scheduler.get_lr(0.0), 1.0, "Step {}", i);
+        }
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.5);
+        fs::remove_file(&path).unwrap();
+    }
+}
\ No newline at end of file
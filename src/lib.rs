@@ -3,10 +3,518 @@ pub mod linear;
 pub mod exponential;
 pub mod cosine_annealing;
 pub mod cosine_annealing_warm_restarts;
+pub mod wsd;
+pub mod shared;
+pub mod distributed;
+pub mod domain;
+pub mod time_based;
+pub mod value;
+pub mod coefficient;
+pub mod policy;
+pub mod batch_size;
+pub mod plateau;
+pub mod early_stopping;
+pub mod noise_scale;
+pub mod history;
+pub mod analysis;
+pub mod compiled;
+pub mod config;
+pub mod batch;
+pub mod any;
+pub mod applied;
+pub mod snapshot;
+pub mod on_cycle;
+pub mod random_amplitude;
+pub mod replay;
+pub mod spike_restart;
+pub mod guard;
+pub mod presets;
+pub mod advisor;
+pub mod search;
+pub mod fit;
+pub mod pbt;
+pub mod pause;
+pub mod cooldown;
+pub mod gated;
+pub mod combinators;
+pub mod phases;
+pub mod dropout;
+pub mod weight_decay;
+pub mod trust_ratio;
+pub mod trust_region;
+pub mod groups;
+pub mod gan;
+pub mod units;
+pub mod horizon;
+pub mod rng;
+pub mod semantics;
+pub mod plot;
+pub mod pid;
+pub mod bandit;
+pub mod testing;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "hotreload")]
+pub mod hotreload;
+#[cfg(feature = "serde")]
+pub mod checkpoint;
+#[cfg(feature = "bench")]
+pub mod bench;
 
-pub trait Scheduler {
-    /// Proceeds the step of scheduler.
-    fn step(&mut self, loss: f64);
-    /// Returns a learning rate for the current step.
-    fn get_lr(&self, loss: f64) -> f64; // The argument `loss` is for schedulers such as ReduceLROnPlateau.
+mod rotation;
+
+/// Common interface of learning rate schedulers, defined in the `lr-schedulers-core` crate.
+/// 
+/// Every scheduler in this crate is `Send + Sync`, so it can be moved into a training task as `Box<dyn Scheduler + Send>`.
+/// Wrappers and combinators are `Send + Sync` whenever the schedulers, transports, clocks and callbacks they hold are,
+/// except that [`RemoteControlled`](crate::remote::RemoteControlled) over a `ChannelTransport` is only `Send`.
+/// This is checked at compile time for each of them.
+/// 
+/// The schedulers of this crate never panic in `step`, `get_lr`, `get_last_lr` or `lr_at`, whatever the constructor parameters
+/// including `init_step`. Integer parameters out of range are clamped as documented on each constructor,
+/// step counts saturate at `usize::MAX`, and non-finite float parameters give non-finite learning rates instead of panics.
+pub use lr_schedulers_core::Scheduler;
+
+/// Schedulers whose learning rate is a function of the step only.
+/// 
+/// Such schedulers can be evaluated at an arbitrary step without being advanced, which enables random access and previews.
+/// Their `step` computes the same formula as `lr_at`, so both agree up to rounding errors.
+pub trait ClosedForm: Scheduler {
+    /// Returns the learning rate at `step` without modifying the scheduler.
+    /// 
+    /// Steps are counted from 0 in the same way as `init_step` of the constructors.
+    fn lr_at(&self, step: usize) -> f64;
+    /// Returns the learning rate at a fractional step `t`, e.g. from the progress of a token or time budget, or for plotting.
+    /// 
+    /// By default, the learning rate is interpolated linearly between the neighboring steps, including across a restart.
+    /// Schedulers defined by a continuous formula evaluate the formula at `t` instead.
+    /// Negative and NaN values of `t` are treated as 0, and `lr_at_f64` agrees with `lr_at` at whole steps up to rounding errors.
+    /// 
+    /// ```
+    /// # use lr_schedulers::linear::LinearLR;
+    /// # use lr_schedulers::ClosedForm;
+    /// let scheduler = LinearLR::new(1.0, 0.0, 1.0, 4, 0);
+    /// assert_eq!(scheduler.lr_at_f64(1.5), 0.375);
+    /// assert_eq!(scheduler.lr_at_f64(2.0), scheduler.lr_at(2));
+    /// ```
+    fn lr_at_f64(&self, t: f64) -> f64 {
+        let t = t.max(0.0);
+        let step = t.floor();
+        let fraction = t - step;
+        // The cast saturates at usize::MAX.
+        let lr = self.lr_at(step as usize);
+        if fraction > 0.0 {
+            (self.lr_at((step as usize).saturating_add(1)) - lr).mul_add(fraction, lr)
+        } else {
+            lr
+        }
+    }
+    /// Returns the number of steps of a finite schedule, after which the learning rate stays at `lr_at(horizon)`.
+    /// 
+    /// Returns None if the learning rate keeps changing, which is the default.
+    fn horizon(&self) -> Option<usize> {
+        None
+    }
+    /// Plays `next` after the horizon of this schedule. See [`Concat`](combinators::Concat).
+    /// 
+    /// Returns an error if this schedule has no horizon.
+    fn then<B: ClosedForm>(self, next: B) -> Result<combinators::Concat<Self, B>, combinators::Unbounded>
+    where
+        Self: Sized,
+    {
+        combinators::Concat::new(self, next)
+    }
+}
+
+impl<S: ClosedForm + ?Sized> ClosedForm for Box<S> {
+    fn lr_at(&self, step: usize) -> f64 {
+        (**self).lr_at(step)
+    }
+
+    fn lr_at_f64(&self, t: f64) -> f64 {
+        (**self).lr_at_f64(t)
+    }
+
+    fn horizon(&self) -> Option<usize> {
+        (**self).horizon()
+    }
+}
+
+/// Schedulers whose learning rate repeats in cycles.
+/// 
+/// Steps are counted from the beginning of training, including `init_step`.
+pub trait Cyclical: Scheduler {
+    /// Returns the index of the current cycle, starting from 0.
+    fn current_cycle(&self) -> usize;
+    /// Returns the number of steps from the beginning of the current cycle to the current step.
+    fn steps_into_cycle(&self) -> usize;
+    /// Returns the number of steps in the current cycle.
+    fn cycle_length(&self) -> usize;
+    /// Returns the number of times a new cycle has started.
+    fn restarts_so_far(&self) -> usize {
+        self.current_cycle()
+    }
+    /// Returns true if the learning rate of the current step is the minimum of the current cycle.
+    /// 
+    /// By default, the minimum is assumed to be at the last step of a cycle.
+    fn at_cycle_minimum(&self) -> bool {
+        self.steps_into_cycle() + 1 == self.cycle_length()
+    }
+}
+
+/// Schedulers whose learning rate is known to stay within bounds, e.g. to check it against the stability limit of an optimizer.
+pub trait Bounded: Scheduler {
+    /// Returns the infimum and the supremum of the learning rate over all steps from step 0.
+    /// 
+    /// The bounds are exact up to rounding errors of the learning rates, but may not be attained,
+    /// e.g. by an exponential decay approaching 0.
+    /// A learning rate growing without bound gives an infinite bound.
+    fn bounds(&self) -> (f64, f64);
+}
+
+impl<S: Bounded + ?Sized> Bounded for Box<S> {
+    fn bounds(&self) -> (f64, f64) {
+        (**self).bounds()
+    }
+}
+
+/// Schedulers whose learning rate is monotone between known steps, so that the step reaching a learning rate is found by bisection.
+pub trait PiecewiseMonotone: ClosedForm {
+    /// Returns the pieces on which the learning rate is monotone, as the first and the last step of each piece, in order of steps.
+    /// 
+    /// The pieces cover every learning rate the schedule takes, so they may stop early, e.g. after the first decay of a periodic schedule.
+    /// The last step of the last piece may be `usize::MAX`.
+    fn monotone_pieces(&self) -> Vec<(usize, usize)>;
+    /// Returns the first step whose learning rate equals `target_lr` or has passed it, e.g. to act when the learning rate drops below a value.
+    /// 
+    /// The learning rate is evaluated by `lr_at` a logarithmic number of times in the length of each piece,
+    /// without computing the trajectory. A warmup from 0 passes any target below the peak,
+    /// so a target of the decay after a warmup is reached during the warmup first.
+    /// Returns None if the schedule never reaches `target_lr`.
+    /// 
+    /// ```
+    /// # use lr_schedulers::exponential::ExponentialLR;
+    /// # use lr_schedulers::{ClosedForm, PiecewiseMonotone};
+    /// let scheduler = ExponentialLR::new(1.0, 0.5, 0);
+    /// assert_eq!(scheduler.step_for_lr(0.2), Some(3));
+    /// assert_eq!(scheduler.step_for_lr(0.25), Some(2));
+    /// assert_eq!(scheduler.step_for_lr(2.0), None);
+    /// ```
+    fn step_for_lr(&self, target_lr: f64) -> Option<usize> {
+        self.monotone_pieces().into_iter().find_map(|(first, last)| {
+            let first_lr = self.lr_at(first);
+            if first_lr == target_lr {
+                return Some(first);
+            }
+            let reached = |step: usize| {
+                let lr = self.lr_at(step);
+                if first_lr < target_lr { lr >= target_lr } else { lr <= target_lr }
+            };
+            if first == last || !reached(last) {
+                return None;
+            }
+            // The target is not reached at `low` and is reached at `high`.
+            let (mut low, mut high) = (first, last);
+            while high - low > 1 {
+                let middle = low + (high - low) / 2;
+                if reached(middle) {
+                    high = middle;
+                } else {
+                    low = middle;
+                }
+            }
+            Some(high)
+        })
+    }
+}
+
+/// Converts a number of epochs, possibly fractional, to the nearest number of steps.
+/// 
+/// Negative and NaN epochs are converted to 0 steps.
+/// 
+/// ```
+/// # use lr_schedulers::epochs_to_steps;
+/// assert_eq!(epochs_to_steps(2.0, 100), 200);
+/// assert_eq!(epochs_to_steps(0.5, 391), 196);
+/// ```
+pub fn epochs_to_steps(epochs: f64, steps_per_epoch: usize) -> usize {
+    (epochs * steps_per_epoch as f64).round() as usize
+}
+
+/// Returns the fraction of a schedule of `horizon` steps completed at `step`. A schedule of 0 steps is complete.
+fn progress_at(step: usize, horizon: usize) -> f64 {
+    if horizon == 0 {
+        1.0
+    } else {
+        step.min(horizon) as f64 / horizon as f64
+    }
+}
+
+const fn assert_send_sync<T: Send + Sync>() {}
+
+#[cfg(feature = "remote")]
+const fn assert_send<T: Send>() {}
+
+// Checks the wrappers and combinators for any contents that are `Send + Sync`.
+const fn assert_wrappers_send_sync<S: Send + Sync, T: Send + Sync, F: Send + Sync>() {
+    assert_send_sync::<applied::Applied<S>>();
+    assert_send_sync::<bandit::BanditScheduler<S>>();
+    assert_send_sync::<batch_size::BatchSizeScheduler<S>>();
+    assert_send_sync::<combinators::Reversed<S>>();
+    assert_send_sync::<combinators::Repeated<S>>();
+    assert_send_sync::<combinators::Concat<S, T>>();
+    assert_send_sync::<combinators::Warmup<S>>();
+    assert_send_sync::<combinators::Blend<S, T>>();
+    assert_send_sync::<combinators::Modulated<S, T>>();
+    assert_send_sync::<cooldown::LinearCooldown<S>>();
+    assert_send_sync::<distributed::DistributedScheduler<S, T>>();
+    assert_send_sync::<domain::DomainScheduler<S>>();
+    assert_send_sync::<dropout::LrDropout<S>>();
+    assert_send_sync::<gan::DualScheduler<S, T>>();
+    assert_send_sync::<gated::GatedWarmup<S>>();
+    assert_send_sync::<groups::GroupScheduler<S>>();
+    assert_send_sync::<guard::DivergenceGuard<S>>();
+    assert_send_sync::<history::Recorded<S>>();
+    assert_send_sync::<noise_scale::NoiseScaleAdaptive<S>>();
+    assert_send_sync::<on_cycle::OnCycle<S, F>>();
+    assert_send_sync::<pause::Pausable<S>>();
+    assert_send_sync::<pbt::Perturbed<S>>();
+    assert_send_sync::<random_amplitude::RandomAmplitude<S>>();
+    assert_send_sync::<shared::SharedScheduler<S>>();
+    assert_send_sync::<snapshot::SnapshotSchedule<S>>();
+    assert_send_sync::<spike_restart::SpikeRestart<S>>();
+    assert_send_sync::<time_based::TimeBasedScheduler<S, T>>();
+    assert_send_sync::<trust_ratio::TrustRatioScheduler<S>>();
+    assert_send_sync::<trust_region::TrustRegion<S>>();
+    assert_send_sync::<value::AsValue<S>>();
+    assert_send_sync::<weight_decay::CoupledWeightDecay<S>>();
+    #[cfg(feature = "hotreload")]
+    assert_send_sync::<hotreload::FileOverride<S>>();
+    #[cfg(feature = "remote")]
+    assert_send_sync::<remote::RemoteControlled<S, T>>();
+}
+
+// Fails to compile when a scheduler loses the thread-safety guarantee documented on `Scheduler`.
+const _: () = {
+    assert_send_sync::<constant::ConstantLR>();
+    assert_send_sync::<linear::LinearLR>();
+    assert_send_sync::<exponential::ExponentialLR>();
+    assert_send_sync::<cosine_annealing::CosineAnnealingLR>();
+    assert_send_sync::<cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts>();
+    assert_send_sync::<wsd::WarmupStableDecay>();
+    assert_send_sync::<phases::Schedule>();
+    assert_send_sync::<compiled::CompiledSchedule>();
+    assert_send_sync::<any::AnyScheduler>();
+    assert_send_sync::<replay::TraceScheduler>();
+    assert_send_sync::<pid::PidLR>();
+    assert_send_sync::<bandit::BanditScheduler<any::AnyScheduler>>();
+    assert_send_sync::<shared::SharedScheduler<Box<dyn Scheduler + Send>>>();
+    assert_send_sync::<coefficient::AdaptiveCoefficient>();
+    assert_send_sync::<plateau::Plateau>();
+    assert_send_sync::<early_stopping::EarlyStopping>();
+    assert_send_sync::<time_based::SystemClock>();
+    assert_send_sync::<time_based::ManualClock>();
+    assert_wrappers_send_sync::<constant::ConstantLR, cosine_annealing::CosineAnnealingLR, fn(usize, f64)>();
+    #[cfg(feature = "remote")]
+    assert_send::<remote::RemoteControlled<constant::ConstantLR, remote::ChannelTransport>>();
+};
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use approx::relative_eq;
+    use crate::exponential::ExponentialLR;
+    use super::*;
+
+    #[test]
+    fn boxed_scheduler_moves_across_threads() {
+        let mut scheduler: Box<dyn Scheduler + Send> = Box::new(ExponentialLR::new(2.0, 0.5, 0));
+        let lr = thread::spawn(move || {
+            scheduler.step(0.0);
+            scheduler.get_lr(0.0)
+        })
+        .join()
+        .unwrap();
+        assert_eq!(lr, 1.0);
+    }
+
+    #[test]
+    fn progress_of_finite_schedules() {
+        let finite: [Box<dyn Scheduler>; 4] = [
+            Box::new(constant::ConstantLR::new(1.0, 0.5, 4, 1)),
+            Box::new(linear::LinearLR::new(1.0, 0.1, 1.0, 4, 1)),
+            Box::new(cosine_annealing::CosineAnnealingLR::decay_once(1.0, 0.1, 4, 1)),
+            Box::new(wsd::WarmupStableDecay::new(1.0, 1, 1).with_decay(2, 2, 0.0, wsd::DecayShape::Linear)),
+        ];
+        for (i, mut scheduler) in finite.into_iter().enumerate() {
+            for (remaining, progress) in [(3, 0.25), (2, 0.5), (1, 0.75), (0, 1.0), (0, 1.0)] {
+                assert_eq!(scheduler.remaining_steps(), Some(remaining), "Scheduler {}", i);
+                assert_eq!(scheduler.progress(), Some(progress), "Scheduler {}", i);
+                scheduler.step(0.0);
+            }
+        }
+        let unbounded: [Box<dyn Scheduler>; 3] = [
+            Box::new(exponential::ExponentialLR::new(1.0, 0.5, 0)),
+            Box::new(cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts::new(1.0, 0.1, 4, 1, 0)),
+            Box::new(wsd::WarmupStableDecay::new(1.0, 1, 0)),
+        ];
+        for scheduler in unbounded {
+            assert_eq!(scheduler.remaining_steps(), None);
+            assert_eq!(scheduler.progress(), None);
+        }
+        let empty = compiled::CompiledSchedule::from_lrs(Vec::new(), compiled::Precision::F64, 0);
+        assert_eq!((empty.remaining_steps(), empty.progress()), (Some(0), Some(1.0)));
+    }
+
+    #[test]
+    fn progress_of_wrappers_and_combinators() {
+        let linear = |total_iters| linear::LinearLR::new(1.0, 1.0, 0.0, total_iters, 0);
+        let finite: [Box<dyn Scheduler>; 9] = [
+            Box::new(pause::Pausable::new(linear(4))),
+            Box::new(pbt::Perturbed::new(linear(4))),
+            Box::new(history::Recorded::new(linear(4), history::Retention::Full)),
+            Box::new(gated::GatedWarmup::new(linear(4), 0.1, gated::Gate::Below(0.0)).with_max_hold(0)),
+            Box::new(cooldown::LinearCooldown::new(linear(4))),
+            Box::new(combinators::Reversed::new(linear(4)).unwrap()),
+            Box::new(linear(2).then(linear(2)).unwrap()),
+            Box::new(combinators::Warmup::new(linear(4), 2)),
+            Box::new(combinators::Blend::new(linear(4), linear(4), 0, 2, combinators::Crossfade::Linear)),
+        ];
+        for (i, mut scheduler) in finite.into_iter().enumerate() {
+            for (remaining, progress) in [(4, 0.0), (3, 0.25), (2, 0.5), (1, 0.75), (0, 1.0), (0, 1.0)] {
+                assert_eq!(scheduler.remaining_steps(), Some(remaining), "Scheduler {}", i);
+                assert_eq!(scheduler.progress(), Some(progress), "Scheduler {}", i);
+                scheduler.step(0.0);
+            }
+        }
+        let shared = shared::SharedScheduler::new(linear(4));
+        shared.step(0.0);
+        assert_eq!((shared.remaining_steps(), shared.progress()), (Some(3), Some(0.25)));
+    }
+
+    #[test]
+    fn epochs_to_steps_rounds_and_saturates() {
+        assert_eq!(epochs_to_steps(1.0 / 3.0, 10), 3);
+        assert_eq!(epochs_to_steps(0.25, 10), 3);
+        assert_eq!(epochs_to_steps(-1.0, 10), 0);
+        assert_eq!(epochs_to_steps(f64::NAN, 10), 0);
+    }
+
+    #[test]
+    fn last_lr_is_previous_lr() {
+        for init_step in [0, 3] {
+            let configs = [
+                config::SchedulerConfig::Constant { base_lr: 1.0, factor: 0.5, total_iters: 3 },
+                config::SchedulerConfig::Linear { base_lr: 1.0, start_factor: 0.1, end_factor: 1.0, total_iters: 5 },
+                config::SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.9, decay_steps: 1, staircase: false },
+                config::SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 4, decay_once: false, min_fraction: None },
+                config::SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.1, t_0: 2, t_mult: 2, steps_per_epoch: 1, semantics: semantics::Semantics::Native },
+            ];
+            for config in configs {
+                let mut scheduler = config.build_any(init_step);
+                let expected = init_step.checked_sub(1).map(|step| scheduler.lr_at(step));
+                assert_eq!(scheduler.get_last_lr(), expected, "{:?}", config);
+                for i in 0 .. 10 {
+                    let lr = scheduler.get_lr(0.0);
+                    scheduler.step(0.0);
+                    assert_eq!(scheduler.get_last_lr(), Some(lr), "{:?}: Step {}", config, i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn never_panic_on_extreme_parameters() {
+        let floats = [0.0, -1.0, 1e308, f64::NAN, f64::INFINITY, f64::NEG_INFINITY];
+        let ints = [0, 1, 2, usize::MAX / 2, usize::MAX / 2 + 1, usize::MAX];
+        let mut configs = Vec::new();
+        for &x in &floats {
+            for &n in &ints {
+                configs.push(config::SchedulerConfig::Constant { base_lr: 1.0, factor: x, total_iters: n });
+                configs.push(config::SchedulerConfig::Linear { base_lr: x, start_factor: 0.1, end_factor: x, total_iters: n });
+                configs.push(config::SchedulerConfig::Exponential { base_lr: 1.0, gamma: x, decay_steps: 1, staircase: false });
+                configs.push(config::SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: x, t_max: n, decay_once: false, min_fraction: None });
+                for &m in &ints {
+                    configs.push(config::SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: x, eta_1: 0.0, t_0: n, t_mult: m, steps_per_epoch: 1, semantics: semantics::Semantics::Native });
+                }
+            }
+        }
+        for config in &configs {
+            for init_step in [0, 1, usize::MAX / 2, usize::MAX - 1, usize::MAX] {
+                let mut scheduler = config.build_any(init_step);
+                for _ in 0 .. 3 {
+                    scheduler.get_lr(0.0);
+                    scheduler.get_last_lr();
+                    scheduler.step(f64::NAN);
+                }
+                for step in [0, 1, usize::MAX / 2, usize::MAX] {
+                    scheduler.lr_at(step);
+                }
+                scheduler.horizon();
+            }
+        }
+        let mut cosine = cosine_annealing::CosineAnnealingLR::decay_once(1.0, 0.0, usize::MAX, usize::MAX);
+        let mut restarts = cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts::new(1.0, 0.0, usize::MAX, usize::MAX, usize::MAX);
+        for _ in 0 .. 3 {
+            cosine.step(0.0);
+            restarts.step(0.0);
+            assert_eq!(cosine.get_lr(0.0), 0.0);
+            assert!(cosine.steps_into_cycle() < cosine.cycle_length());
+            assert!(restarts.steps_into_cycle() < restarts.cycle_length());
+            assert!(restarts.current_cycle() > 0 && !restarts.at_cycle_minimum());
+        }
+    }
+
+    #[test]
+    fn bounds_are_tight() {
+        let configs = [
+            config::SchedulerConfig::Constant { base_lr: 1.0, factor: 0.5, total_iters: 3 },
+            config::SchedulerConfig::Constant { base_lr: 1.0, factor: 0.5, total_iters: 0 },
+            config::SchedulerConfig::Linear { base_lr: 2.0, start_factor: 1.0, end_factor: 0.1, total_iters: 5 },
+            config::SchedulerConfig::Exponential { base_lr: 1.0, gamma: -0.5, decay_steps: 1, staircase: false },
+            config::SchedulerConfig::CosineAnnealing { eta_0: 0.1, eta_1: 1.0, t_max: 4, decay_once: false, min_fraction: None },
+            config::SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.1, t_0: 2, t_mult: 2, steps_per_epoch: 1, semantics: semantics::Semantics::Native },
+        ];
+        for config in configs {
+            let scheduler = config.build_any(0);
+            let lrs: Vec<_> = (0 .. 100).map(|step| scheduler.lr_at(step)).collect();
+            let min = lrs.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = lrs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let (lower, upper) = scheduler.bounds();
+            assert!(relative_eq!(lower, min, epsilon = 1e-12) && relative_eq!(upper, max, epsilon = 1e-12), "{:?}", config);
+        }
+    }
+
+    #[test]
+    fn step_for_lr_is_first_crossing() {
+        let schedulers: [Box<dyn PiecewiseMonotone>; 6] = [
+            Box::new(constant::ConstantLR::new(1.0, 0.5, 4, 0)),
+            Box::new(linear::LinearLR::new(1.0, 1.0, 0.1, 30, 0)),
+            Box::new(exponential::ExponentialLR::new(1.0, 0.9, 0).with_decay_steps(3, true)),
+            Box::new(cosine_annealing::CosineAnnealingLR::new(1.0, 0.1, 20, 0)),
+            Box::new(wsd::WarmupStableDecay::new(1.0, 10, 0).with_decay(30, 20, 0.1, wsd::DecayShape::OneMinusSqrt)),
+            Box::new(phases::Schedule::phases(1.0).hold(5).decay(20, phases::Decay::Exponential { to: 0.1 }).warmup(10, phases::Ramp::Cosine).build(0)),
+        ];
+        for (i, scheduler) in schedulers.iter().enumerate() {
+            for target in [0.0, 0.05, 0.1, 0.3, 0.5, 0.55, 0.95, 1.0, 1.5] {
+                let initial = scheduler.lr_at(0);
+                // The exponential decay underflows to 0 after about 21000 steps.
+                let expected = (0 .. 30_000).find(|&step| {
+                    let lr = scheduler.lr_at(step);
+                    let previous = step.checked_sub(1).map_or(initial, |step| scheduler.lr_at(step));
+                    lr == target || (lr - target).signum() != (previous - target).signum()
+                });
+                assert_eq!(scheduler.step_for_lr(target), expected, "Scheduler {}: target {}", i, target);
+            }
+        }
+    }
+
+    #[test]
+    fn boxed_scheduler_is_shareable() {
+        let scheduler: Box<dyn Scheduler + Send> = Box::new(ExponentialLR::new(2.0, 0.5, 0));
+        let scheduler = shared::SharedScheduler::new(scheduler);
+        let worker = scheduler.clone();
+        thread::spawn(move || worker.step(0.0)).join().unwrap();
+        assert_eq!(scheduler.lr(), 1.0);
+    }
 }
\ No newline at end of file
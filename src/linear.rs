@@ -1,4 +1,5 @@
-use crate::Scheduler;
+use crate::pbt::Perturbable;
+use crate::{progress_at, Bounded, ClosedForm, PiecewiseMonotone, Scheduler};
 
 /// Changes the learning rate linearly until the number of steps reaches a given number.
 /// 
@@ -65,63 +66,124 @@ impl LinearLR {
     /// This scheduler returns learning rate that interpolates `start_factor * base_lr` and `end_factor * base_lr` when the number of steps is between 0 and `total_iters`.
     /// After that, this returns `end_factor * base_lr`.
     /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
-    pub fn new(
+    /// 
+    /// This is a `const fn`, so the scheduler can be constructed in `const` and `static` items.
+    pub const fn new(
         base_lr: f64,
         start_factor: f64,
         end_factor: f64,
         total_iters: usize,
         init_step: usize
     ) -> Self {
-        if init_step >= total_iters {
-            LinearLR {
-                lr: end_factor * base_lr,
-                base_lr,
-                step: init_step,
-                total_iters,
-                grad: 0.0, // Dummy gradient
-                start_factor,
-                end_factor,
-            }
-        } else if init_step == 0 {
-            let grad = (end_factor - start_factor) / (total_iters as f64);
-            LinearLR {
-                lr: start_factor * base_lr,
-                base_lr,
-                step: 0,
-                total_iters,
-                grad,
-                start_factor,
-                end_factor,
-            }
+        let grad = if total_iters > 0 {
+            (end_factor - start_factor) / (total_iters as f64)
+        } else {
+            0.0 // Dummy gradient
+        };
+        let mut scheduler = LinearLR {
+            lr: end_factor * base_lr,
+            base_lr,
+            step: init_step,
+            total_iters,
+            grad,
+            start_factor,
+            end_factor,
+        };
+        scheduler.lr = scheduler.value_at(init_step);
+        scheduler
+    }
+
+    /// Returns the learning rates of the first `N` steps.
+    /// 
+    /// This is a `const fn`, so short schedules can be baked into static arrays at compile time.
+    /// 
+    /// ```
+    /// # use lr_schedulers::linear::LinearLR;
+    /// const LRS: [f64; 4] = LinearLR::new(1.0, 0.5, 1.0, 2, 0).table::<4>();
+    /// assert_eq!(LRS, [0.5, 0.75, 1.0, 1.0]);
+    /// ```
+    pub const fn table<const N: usize>(&self) -> [f64; N] {
+        let mut lrs = [0.0; N];
+        let mut i = 0;
+        while i < N {
+            lrs[i] = self.value_at(i);
+            i += 1;
+        }
+        lrs
+    }
+
+    /// Changes `base_lr` without resetting the step.
+    pub fn set_base_lr(&mut self, base_lr: f64) {
+        self.base_lr = base_lr;
+        self.lr = self.value_at(self.step);
+    }
+
+    const fn value_at(&self, step: usize) -> f64 {
+        if step >= self.total_iters {
+            self.end_factor * self.base_lr
         } else {
-            let grad = (end_factor - start_factor) / (total_iters as f64);
-            let lr = base_lr * (init_step as f64).mul_add(grad, start_factor);
-            LinearLR {
-                lr,
-                base_lr,
-                step: init_step,
-                total_iters,
-                grad,
-                start_factor,
-                end_factor,
-            }
+            self.base_lr * (step as f64).mul_add(self.grad, self.start_factor)
         }
     }
+
+    /// Returns the current step, counted from 0 in the same way as `init_step`.
+    pub(crate) fn current_step(&self) -> usize {
+        self.step
+    }
 }
 
 impl Scheduler for LinearLR {
     fn step(&mut self, _loss: f64) {
-        self.step += 1;
-        if self.step >= self.total_iters {
-            self.lr = self.end_factor * self.base_lr;
-        } else {
-            self.lr = self.base_lr * (self.step as f64).mul_add(self.grad, self.start_factor);
-        }
+        self.step = self.step.saturating_add(1);
+        self.lr = self.lr_at(self.step);
     }
 
     fn get_lr(&self, _loss: f64) -> f64 {
         self.lr
     }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        Some(self.total_iters.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        Some(progress_at(self.step, self.total_iters))
+    }
+}
+
+impl ClosedForm for LinearLR {
+    fn lr_at(&self, step: usize) -> f64 {
+        self.value_at(step)
+    }
+
+    fn horizon(&self) -> Option<usize> {
+        Some(self.total_iters)
+    }
+}
+
+impl PiecewiseMonotone for LinearLR {
+    fn monotone_pieces(&self) -> Vec<(usize, usize)> {
+        vec![(0, self.total_iters)]
+    }
+}
+
+impl Bounded for LinearLR {
+    /// Returns the learning rates at step 0 and `total_iters`, between which the learning rate changes linearly.
+    fn bounds(&self) -> (f64, f64) {
+        let (start, end) = (self.value_at(0), self.value_at(self.total_iters));
+        (start.min(end), start.max(end))
+    }
+}
+
+impl Perturbable for LinearLR {
+    fn perturb(&mut self, factor: f64) {
+        self.base_lr *= factor;
+        self.lr *= factor;
+    }
 }
 
 #[cfg(test)]
@@ -223,4 +285,23 @@ mod tests {
             scheduler.step(0.0);
         }
     }
+
+    #[test]
+    fn random_access() {
+        let mut scheduler = LinearLR::new(1.0, 0.5, 2.0, 3, 0);
+        let reference = scheduler.clone();
+        for i in 0 .. 6 {
+            assert_eq!(reference.lr_at(i), scheduler.get_lr(0.0), "Step {}", i);
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn const_table() {
+        const SCHEDULER: LinearLR = LinearLR::new(1.0, 0.1, 0.9, 3, 0);
+        static LRS: [f64; 5] = SCHEDULER.table::<5>();
+        for (i, lr) in LRS.iter().enumerate() {
+            assert_eq!(*lr, SCHEDULER.lr_at(i), "Step {}", i);
+        }
+    }
 }
\ No newline at end of file
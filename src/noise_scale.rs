@@ -0,0 +1,157 @@
+use crate::Scheduler;
+
+/// Adapts the learning rate to estimates of the gradient noise scale.
+/// 
+/// Following "An Empirical Model of Large-Batch Training" (McCandlish et al., 2018), the learning rate of the wrapped scheduler
+/// is taken as the maximum learning rate `eps_max`, and the learning rate of a batch size `B` is `eps_max / (1 + B_noise / B)`,
+/// where `B_noise` is the noise scale. The noise scale is also the suggested batch size, around which training trades
+/// time for compute most efficiently.
+/// 
+/// Noise scale estimates are passed to `step_with_signal` whenever they are available, e.g. every few hundred steps,
+/// and smoothed with an exponential moving average, since single estimates are noisy.
+/// Until the first estimate, the learning rate of the wrapped scheduler is returned as is.
+/// Estimates that are not finite or not positive are ignored.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::constant::ConstantLR;
+/// # use lr_schedulers::noise_scale::NoiseScaleAdaptive;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = NoiseScaleAdaptive::new(ConstantLR::new(1.0, 1.0, 0, 0), 32, 0.5);
+/// assert_eq!(scheduler.get_lr(0.01), 1.0);
+/// scheduler.step_with_signal(0.01, Some(96.0));
+/// assert_eq!(scheduler.get_lr(0.01), 0.25);
+/// assert_eq!(scheduler.suggested_batch_size(), Some(96));
+/// // The next estimate is averaged with the previous one.
+/// scheduler.step_with_signal(0.01, Some(32.0));
+/// assert_eq!(scheduler.noise_scale(), Some(64.0));
+/// // Adopting the suggested batch size raises the learning rate.
+/// scheduler.set_batch_size(64);
+/// assert_eq!(scheduler.get_lr(0.01), 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NoiseScaleAdaptive<S> {
+    scheduler: S,
+    batch_size: usize,
+    smoothing: f64,
+    noise_scale: Option<f64>,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> NoiseScaleAdaptive<S> {
+    /// Constructs a NoiseScaleAdaptive instance for training with `batch_size`.
+    /// 
+    /// `smoothing` is the weight of the previous average in the moving average of estimates, clamped to `[0, 1)`.
+    /// Use 0 to take every estimate as is.
+    pub fn new(scheduler: S, batch_size: usize, smoothing: f64) -> Self {
+        let smoothing = if smoothing.is_nan() { 0.0 } else { smoothing.clamp(0.0, 1.0 - f64::EPSILON) };
+        NoiseScaleAdaptive { scheduler, batch_size: batch_size.max(1), smoothing, noise_scale: None, last_lr: None }
+    }
+
+    /// Records a noise scale estimate, if any, and proceeds the step.
+    /// 
+    /// The estimate applies from the next step.
+    pub fn step_with_signal(&mut self, loss: f64, noise_scale: Option<f64>) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.scheduler.step(loss);
+        if let Some(estimate) = noise_scale.filter(|b| b.is_finite() && *b > 0.0) {
+            self.noise_scale = Some(match self.noise_scale {
+                Some(average) => self.smoothing * average + (1.0 - self.smoothing) * estimate,
+                None => estimate,
+            });
+        }
+    }
+
+    /// Changes the batch size, e.g. after adopting the suggested batch size.
+    pub fn set_batch_size(&mut self, batch_size: usize) {
+        self.batch_size = batch_size.max(1);
+    }
+
+    /// Returns the batch size.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the smoothed noise scale, or None before the first estimate.
+    pub fn noise_scale(&self) -> Option<f64> {
+        self.noise_scale
+    }
+
+    /// Returns the factor `1 / (1 + B_noise / B)` applied to the learning rate of the wrapped scheduler.
+    pub fn lr_factor(&self) -> f64 {
+        match self.noise_scale {
+            Some(noise_scale) => 1.0 / (1.0 + noise_scale / self.batch_size as f64),
+            None => 1.0,
+        }
+    }
+
+    /// Returns the noise scale rounded to a batch size of at least 1, or None before the first estimate.
+    pub fn suggested_batch_size(&self) -> Option<usize> {
+        // `as` saturates for huge noise scales.
+        self.noise_scale.map(|noise_scale| (noise_scale.round() as usize).max(1))
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for NoiseScaleAdaptive<S> {
+    /// Proceeds the step without a noise scale estimate.
+    fn step(&mut self, loss: f64) {
+        self.step_with_signal(loss, None);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss) * self.lr_factor()
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant::ConstantLR;
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn follow_inner_schedule() {
+        let mut scheduler = NoiseScaleAdaptive::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 100, 0.0);
+        let signals = [None, Some(100.0), None, Some(f64::NAN), Some(300.0)];
+        let expected_lrs = [1.0, 0.75, 0.25, 0.125, 0.0];
+        for (i, (signal, exp_lr)) in signals.iter().zip(expected_lrs).enumerate() {
+            assert_eq!(scheduler.get_lr(0.0), exp_lr, "Step {}", i);
+            scheduler.step_with_signal(0.0, *signal);
+            assert_eq!(scheduler.get_last_lr(), Some(exp_lr), "Step {}", i);
+        }
+        assert_eq!(scheduler.noise_scale(), Some(300.0));
+        assert_eq!(scheduler.suggested_batch_size(), Some(300));
+    }
+
+    #[test]
+    fn smooth_estimates() {
+        let mut scheduler = NoiseScaleAdaptive::new(ConstantLR::new(1.0, 1.0, 0, 0), 1, 0.75);
+        let signals = [8.0, 16.0, 0.0, -1.0, 4.0];
+        let expected = [8.0, 10.0, 10.0, 10.0, 8.5];
+        for (i, (signal, exp)) in signals.iter().zip(expected).enumerate() {
+            scheduler.step_with_signal(0.0, Some(*signal));
+            assert_eq!(scheduler.noise_scale(), Some(exp), "Step {}", i);
+        }
+        assert_eq!(scheduler.suggested_batch_size(), Some(9));
+        scheduler.set_batch_size(0);
+        assert_eq!(scheduler.batch_size(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,92 @@
+use crate::{Cyclical, Scheduler};
+
+/// Invokes a closure at the start of every new cycle of a cyclical scheduler.
+/// 
+/// The closure receives the index of the new cycle and the learning rate of its first step, i.e. the new peak for warm restarts.
+/// It is called at the end of the `step` entering the cycle, e.g. to reset an EMA of weights or save a snapshot.
+/// The cycle the scheduler starts in does not trigger the closure.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+/// # use lr_schedulers::on_cycle::OnCycle;
+/// # use lr_schedulers::Scheduler;
+/// let mut restarts = Vec::new();
+/// // Cycles of 2, 3 and 5 steps.
+/// let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 1, 2, 0);
+/// let mut scheduler = OnCycle::new(scheduler, |cycle, lr| restarts.push((cycle, lr)));
+/// for _ in 0 .. 9 {
+///     // Note: loss value is not used in this scheduler.
+///     scheduler.step(0.01);
+/// }
+/// drop(scheduler);
+/// assert_eq!(restarts, [(1, 1.0), (2, 1.0)]);
+/// ```
+#[derive(Clone)]
+pub struct OnCycle<S, F> {
+    scheduler: S,
+    callback: F,
+}
+
+impl<S: Cyclical, F: FnMut(usize, f64)> OnCycle<S, F> {
+    /// Constructs an OnCycle instance calling `callback` at every new cycle.
+    pub fn new(scheduler: S, callback: F) -> Self {
+        OnCycle { scheduler, callback }
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+
+    /// Returns the wrapped scheduler and the callback.
+    pub fn into_parts(self) -> (S, F) {
+        (self.scheduler, self.callback)
+    }
+}
+
+impl<S: Cyclical, F: FnMut(usize, f64)> Scheduler for OnCycle<S, F> {
+    fn step(&mut self, loss: f64) {
+        let cycle = self.scheduler.current_cycle();
+        self.scheduler.step(loss);
+        let new_cycle = self.scheduler.current_cycle();
+        if new_cycle != cycle {
+            (self.callback)(new_cycle, self.scheduler.get_lr(loss));
+        }
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.scheduler.get_last_lr()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosine_annealing::CosineAnnealingLR;
+    use super::*;
+
+    #[test]
+    fn call_at_each_period() {
+        let mut calls = Vec::new();
+        let mut scheduler = OnCycle::new(CosineAnnealingLR::new(1.0, 0.0, 2, 3), |cycle, lr| calls.push((cycle, lr)));
+        for _ in 0 .. 9 {
+            scheduler.step(0.0);
+        }
+        let (inner, _) = scheduler.into_parts();
+        assert_eq!(inner.current_cycle(), 3);
+        assert_eq!(calls, [(1, 1.0), (2, 1.0), (3, 1.0)]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,119 @@
+use crate::Scheduler;
+
+/// Allows a scheduler to be paused, e.g. during evaluation-only loops or learning rate freeze ablations.
+/// 
+/// While paused, `step` is accepted but does not advance the wrapped scheduler, so the learning rate stays the same.
+/// After `resume`, the schedule continues from where it was paused.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::pause::Pausable;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = Pausable::new(ExponentialLR::new(1.0, 0.5, 0));
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// scheduler.pause();
+/// scheduler.step(0.01);
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 0.5);
+/// scheduler.resume();
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 0.25);
+/// assert_eq!(scheduler.paused_steps(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pausable<S> {
+    scheduler: S,
+    paused: bool,
+    paused_steps: usize,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> Pausable<S> {
+    /// Constructs a Pausable instance, which is not paused.
+    pub fn new(scheduler: S) -> Self {
+        Pausable { scheduler, paused: false, paused_steps: 0, last_lr: None }
+    }
+
+    /// Stops advancing the wrapped scheduler.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Advances the wrapped scheduler again at every `step`.
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Returns true if the scheduler is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns the total number of steps taken while paused.
+    pub fn paused_steps(&self) -> usize {
+        self.paused_steps
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for Pausable<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.scheduler.get_lr(loss));
+        if self.paused {
+            self.paused_steps += 1;
+        } else {
+            self.scheduler.step(loss);
+        }
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the remaining steps of the wrapped scheduler, which do not count future pauses.
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn hold_while_paused() {
+        let mut scheduler = Pausable::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0));
+        let paused = [false, true, true, false, false, true];
+        let expected_lrs = [0.75, 0.75, 0.75, 0.5, 0.25, 0.25];
+        for (i, (pause, exp_lr)) in paused.iter().zip(expected_lrs).enumerate() {
+            if *pause {
+                scheduler.pause();
+            } else {
+                scheduler.resume();
+            }
+            let prev_lr = scheduler.get_lr(0.0);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+            assert_eq!(scheduler.get_lr(0.0), exp_lr, "Step {}", i);
+            assert_eq!(scheduler.get_last_lr(), Some(prev_lr), "Step {}", i);
+        }
+        assert_eq!(scheduler.paused_steps(), 3);
+        assert!(scheduler.is_paused());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,178 @@
+use crate::Scheduler;
+
+/// Schedulers whose learning rate can be scaled in the middle of training, as in Population Based Training.
+/// 
+/// Perturbing keeps the step and the shape of the schedule: every learning rate from the current step on,
+/// as well as `lr_at` of [`ClosedForm`](crate::ClosedForm) schedulers, is multiplied by the factor.
+pub trait Perturbable: Scheduler {
+    /// Multiplies the learning rate of the current and later steps by `factor`.
+    fn perturb(&mut self, factor: f64);
+}
+
+impl<S: Perturbable + ?Sized> Perturbable for Box<S> {
+    fn perturb(&mut self, factor: f64) {
+        (**self).perturb(factor)
+    }
+}
+
+/// A perturbation applied by [`Perturbed`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Perturbation {
+    /// Number of steps processed before the perturbation.
+    pub step: usize,
+    /// Factor the learning rate was multiplied by.
+    pub factor: f64,
+}
+
+/// Records the perturbations applied to a scheduler.
+/// 
+/// The history can be stored with the checkpoint of a worker, and a scheduler is restored by `replay`,
+/// e.g. when another worker exploits the checkpoint.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::pbt::Perturbed;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = Perturbed::new(ExponentialLR::new(1.0, 0.5, 0));
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// scheduler.perturb(1.2);
+/// assert_eq!(scheduler.get_lr(0.01), 0.6);
+/// 
+/// scheduler.step(0.01);
+/// 
+/// // Restore the scheduler of the checkpoint at step 2.
+/// let mut restored = Perturbed::new(ExponentialLR::new(1.0, 0.5, 0));
+/// for _ in 0 .. 2 {
+///     restored.replay(scheduler.history());
+///     restored.step(0.01);
+/// }
+/// assert_eq!(restored.get_lr(0.01), scheduler.get_lr(0.01));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Perturbed<S> {
+    scheduler: S,
+    step: usize,
+    history: Vec<Perturbation>,
+}
+
+impl<S: Perturbable> Perturbed<S> {
+    /// Constructs a Perturbed instance.
+    pub fn new(scheduler: S) -> Self {
+        Perturbed { scheduler, step: 0, history: Vec::new() }
+    }
+
+    /// Multiplies the learning rate of the current and later steps by `factor`, and records the perturbation.
+    pub fn perturb(&mut self, factor: f64) {
+        self.scheduler.perturb(factor);
+        self.history.push(Perturbation { step: self.step, factor });
+    }
+
+    /// Applies the perturbations of `history` recorded at the current step which have not been applied yet.
+    /// 
+    /// Call this before every `step` to follow the history of another scheduler constructed with the same parameters.
+    pub fn replay(&mut self, history: &[Perturbation]) {
+        let (applied, step) = (self.history.len(), self.step);
+        for perturbation in history.iter().skip(applied).take_while(|p| p.step == step) {
+            self.perturb(perturbation.factor);
+        }
+    }
+
+    /// Returns the perturbations applied so far, in order.
+    pub fn history(&self) -> &[Perturbation] {
+        &self.history
+    }
+
+    /// Returns the product of the factors applied so far.
+    pub fn cumulative_factor(&self) -> f64 {
+        self.history.iter().map(|p| p.factor).product()
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Perturbable> Scheduler for Perturbed<S> {
+    fn step(&mut self, loss: f64) {
+        self.scheduler.step(loss);
+        self.step += 1;
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.scheduler.get_last_lr()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+impl<S: Perturbable> Perturbable for Perturbed<S> {
+    fn perturb(&mut self, factor: f64) {
+        Perturbed::perturb(self, factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::SchedulerConfig;
+    use crate::semantics::Semantics;
+    use crate::ClosedForm;
+    use super::*;
+
+    #[test]
+    fn keep_shape() {
+        let configs = [
+            SchedulerConfig::Constant { base_lr: 1.0, factor: 0.5, total_iters: 3 },
+            SchedulerConfig::Linear { base_lr: 1.0, start_factor: 0.1, end_factor: 1.0, total_iters: 5 },
+            SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.9, decay_steps: 1, staircase: false },
+            SchedulerConfig::CosineAnnealing { eta_0: 1.0, eta_1: 0.1, t_max: 4, decay_once: false, min_fraction: None },
+            SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1.0, eta_1: 0.1, t_0: 2, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native },
+        ];
+        for config in configs {
+            let reference = config.build_any(0);
+            let mut scheduler = config.build_any(0);
+            for _ in 0 .. 2 {
+                scheduler.step(0.0);
+            }
+            scheduler.perturb(0.5);
+            for i in 2 .. 12 {
+                let lr = scheduler.get_lr(0.0);
+                let expected = 0.5 * reference.lr_at(i);
+                assert!((lr - expected).abs() < 1e-12, "{:?}: Step {}: left: {}, right: {}", config, i, lr, expected);
+                assert!((scheduler.lr_at(i) - expected).abs() < 1e-12, "{:?}: Step {}", config, i);
+                scheduler.step(0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn record_history() {
+        let mut scheduler = Perturbed::new(SchedulerConfig::Exponential { base_lr: 1.0, gamma: 0.9, decay_steps: 1, staircase: false }.build_any(0));
+        scheduler.perturb(0.8);
+        scheduler.step(0.0);
+        scheduler.perturb(1.25);
+        scheduler.perturb(2.0);
+        assert_eq!(
+            scheduler.history(),
+            [
+                Perturbation { step: 0, factor: 0.8 },
+                Perturbation { step: 1, factor: 1.25 },
+                Perturbation { step: 1, factor: 2.0 },
+            ]
+        );
+        assert_eq!(scheduler.cumulative_factor(), 2.0);
+    }
+}
\ No newline at end of file
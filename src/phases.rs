@@ -0,0 +1,309 @@
+use crate::{progress_at, Bounded, ClosedForm, PiecewiseMonotone, Scheduler};
+
+const PI: f64 = std::f64::consts::PI;
+
+/// Shape of a warmup phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ramp {
+    /// Increases linearly.
+    Linear,
+    /// Increases along a half cosine, which starts and ends slowly.
+    Cosine,
+}
+
+/// Shape and final learning rate of a decay phase.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Decay {
+    /// Decreases linearly to `to`.
+    Linear { to: f64 },
+    /// Decreases along a half cosine to `to`.
+    Cosine { to: f64 },
+    /// Decreases geometrically to `to`. The learning rates at both ends must be positive.
+    Exponential { to: f64 },
+}
+
+/// Kind of a phase of [`Schedule`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PhaseKind {
+    /// Increases the learning rate from 0 to the peak learning rate.
+    Warmup,
+    /// Holds the learning rate.
+    Hold,
+    /// Decreases the learning rate to a given value.
+    Decay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Curve {
+    Linear,
+    Cosine,
+    Exponential,
+}
+
+/// A phase of [`Schedule`], as listed in its phase table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    /// Name of the phase, which is the name of its kind unless given with `named`.
+    pub name: String,
+    /// Kind of the phase.
+    pub kind: PhaseKind,
+    /// First step of the phase.
+    pub start: usize,
+    /// Number of steps of the phase.
+    pub steps: usize,
+    /// Learning rate at the first step of the phase.
+    pub start_lr: f64,
+    /// Learning rate reached at the end of the phase, i.e. at the first step of the next phase.
+    pub end_lr: f64,
+    curve: Curve,
+}
+
+impl Phase {
+    /// Returns the step following the last step of the phase.
+    pub fn end(&self) -> usize {
+        self.start.saturating_add(self.steps)
+    }
+
+    fn lr_at(&self, step: usize) -> f64 {
+        let progress = (step - self.start) as f64 / self.steps as f64;
+        match self.curve {
+            Curve::Linear => (self.end_lr - self.start_lr).mul_add(progress, self.start_lr),
+            Curve::Cosine => (self.start_lr - self.end_lr).mul_add(0.5 * (1.0 + (PI * progress).cos()), self.end_lr),
+            Curve::Exponential => self.start_lr * (self.end_lr / self.start_lr).powf(progress),
+        }
+    }
+}
+
+/// Builder of a [`Schedule`] from a sequence of phases. See [`Schedule::phases`].
+#[derive(Debug, Clone)]
+pub struct PhaseBuilder {
+    peak_lr: f64,
+    phases: Vec<Phase>,
+}
+
+impl PhaseBuilder {
+    /// Appends a warmup from 0 to the peak learning rate over `steps` steps.
+    pub fn warmup(self, steps: usize, ramp: Ramp) -> Self {
+        let curve = match ramp {
+            Ramp::Linear => Curve::Linear,
+            Ramp::Cosine => Curve::Cosine,
+        };
+        let peak_lr = self.peak_lr;
+        self.push(PhaseKind::Warmup, steps, 0.0, peak_lr, curve)
+    }
+
+    /// Appends a phase holding the current learning rate for `steps` steps.
+    pub fn hold(self, steps: usize) -> Self {
+        let lr = self.end_lr();
+        self.push(PhaseKind::Hold, steps, lr, lr, Curve::Linear)
+    }
+
+    /// Appends a decay from the current learning rate over `steps` steps.
+    pub fn decay(self, steps: usize, decay: Decay) -> Self {
+        let (to, curve) = match decay {
+            Decay::Linear { to } => (to, Curve::Linear),
+            Decay::Cosine { to } => (to, Curve::Cosine),
+            Decay::Exponential { to } => (to, Curve::Exponential),
+        };
+        let lr = self.end_lr();
+        self.push(PhaseKind::Decay, steps, lr, to, curve)
+    }
+
+    /// Names the last phase, e.g. to tell apart two decays in the phase table.
+    pub fn named(mut self, name: &str) -> Self {
+        if let Some(phase) = self.phases.last_mut() {
+            phase.name = name.to_string();
+        }
+        self
+    }
+
+    /// Builds the schedule starting from `init_step`. Use `init_step=0` to train a model from the beginning.
+    pub fn build(self, init_step: usize) -> Schedule {
+        Schedule { peak_lr: self.peak_lr, phases: self.phases, step: init_step }
+    }
+
+    fn end_lr(&self) -> f64 {
+        self.phases.last().map_or(self.peak_lr, |phase| phase.end_lr)
+    }
+
+    fn push(mut self, kind: PhaseKind, steps: usize, start_lr: f64, end_lr: f64, curve: Curve) -> Self {
+        let start = self.phases.last().map_or(0, Phase::end);
+        let name = match kind {
+            PhaseKind::Warmup => "warmup",
+            PhaseKind::Hold => "hold",
+            PhaseKind::Decay => "decay",
+        };
+        self.phases.push(Phase { name: name.to_string(), kind, start, steps, start_lr, end_lr, curve });
+        self
+    }
+}
+
+/// Schedule composed of named phases played one after another.
+/// 
+/// A warmup rises from 0 to the peak learning rate, a hold keeps the current learning rate, and a decay goes from
+/// the current learning rate to a given one. The learning rate before any phase is the peak learning rate,
+/// and the learning rate at the end of the last phase is held afterwards.
+/// The phase table tells which phase a step belongs to, e.g. to annotate logs and plots.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::phases::{Decay, Ramp, Schedule};
+/// # use lr_schedulers::{ClosedForm, Scheduler};
+/// let scheduler = Schedule::phases(1e-3)
+///     .warmup(100, Ramp::Linear)
+///     .hold(800)
+///     .decay(100, Decay::Cosine { to: 1e-5 })
+///     .named("anneal")
+///     .build(0);
+/// assert_eq!(scheduler.lr_at(0), 0.0);
+/// assert_eq!(scheduler.lr_at(500), 1e-3);
+/// assert_eq!(scheduler.lr_at(1000), 1e-5);
+/// assert_eq!(scheduler.horizon(), Some(1000));
+/// let names: Vec<_> = scheduler.phase_table().iter().map(|phase| (phase.name.as_str(), phase.start)).collect();
+/// assert_eq!(names, [("warmup", 0), ("hold", 100), ("anneal", 900)]);
+/// assert_eq!(scheduler.current_phase().unwrap().name, "warmup");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    peak_lr: f64,
+    phases: Vec<Phase>,
+    step: usize,
+}
+
+impl Schedule {
+    /// Starts building a schedule whose warmups reach `peak_lr`.
+    pub fn phases(peak_lr: f64) -> PhaseBuilder {
+        PhaseBuilder { peak_lr, phases: Vec::new() }
+    }
+
+    /// Returns the phases in order.
+    pub fn phase_table(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    /// Returns the phase containing `step`, or None after the last phase.
+    pub fn phase_at(&self, step: usize) -> Option<&Phase> {
+        let index = self.phases.partition_point(|phase| phase.end() <= step);
+        self.phases.get(index)
+    }
+
+    /// Returns the phase of the current step, or None after the last phase.
+    pub fn current_phase(&self) -> Option<&Phase> {
+        self.phase_at(self.step)
+    }
+
+    fn total_steps(&self) -> usize {
+        self.phases.last().map_or(0, Phase::end)
+    }
+
+    /// Returns the current step, counted from 0 in the same way as `init_step`.
+    pub(crate) fn current_step(&self) -> usize {
+        self.step
+    }
+}
+
+impl Scheduler for Schedule {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        Some(self.total_steps().saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        Some(progress_at(self.step, self.total_steps()))
+    }
+}
+
+impl ClosedForm for Schedule {
+    fn lr_at(&self, step: usize) -> f64 {
+        match self.phase_at(step) {
+            Some(phase) => phase.lr_at(step),
+            None => self.phases.last().map_or(self.peak_lr, |phase| phase.end_lr),
+        }
+    }
+
+    fn horizon(&self) -> Option<usize> {
+        Some(self.total_steps())
+    }
+}
+
+/// Each phase is a piece, and so is the change from its last step to the next phase, where a warmup restarts from 0.
+impl PiecewiseMonotone for Schedule {
+    fn monotone_pieces(&self) -> Vec<(usize, usize)> {
+        let pieces: Vec<_> = self.phases
+            .iter()
+            .filter(|phase| phase.steps > 0)
+            .flat_map(|phase| [(phase.start, phase.end() - 1), (phase.end() - 1, phase.end())])
+            .collect();
+        if pieces.is_empty() { vec![(0, 0)] } else { pieces }
+    }
+}
+
+/// Returns the smallest and the largest learning rates at the ends of the phases, between which every curve is monotone.
+impl Bounded for Schedule {
+    fn bounds(&self) -> (f64, f64) {
+        let initial = self.lr_at(0);
+        self.phases
+            .iter()
+            .filter(|phase| phase.steps > 0)
+            .flat_map(|phase| [phase.start_lr, phase.end_lr])
+            .chain([self.lr_at(self.total_steps())])
+            .fold((initial, initial), |(min, max), lr| (min.min(lr), max.max(lr)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn play_phases() {
+        let mut scheduler = Schedule::phases(1.0)
+            .warmup(2, Ramp::Linear)
+            .hold(1)
+            .decay(2, Decay::Linear { to: 0.5 })
+            .decay(2, Decay::Exponential { to: 0.125 })
+            .named("tail")
+            .build(0);
+        let expected_lrs = [0.0, 0.5, 1.0, 1.0, 0.75, 0.5, 0.25, 0.125, 0.125];
+        let expected_phases = ["warmup", "warmup", "hold", "decay", "decay", "tail", "tail"];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            assert_eq!(scheduler.get_lr(0.0), *exp_lr, "Step {}", i);
+            assert_eq!(scheduler.current_phase().map(|phase| phase.name.as_str()), expected_phases.get(i).copied(), "Step {}", i);
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.bounds(), (0.0, 1.0));
+        assert_eq!(scheduler.remaining_steps(), Some(0));
+    }
+
+    #[test]
+    fn cosine_curves_and_empty_phases() {
+        let scheduler = Schedule::phases(2.0)
+            .hold(2)
+            .warmup(0, Ramp::Cosine)
+            .decay(4, Decay::Cosine { to: 0.0 })
+            .warmup(2, Ramp::Cosine)
+            .build(0);
+        let expected_lrs = [2.0, 2.0, 2.0, 1.0 + 0.5f64.sqrt(), 1.0, 1.0 - 0.5f64.sqrt(), 0.0, 1.0, 2.0];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            let lr = scheduler.lr_at(i);
+            assert!((lr - exp_lr).abs() < 1e-12, "Step {}: left: {}, right: {}", i, lr, exp_lr);
+        }
+        // The empty warmup never contains a step.
+        assert_eq!(scheduler.phase_at(2).unwrap().kind, PhaseKind::Decay);
+        assert_eq!(scheduler.bounds(), (0.0, 2.0));
+        let empty = Schedule::phases(1.0).build(3);
+        assert_eq!((empty.get_lr(0.0), empty.horizon(), empty.current_phase()), (1.0, Some(0), None));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,187 @@
+use crate::{Bounded, Scheduler};
+
+/// Controls the learning rate so that the smoothed loss decreases at a given rate per step.
+/// 
+/// The loss passed to `step` is smoothed with an exponential moving average, updated as
+/// `average = smoothing * average + (1 - smoothing) * loss` and initialized with the first loss,
+/// and the decrease rate is the decrease of the average in a step.
+/// From the second loss on, the error `e = setpoint - rate` drives a PID controller and the learning rate becomes
+/// `initial_lr + kp * e + ki * sum(e) - kd * (rate - previous rate)`, clamped to the output bounds.
+/// The derivative is taken on the rate instead of the error, so that changing the setpoint does not cause a kick.
+/// 
+/// The error is not accumulated while the output is saturated and the error would push it further, i.e. anti-windup by
+/// conditional integration, so the controller responds as soon as the loss allows it. Losses that are not finite are ignored.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::pid::PidLR;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = PidLR::new(0.25, 0.5, 0.5, 0.0, 0.0, 0.0).with_output_bounds(0.0, 1.0);
+/// scheduler.step(10.0);
+/// assert_eq!(scheduler.get_lr(10.0), 0.25);
+/// // The loss decreases slower than the setpoint, so the learning rate is raised.
+/// scheduler.step(10.0);
+/// assert_eq!(scheduler.rate(), Some(0.0));
+/// assert_eq!(scheduler.get_lr(10.0), 0.5);
+/// // The loss decreases faster than the setpoint, so the learning rate is lowered.
+/// scheduler.step(9.0);
+/// assert_eq!(scheduler.get_lr(9.0), 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PidLR {
+    initial_lr: f64,
+    setpoint: f64,
+    kp: f64,
+    ki: f64,
+    kd: f64,
+    smoothing: f64,
+    min_lr: f64,
+    max_lr: f64,
+    average: Option<f64>,
+    rate: Option<f64>,
+    integral: f64,
+    lr: f64,
+    last_lr: Option<f64>,
+}
+
+impl PidLR {
+    /// Constructs a PidLR instance starting from `initial_lr` and targeting a decrease of `setpoint` per step.
+    /// 
+    /// `kp`, `ki` and `kd` are the proportional, integral and derivative gains.
+    /// The parameter `smoothing` must be in `[0, 1)`. Values outside the range are clamped.
+    /// The output is bounded to `[0, ∞)` by default. See `with_output_bounds`.
+    pub fn new(initial_lr: f64, setpoint: f64, kp: f64, ki: f64, kd: f64, smoothing: f64) -> Self {
+        PidLR {
+            initial_lr,
+            setpoint,
+            kp,
+            ki,
+            kd,
+            smoothing: smoothing.clamp(0.0, 1.0 - f64::EPSILON),
+            min_lr: 0.0,
+            max_lr: f64::INFINITY,
+            average: None,
+            rate: None,
+            integral: 0.0,
+            lr: initial_lr,
+            last_lr: None,
+        }
+    }
+
+    /// Clamps the learning rate to `[min_lr, max_lr]`. The bounds are swapped if `min_lr > max_lr`.
+    pub fn with_output_bounds(self, min_lr: f64, max_lr: f64) -> Self {
+        let (min_lr, max_lr) = if min_lr > max_lr { (max_lr, min_lr) } else { (min_lr, max_lr) };
+        PidLR { min_lr, max_lr, lr: self.lr.clamp(min_lr, max_lr), ..self }
+    }
+
+    /// Changes the target decrease rate from the next step on.
+    pub fn set_setpoint(&mut self, setpoint: f64) {
+        self.setpoint = setpoint;
+    }
+
+    /// Returns the target decrease rate.
+    pub fn setpoint(&self) -> f64 {
+        self.setpoint
+    }
+
+    /// Returns the moving average of the loss, or None before the first finite loss.
+    pub fn smoothed_loss(&self) -> Option<f64> {
+        self.average
+    }
+
+    /// Returns the decrease of the moving average in the last step, or None before the second finite loss.
+    pub fn rate(&self) -> Option<f64> {
+        self.rate
+    }
+
+    /// Returns the accumulated error of the integral term.
+    pub fn integral(&self) -> f64 {
+        self.integral
+    }
+
+    fn control(&self, error: f64, integral: f64, derivative: f64) -> f64 {
+        self.kd.mul_add(-derivative, self.ki.mul_add(integral, self.kp.mul_add(error, self.initial_lr)))
+    }
+}
+
+impl Scheduler for PidLR {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.lr);
+        if !loss.is_finite() {
+            return;
+        }
+        let Some(previous) = self.average else {
+            self.average = Some(loss);
+            return;
+        };
+        let average = self.smoothing.mul_add(previous, (1.0 - self.smoothing) * loss);
+        let rate = previous - average;
+        let derivative = self.rate.map_or(0.0, |previous_rate| rate - previous_rate);
+        let error = self.setpoint - rate;
+        let output = self.control(error, self.integral + error, derivative);
+        let winding_up = (output > self.max_lr && error * self.ki > 0.0) || (output < self.min_lr && error * self.ki < 0.0);
+        let output = if winding_up {
+            self.control(error, self.integral, derivative)
+        } else {
+            self.integral += error;
+            output
+        };
+        self.lr = output.clamp(self.min_lr, self.max_lr);
+        self.average = Some(average);
+        self.rate = Some(rate);
+    }
+
+    /// Returns the learning rate computed at the last step.
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+}
+
+impl Bounded for PidLR {
+    /// Returns the output bounds, widened to include the initial learning rate.
+    fn bounds(&self) -> (f64, f64) {
+        (self.min_lr.min(self.initial_lr), self.max_lr.max(self.initial_lr))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn track_setpoint() {
+        let mut scheduler = PidLR::new(0.5, 1.0, 0.0, 0.25, 0.0, 0.0).with_output_bounds(0.0, 1.0);
+        let losses = [10.0, 10.0, 10.0, 10.0, 8.0, 7.0, f64::NAN, 5.0];
+        // The integral stops at 2 while the output is saturated at 1, and unwinds as soon as the loss decreases fast enough.
+        let expected_lrs = [0.5, 0.75, 1.0, 1.0, 0.75, 0.75, 0.75, 0.5];
+        let expected_integrals = [0.0, 1.0, 2.0, 2.0, 1.0, 1.0, 1.0, 0.0];
+        for (i, loss) in losses.iter().enumerate() {
+            scheduler.step(*loss);
+            assert_eq!(scheduler.get_lr(0.0), expected_lrs[i], "Step {}", i);
+            assert_eq!(scheduler.integral(), expected_integrals[i], "Step {}", i);
+        }
+        assert_eq!(scheduler.get_last_lr(), Some(0.75));
+        assert_eq!(scheduler.bounds(), (0.0, 1.0));
+    }
+
+    #[test]
+    fn derivative_on_rate() {
+        let mut scheduler = PidLR::new(1.0, 0.0, 0.0, 0.0, 0.5, 0.5);
+        for loss in [4.0, 2.0, 2.0] {
+            scheduler.step(loss);
+        }
+        // The average goes 4, 3, 2.5, so the rate drops from 1 to 0.5.
+        assert_eq!(scheduler.smoothed_loss(), Some(2.5));
+        assert_eq!(scheduler.rate(), Some(0.5));
+        assert_eq!(scheduler.get_lr(0.0), 1.25);
+        // A new setpoint does not kick the derivative term.
+        scheduler.set_setpoint(10.0);
+        scheduler.step(2.5);
+        assert_eq!(scheduler.get_lr(0.0), 1.25);
+    }
+}
@@ -0,0 +1,256 @@
+use crate::semantics::Semantics;
+
+/// Direction in which the monitored metric improves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Lower is better, e.g. loss.
+    Min,
+    /// Higher is better, e.g. accuracy.
+    Max,
+}
+
+/// Interpretation of the improvement threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdMode {
+    /// A metric improves when it beats `best - |best| * threshold` in `Mode::Min` or `best + |best| * threshold` in `Mode::Max`.
+    /// 
+    /// With `Semantics::PyTorchParity`, it beats `best * (1 - threshold)` or `best * (1 + threshold)` instead.
+    Rel,
+    /// A metric improves when it beats `best - threshold` in `Mode::Min` or `best + threshold` in `Mode::Max`.
+    Abs,
+}
+
+/// Detects plateaus of a metric.
+/// 
+/// A metric that does not improve on the best value seen so far is counted as a bad epoch.
+/// A plateau is detected when the number of consecutive bad epochs exceeds `patience`, as in PyTorch's ReduceLROnPlateau.
+/// NaN metrics never improve.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::plateau::{Mode, Plateau, ThresholdMode};
+/// let mut plateau = Plateau::new(Mode::Min, 1, 0.0, ThresholdMode::Abs);
+/// assert!(!plateau.update(1.0));
+/// assert!(!plateau.update(1.0));
+/// assert!(plateau.update(1.0));
+/// assert_eq!(plateau.best(), 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Plateau {
+    mode: Mode,
+    patience: usize,
+    threshold: f64,
+    threshold_mode: ThresholdMode,
+    best: f64,
+    num_bad_epochs: usize,
+    warmup_epochs: usize,
+    epochs: usize,
+    semantics: Semantics,
+}
+
+impl Plateau {
+    /// Constructs a Plateau instance.
+    pub fn new(mode: Mode, patience: usize, threshold: f64, threshold_mode: ThresholdMode) -> Self {
+        let best = match mode {
+            Mode::Min => f64::INFINITY,
+            Mode::Max => f64::NEG_INFINITY,
+        };
+        Plateau { mode, patience, threshold, threshold_mode, best, num_bad_epochs: 0, warmup_epochs: 0, epochs: 0, semantics: Semantics::Native }
+    }
+
+    /// Ignores the first `warmup_epochs` metrics entirely.
+    /// 
+    /// Metrics during the warmup neither update the best value nor count as bad epochs,
+    /// so noisy early epochs cannot trigger a premature plateau.
+    /// 
+    /// ```
+    /// # use lr_schedulers::plateau::{Mode, Plateau, ThresholdMode};
+    /// let mut plateau = Plateau::new(Mode::Min, 0, 0.0, ThresholdMode::Abs).with_warmup(2);
+    /// assert!(!plateau.update(0.1));
+    /// assert!(!plateau.update(2.0));
+    /// assert!(!plateau.update(1.0));
+    /// assert_eq!(plateau.best(), 1.0);
+    /// ```
+    pub fn with_warmup(self, warmup_epochs: usize) -> Self {
+        Plateau { warmup_epochs, ..self }
+    }
+
+    /// Selects how a relative threshold treats a negative best value. See [`Semantics`].
+    /// 
+    /// ```
+    /// # use lr_schedulers::plateau::{Mode, Plateau, ThresholdMode};
+    /// # use lr_schedulers::semantics::Semantics;
+    /// let native = Plateau::new(Mode::Min, 0, 0.1, ThresholdMode::Rel).with_history(&[-1.0]);
+    /// let torch = Plateau::new(Mode::Min, 0, 0.1, ThresholdMode::Rel).with_semantics(Semantics::PyTorchParity).with_history(&[-1.0]);
+    /// assert!(!native.is_better(-1.05));
+    /// assert!(torch.is_better(-1.05));
+    /// ```
+    pub fn with_semantics(self, semantics: Semantics) -> Self {
+        Plateau { semantics, ..self }
+    }
+
+    /// Replays the metrics of past epochs, e.g. to resume from a checkpoint that stores only the metric history.
+    /// 
+    /// The best value, the bad epochs and the warmup end as if each metric had been passed to `update` in order.
+    /// 
+    /// ```
+    /// # use lr_schedulers::plateau::{Mode, Plateau, ThresholdMode};
+    /// let plateau = Plateau::new(Mode::Min, 2, 0.0, ThresholdMode::Abs).with_history(&[1.0, 0.5, 0.6, 0.7]);
+    /// assert_eq!(plateau.best(), 0.5);
+    /// assert_eq!(plateau.num_bad_epochs(), 2);
+    /// ```
+    pub fn with_history(mut self, metrics: &[f64]) -> Self {
+        for &metric in metrics {
+            self.update(metric);
+        }
+        self
+    }
+
+    /// Returns true if `metric` improves on the best value by more than the threshold.
+    pub fn is_better(&self, metric: f64) -> bool {
+        if self.semantics == Semantics::PyTorchParity && self.threshold_mode == ThresholdMode::Rel {
+            return match self.mode {
+                Mode::Min => metric < self.best * (1.0 - self.threshold),
+                Mode::Max => metric > self.best * (1.0 + self.threshold),
+            };
+        }
+        let margin = match self.threshold_mode {
+            // The initial best value is infinite, which any finite metric beats.
+            ThresholdMode::Rel if self.best.is_infinite() => 0.0,
+            ThresholdMode::Rel => self.best.abs() * self.threshold,
+            ThresholdMode::Abs => self.threshold,
+        };
+        match self.mode {
+            Mode::Min => metric < self.best - margin,
+            Mode::Max => metric > self.best + margin,
+        }
+    }
+
+    /// Records `metric` and returns true if the metric is on a plateau.
+    pub fn update(&mut self, metric: f64) -> bool {
+        if self.epochs < self.warmup_epochs {
+            self.epochs += 1;
+            return false;
+        }
+        if self.is_better(metric) {
+            self.best = metric;
+            self.num_bad_epochs = 0;
+        } else {
+            self.num_bad_epochs += 1;
+        }
+        self.is_plateaued()
+    }
+
+    /// Returns true if the number of bad epochs exceeds the patience.
+    pub fn is_plateaued(&self) -> bool {
+        self.num_bad_epochs > self.patience
+    }
+
+    /// Resets the number of bad epochs, e.g. after acting on a plateau.
+    pub fn reset_bad_epochs(&mut self) {
+        self.num_bad_epochs = 0;
+    }
+
+    /// Returns the best metric seen so far.
+    pub fn best(&self) -> f64 {
+        self.best
+    }
+
+    /// Returns the number of consecutive epochs without improvement.
+    pub fn num_bad_epochs(&self) -> usize {
+        self.num_bad_epochs
+    }
+
+    /// Returns true while the first `warmup_epochs` metrics are being ignored.
+    pub fn in_warmup(&self) -> bool {
+        self.epochs < self.warmup_epochs
+    }
+
+    /// Returns the direction in which the metric improves.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_threshold() {
+        let mut plateau = Plateau::new(Mode::Min, 0, 0.1, ThresholdMode::Rel);
+        let metrics = [1.0, 0.95, 0.85, 0.8, 0.7];
+        let expected = [false, true, false, true, false];
+        for (i, (metric, exp)) in metrics.iter().zip(expected).enumerate() {
+            assert_eq!(plateau.update(*metric), exp, "Epoch {}", i);
+            if exp {
+                plateau.reset_bad_epochs();
+            }
+        }
+        assert_eq!(plateau.best(), 0.7);
+    }
+
+    #[test]
+    fn max_mode() {
+        let mut plateau = Plateau::new(Mode::Max, 1, 0.5, ThresholdMode::Abs);
+        let metrics = [1.0, 1.4, 1.6, 2.0, 2.05];
+        let expected_bad_epochs = [0, 1, 0, 1, 2];
+        for (i, (metric, exp)) in metrics.iter().zip(expected_bad_epochs).enumerate() {
+            plateau.update(*metric);
+            assert_eq!(plateau.num_bad_epochs(), exp, "Epoch {}", i);
+        }
+        assert!(plateau.is_plateaued());
+        assert_eq!(plateau.best(), 1.6);
+    }
+
+    #[test]
+    fn nan_is_bad_epoch() {
+        let mut plateau = Plateau::new(Mode::Min, 0, 0.0, ThresholdMode::Rel);
+        plateau.update(1.0);
+        assert!(plateau.update(f64::NAN));
+        assert_eq!(plateau.best(), 1.0);
+    }
+
+    #[test]
+    fn relative_threshold_of_negative_metric() {
+        let metrics = [-1.0, -1.05, -1.2, -1.25];
+        for (semantics, expected_best) in [(Semantics::Native, -1.2), (Semantics::PyTorchParity, -1.25)] {
+            let mut plateau = Plateau::new(Mode::Min, 5, 0.1, ThresholdMode::Rel).with_semantics(semantics);
+            for metric in metrics {
+                plateau.update(metric);
+            }
+            assert_eq!(plateau.best(), expected_best, "{:?}", semantics);
+        }
+        let mut plateau = Plateau::new(Mode::Max, 5, 0.1, ThresholdMode::Rel);
+        plateau.update(-1.0);
+        assert!(plateau.is_better(-0.85) && !plateau.is_better(-0.95));
+    }
+
+    #[test]
+    fn replay_history() {
+        let metrics = [0.1, 5.0, 0.2, 1.0, 0.15, 0.3];
+        let mut plateau = Plateau::new(Mode::Min, 3, 0.0, ThresholdMode::Abs).with_warmup(2);
+        for (i, metric) in metrics.iter().enumerate() {
+            plateau.update(*metric);
+            let resumed = Plateau::new(Mode::Min, 3, 0.0, ThresholdMode::Abs).with_warmup(2).with_history(&metrics[.. i + 1]);
+            assert_eq!(resumed.best(), plateau.best(), "Epoch {}", i);
+            assert_eq!(resumed.num_bad_epochs(), plateau.num_bad_epochs(), "Epoch {}", i);
+            assert_eq!(resumed.in_warmup(), plateau.in_warmup(), "Epoch {}", i);
+        }
+    }
+
+    #[test]
+    fn ignore_warmup_epochs() {
+        let mut plateau = Plateau::new(Mode::Min, 1, 0.0, ThresholdMode::Abs).with_warmup(3);
+        let metrics = [0.1, 5.0, 0.2, 1.0, 1.0, 1.0];
+        let expected_bad_epochs = [0, 0, 0, 0, 1, 2];
+        for (i, (metric, exp)) in metrics.iter().zip(expected_bad_epochs).enumerate() {
+            assert_eq!(plateau.in_warmup(), i < 3, "Epoch {}", i);
+            plateau.update(*metric);
+            assert_eq!(plateau.num_bad_epochs(), exp, "Epoch {}", i);
+        }
+        assert!(plateau.is_plateaued());
+        assert_eq!(plateau.best(), 1.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,153 @@
+use crate::analysis::trajectory;
+use crate::Scheduler;
+
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders the first `steps` learning rates of `scheduler` as a text plot of `width` columns and `height` rows.
+/// 
+/// Each column covers an equal share of the steps and is drawn from its lowest to its highest learning rate.
+/// The top and the bottom rows are labeled with the largest and the smallest learning rates, and the axis is labeled with
+/// the first and the last steps. Columns containing a restart, i.e. a step whose learning rate jumps up by more than
+/// half of the plotted range, are marked with `^` below the axis. Lines have no trailing spaces, and NaN learning rates are not drawn.
+/// Returns an empty string if any of `steps`, `width` and `height` is 0.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::plot;
+/// let plot = plot::ascii(&LinearLR::new(1.0, 1.0, 0.0, 4, 0), 5, 5, 3);
+/// let expected = "\
+/// 1.000e0 |*
+///         | **
+/// 0.000e0 |   **
+///         +-----
+///          0   4
+/// ";
+/// assert_eq!(plot, expected);
+/// ```
+pub fn ascii<S: Scheduler + Clone>(scheduler: &S, steps: usize, width: usize, height: usize) -> String {
+    if steps == 0 || width == 0 || height == 0 {
+        return String::new();
+    }
+    let lrs = trajectory(scheduler, steps);
+    let (min, max) = range(&lrs);
+    let width = width.min(steps);
+    let row = |lr: f64| {
+        if max > min {
+            ((max - lr) / (max - min) * (height - 1) as f64).round() as usize
+        } else {
+            (height - 1) / 2
+        }
+    };
+    let mut grid = vec![vec![' '; width]; height];
+    let mut restarts = vec![' '; width];
+    for column in 0 .. width {
+        let bucket = &lrs[column * steps / width .. (column + 1) * steps / width];
+        let (low, high) = range(bucket);
+        if !low.is_nan() {
+            for line in grid.iter_mut().take(row(low) + 1).skip(row(high)) {
+                line[column] = '*';
+            }
+        }
+        let start = column * steps / width;
+        let restarted = (start.max(1) .. start + bucket.len()).any(|step| lrs[step] - lrs[step - 1] > 0.5 * (max - min));
+        if restarted {
+            restarts[column] = '^';
+        }
+    }
+    let labels = [format!("{:.3e}", max), format!("{:.3e}", min)];
+    let pad = labels.iter().map(String::len).max().unwrap_or(0);
+    let mut plot = String::new();
+    for (i, line) in grid.iter().enumerate() {
+        let label = match i {
+            0 => labels[0].as_str(),
+            i if i == height - 1 => labels[1].as_str(),
+            _ => "",
+        };
+        push_line(&mut plot, &format!("{:<pad$} |{}", label, line.iter().collect::<String>()));
+    }
+    push_line(&mut plot, &format!("{:pad$} +{}", "", "-".repeat(width)));
+    if restarts.contains(&'^') {
+        push_line(&mut plot, &format!("{:pad$}  {}", "", restarts.iter().collect::<String>()));
+    }
+    let last = (steps - 1).to_string();
+    let gap = width.saturating_sub(1 + last.len());
+    let axis = if width > last.len() { format!("0{}{}", " ".repeat(gap), last) } else { "0".to_string() };
+    push_line(&mut plot, &format!("{:pad$}  {}", "", axis));
+    plot
+}
+
+/// Renders the first `steps` learning rates of `scheduler` as a single line of `width` block characters, e.g. for a log line.
+/// 
+/// Each character shows the mean learning rate of an equal share of the steps, from `▁` for the smallest to `█` for the largest.
+/// Returns an empty string if `steps` or `width` is 0.
+/// 
+/// ```
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::plot;
+/// assert_eq!(plot::sparkline(&LinearLR::new(1.0, 0.0, 1.0, 7, 0), 8, 8), "▁▂▃▄▅▆▇█");
+/// ```
+pub fn sparkline<S: Scheduler + Clone>(scheduler: &S, steps: usize, width: usize) -> String {
+    if steps == 0 || width == 0 {
+        return String::new();
+    }
+    let lrs = trajectory(scheduler, steps);
+    let width = width.min(steps);
+    let means: Vec<f64> = (0 .. width)
+        .map(|column| {
+            let bucket = &lrs[column * steps / width .. (column + 1) * steps / width];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect();
+    let (min, max) = range(&means);
+    means
+        .iter()
+        .map(|&mean| {
+            let level = if max > min { ((mean - min) / (max - min) * 7.0).round() as usize } else { 0 };
+            BLOCKS[level.min(7)]
+        })
+        .collect()
+}
+
+/// Returns the smallest and the largest of `lrs` ignoring NaN, or NaN for both if there is no such value.
+fn range(lrs: &[f64]) -> (f64, f64) {
+    (lrs.iter().copied().fold(f64::NAN, f64::min), lrs.iter().copied().fold(f64::NAN, f64::max))
+}
+
+fn push_line(plot: &mut String, line: &str) {
+    plot.push_str(line.trim_end());
+    plot.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::constant::ConstantLR;
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use super::*;
+
+    #[test]
+    fn mark_restarts() {
+        let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 4, 1, 0);
+        let plot = ascii(&scheduler, 15, 15, 5);
+        let lines: Vec<_> = plot.lines().collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0], "1.000e0 |*    *    *");
+        assert_eq!(lines[4], "0.000e0 |    *    *    *");
+        assert_eq!(lines[6], "              ^    ^");
+        assert_eq!(lines[7], "         0            14");
+    }
+
+    #[test]
+    fn degenerate_plots() {
+        let constant = ConstantLR::new(0.5, 1.0, 0, 0);
+        assert_eq!(ascii(&constant, 0, 10, 3), "");
+        assert_eq!(ascii(&constant, 3, 10, 3), "5.000e-1 |\n         |***\n5.000e-1 |\n         +---\n          0 2\n");
+        assert_eq!(sparkline(&constant, 3, 10), "▁▁▁");
+        assert_eq!(sparkline(&constant, 10, 0), "");
+        // Wide plots of many steps average the steps of each column.
+        let decay = CosineAnnealingWarmRestarts::new(1.0, 0.0, 999, 1, 0);
+        assert_eq!(sparkline(&decay, 1000, 4).chars().next(), Some('█'));
+        assert_eq!(ascii(&decay, 1000, 40, 10).lines().count(), 12);
+    }
+}
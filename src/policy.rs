@@ -0,0 +1,69 @@
+use crate::any::AnyScheduler;
+use crate::constant::ConstantLR;
+use crate::cosine_annealing::CosineAnnealingLR;
+use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+use crate::exponential::ExponentialLR;
+use crate::linear::LinearLR;
+use crate::wsd::WarmupStableDecay;
+use crate::Scheduler;
+
+/// Hyperparameters scheduled for a step.
+/// 
+/// Quantities that a scheduler does not schedule are None, and the optimizer keeps its own values for them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolicyOutput {
+    /// Learning rate.
+    pub lr: f64,
+    /// Momentum, or beta1 of Adam-like optimizers.
+    pub momentum: Option<f64>,
+    /// Weight decay.
+    pub weight_decay: Option<f64>,
+}
+
+impl PolicyOutput {
+    /// Constructs a PolicyOutput scheduling only the learning rate.
+    pub fn lr(lr: f64) -> Self {
+        PolicyOutput { lr, momentum: None, weight_decay: None }
+    }
+}
+
+/// Schedulers of the full optimizer policy, i.e. the learning rate and optionally the momentum and the weight decay.
+/// 
+/// Optimizer adapters can consume all scheduled quantities uniformly through `get_policy`.
+/// The default implementation schedules only the learning rate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::policy::{PolicyOutput, PolicyScheduler};
+/// # use lr_schedulers::weight_decay::{CoupledWeightDecay, Coupling};
+/// fn apply<S: PolicyScheduler>(scheduler: &S) -> (f64, f64) {
+///     let policy = scheduler.get_policy(0.01);
+///     (policy.lr, policy.weight_decay.unwrap_or(0.01))
+/// }
+/// let scheduler = ExponentialLR::new(1e-3, 0.5, 0);
+/// assert_eq!(apply(&scheduler), (1e-3, 0.01));
+/// let scheduler = CoupledWeightDecay::new(scheduler, 0.1, Coupling::Proportional);
+/// assert_eq!(apply(&scheduler), (1e-3, 0.1));
+/// ```
+pub trait PolicyScheduler: Scheduler {
+    /// Returns the hyperparameters for the current step.
+    fn get_policy(&self, loss: f64) -> PolicyOutput {
+        PolicyOutput::lr(self.get_lr(loss))
+    }
+}
+
+impl<S: PolicyScheduler + ?Sized> PolicyScheduler for Box<S> {
+    fn get_policy(&self, loss: f64) -> PolicyOutput {
+        (**self).get_policy(loss)
+    }
+}
+
+impl PolicyScheduler for ConstantLR {}
+impl PolicyScheduler for LinearLR {}
+impl PolicyScheduler for ExponentialLR {}
+impl PolicyScheduler for CosineAnnealingLR {}
+impl PolicyScheduler for CosineAnnealingWarmRestarts {}
+impl PolicyScheduler for WarmupStableDecay {}
+impl PolicyScheduler for AnyScheduler {}
\ No newline at end of file
@@ -0,0 +1,165 @@
+use crate::combinators::Concat;
+use crate::constant::ConstantLR;
+use crate::cosine_annealing::CosineAnnealingLR;
+use crate::phases::{Decay, Ramp, Schedule};
+use crate::{epochs_to_steps, ClosedForm};
+
+/// Ratio of the learning rate during the frozen phase of [`fine_tune`] to `base_lr`.
+pub const FROZEN_FACTOR: f64 = 0.1;
+
+/// Builds a two-stage fine-tuning schedule.
+/// 
+/// During the first `frozen_steps` steps, where only the new layers are trained, the learning rate is held at `FROZEN_FACTOR * base_lr`.
+/// Then the whole model is trained with a cosine decay from `base_lr` to 0 until `total_steps`, after which 0 is held.
+/// 
+/// Like the other presets, the schedule is evaluated lazily. To precompute it, e.g. for a hot loop, use
+/// [`CompiledSchedule::from_closed_form`](crate::compiled::CompiledSchedule::from_closed_form).
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::presets::fine_tune;
+/// # use lr_schedulers::ClosedForm;
+/// let scheduler = fine_tune(1e-3, 100, 1000);
+/// assert_eq!(scheduler.lr_at(0), 1e-4);
+/// assert_eq!(scheduler.lr_at(100), 1e-3);
+/// assert_eq!(scheduler.lr_at(1000), 0.0);
+/// ```
+pub fn fine_tune(base_lr: f64, frozen_steps: usize, total_steps: usize) -> Concat<ConstantLR, CosineAnnealingLR> {
+    let frozen = ConstantLR::new(base_lr, FROZEN_FACTOR, frozen_steps, 0);
+    let cosine = CosineAnnealingLR::decay_once(base_lr, 0.0, total_steps.saturating_sub(frozen_steps), 0);
+    // The frozen phase ends after `frozen_steps` steps, so it is bounded.
+    frozen.then(cosine).unwrap()
+}
+
+/// Builds the ResNet-50 ImageNet schedule of Goyal et al. (2017).
+/// 
+/// Over a run of 90 epochs scaled to `total_steps`, the learning rate increases linearly from 0 to `base_lr` during the first 5 epochs,
+/// and is multiplied by 0.1 at epochs 30, 60 and 80.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::presets::resnet50_imagenet;
+/// # use lr_schedulers::ClosedForm;
+/// let scheduler = resnet50_imagenet(0.1, 900);
+/// assert_eq!(scheduler.lr_at(50), 0.1);
+/// assert!((scheduler.lr_at(300) - 0.01).abs() < 1e-15);
+/// ```
+pub fn resnet50_imagenet(base_lr: f64, total_steps: usize) -> Schedule {
+    // Scales an epoch of the original 90-epoch run to steps.
+    let epoch = |epochs: f64| epochs_to_steps(epochs / 90.0, total_steps);
+    let mut builder = Schedule::phases(base_lr).warmup(epoch(5.0), Ramp::Linear);
+    let mut lr = base_lr;
+    let mut end = epoch(5.0);
+    for milestone in [epoch(30.0), epoch(60.0), epoch(80.0)] {
+        // A decay of a single step ending at the milestone drops the learning rate at the milestone.
+        lr *= 0.1;
+        builder = builder.hold(milestone.saturating_sub(end + 1)).decay(1, Decay::Linear { to: lr });
+        end = end.max(milestone.saturating_sub(1)) + 1;
+    }
+    builder.hold(total_steps.saturating_sub(end)).build(0)
+}
+
+/// Builds the BERT fine-tuning schedule of Devlin et al. (2019).
+/// 
+/// The learning rate increases linearly from 0 to `base_lr` during the first 10% of `total_steps`,
+/// and then decreases linearly to 0 at `total_steps`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::presets::bert_finetune;
+/// # use lr_schedulers::ClosedForm;
+/// let scheduler = bert_finetune(2e-5, 1000);
+/// assert_eq!(scheduler.lr_at(100), 2e-5);
+/// assert_eq!(scheduler.lr_at(1000), 0.0);
+/// ```
+pub fn bert_finetune(base_lr: f64, total_steps: usize) -> Schedule {
+    let warmup_steps = epochs_to_steps(0.1, total_steps);
+    warmup_then(base_lr, warmup_steps, total_steps, Decay::Linear { to: 0.0 })
+}
+
+/// Builds a GPT pretraining schedule following Hoffmann et al. (2022).
+/// 
+/// The learning rate increases linearly from 0 to `base_lr` during the first 1% of `total_steps`,
+/// and then follows a cosine decay to 10% of `base_lr` at `total_steps`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::presets::gpt_pretrain;
+/// # use lr_schedulers::ClosedForm;
+/// let scheduler = gpt_pretrain(6e-4, 10_000);
+/// assert!((scheduler.lr_at(100) - 6e-4).abs() < 1e-15);
+/// assert!((scheduler.lr_at(10_000) - 6e-5).abs() < 1e-15);
+/// ```
+pub fn gpt_pretrain(base_lr: f64, total_steps: usize) -> Schedule {
+    let warmup_steps = epochs_to_steps(0.01, total_steps);
+    warmup_then(base_lr, warmup_steps, total_steps, Decay::Cosine { to: 0.1 * base_lr })
+}
+
+/// Increases the learning rate linearly from 0 to `base_lr` over `warmup_steps`, and then follows `decay` until `total_steps`.
+fn warmup_then(base_lr: f64, warmup_steps: usize, total_steps: usize, decay: Decay) -> Schedule {
+    Schedule::phases(base_lr).warmup(warmup_steps, Ramp::Linear).decay(total_steps - warmup_steps, decay).build(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ClosedForm, Scheduler};
+    use super::*;
+
+    #[test]
+    fn fine_tune_phases() {
+        let mut scheduler = fine_tune(1.0, 2, 6);
+        let expected_lrs = [0.1, 0.1, 1.0, 0.5 * (1.0 + (std::f64::consts::PI / 4.0).cos()), 0.5, 0.5 * (1.0 - (std::f64::consts::PI / 4.0).cos()), 0.0, 0.0];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            let lr = scheduler.get_lr(0.0);
+            assert!((lr - exp_lr).abs() < 1e-12, "Step {}: left: {}, right: {}", i, lr, exp_lr);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn resnet50_milestones() {
+        let scheduler = resnet50_imagenet(0.4, 90);
+        let steps = [0, 1, 5, 29, 30, 60, 80, 90];
+        let expected_lrs = [0.0, 0.08, 0.4, 0.4, 0.04, 0.004, 0.0004, 0.0004];
+        for (step, exp_lr) in steps.iter().zip(expected_lrs) {
+            let lr = scheduler.lr_at(*step);
+            assert!((lr - exp_lr).abs() < 1e-15, "Step {}: left: {}, right: {}", step, lr, exp_lr);
+        }
+    }
+
+    #[test]
+    fn bert_warmup_and_decay() {
+        let scheduler = bert_finetune(1.0, 20);
+        let steps = [0, 1, 2, 11, 20];
+        let expected_lrs = [0.0, 0.5, 1.0, 0.5, 0.0];
+        for (step, exp_lr) in steps.iter().zip(expected_lrs) {
+            let lr = scheduler.lr_at(*step);
+            assert!((lr - exp_lr).abs() < 1e-15, "Step {}: left: {}, right: {}", step, lr, exp_lr);
+        }
+    }
+
+    #[test]
+    fn long_budgets_are_lazy() {
+        let total_steps = usize::MAX / 2;
+        assert_eq!(fine_tune(1.0, 10, total_steps).horizon(), Some(total_steps));
+        assert_eq!(resnet50_imagenet(1.0, total_steps).horizon(), Some(total_steps));
+        assert_eq!(bert_finetune(1.0, total_steps).horizon(), Some(total_steps));
+        assert_eq!(gpt_pretrain(1.0, total_steps).lr_at(total_steps), 0.1);
+    }
+
+    #[test]
+    fn gpt_decays_to_ten_percent() {
+        let scheduler = gpt_pretrain(1.0, 200);
+        let steps = [0, 1, 2, 101, 200, 300];
+        let expected_lrs = [0.0, 0.5, 1.0, 0.55, 0.1, 0.1];
+        for (step, exp_lr) in steps.iter().zip(expected_lrs) {
+            let lr = scheduler.lr_at(*step);
+            assert!((lr - exp_lr).abs() < 1e-12, "Step {}: left: {}, right: {}", step, lr, exp_lr);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,112 @@
+use crate::rng::SchedulerRng;
+use crate::{Bounded, Cyclical, Scheduler};
+
+/// Randomizes the peak learning rate of every cycle of a cyclical scheduler, as in random cyclical learning rates for ensembling.
+/// 
+/// The peak of each cycle is sampled uniformly from `[low, high]`, and the learning rate within the cycle is rescaled linearly
+/// so that the bottom of the schedule stays at its lower bound and its top reaches the sampled peak.
+/// The peak of a cycle depends only on `seed` and the index of the cycle, so a resumed run with the same `seed` reproduces
+/// the same peaks, and `peak` returns the peak of any cycle for replay or logging.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+/// # use lr_schedulers::random_amplitude::RandomAmplitude;
+/// # use lr_schedulers::Scheduler;
+/// let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 4, 1, 0);
+/// let mut scheduler = RandomAmplitude::new(scheduler, 0.5, 1.0, 42);
+/// for cycle in 0 .. 3 {
+///     let peak = scheduler.get_lr(0.01);
+///     assert_eq!(peak, scheduler.peak(cycle));
+///     assert!((0.5 .. 1.0).contains(&peak));
+///     for _ in 0 .. 5 {
+///         // Note: loss value is not used in this scheduler.
+///         scheduler.step(0.01);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RandomAmplitude<S> {
+    scheduler: S,
+    low: f64,
+    high: f64,
+    rng: SchedulerRng,
+    last_lr: Option<f64>,
+}
+
+impl<S: Cyclical + Bounded> RandomAmplitude<S> {
+    /// Constructs a RandomAmplitude instance sampling peaks from `[low, high]` with the random seed `seed`.
+    pub fn new(scheduler: S, low: f64, high: f64, seed: u64) -> Self {
+        RandomAmplitude { scheduler, low, high, rng: SchedulerRng::new(seed), last_lr: None }
+    }
+
+    /// Returns the peak learning rate of `cycle`.
+    pub fn peak(&self, cycle: usize) -> f64 {
+        (self.high - self.low).mul_add(self.rng.unit_at(cycle as u64), self.low)
+    }
+
+    /// Returns the peaks of the cycles so far, including the current one.
+    pub fn peaks(&self) -> Vec<f64> {
+        (0 ..= self.scheduler.current_cycle()).map(|cycle| self.peak(cycle)).collect()
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Cyclical + Bounded> Scheduler for RandomAmplitude<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.scheduler.step(loss);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        let lr = self.scheduler.get_lr(loss);
+        let (min, max) = self.scheduler.bounds();
+        if max <= min {
+            return lr;
+        }
+        let peak = self.peak(self.scheduler.current_cycle());
+        min + (lr - min) * (peak - min) / (max - min)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::relative_eq;
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use crate::ClosedForm;
+    use super::*;
+
+    #[test]
+    fn rescale_each_cycle() {
+        let mut scheduler = RandomAmplitude::new(CosineAnnealingWarmRestarts::new(1.0, 0.2, 2, 1, 0), 0.4, 0.8, 7);
+        let reference = CosineAnnealingWarmRestarts::new(1.0, 0.2, 2, 1, 0);
+        for i in 0 .. 12 {
+            let cycle = i / 3;
+            let fraction = (reference.lr_at(i) - 0.2) / 0.8;
+            let expected = 0.2 + fraction * (scheduler.peak(cycle) - 0.2);
+            assert!(relative_eq!(scheduler.get_lr(0.0), expected, epsilon = 1e-12), "Step {}", i);
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.peaks().len(), 5);
+        assert_ne!(scheduler.peak(0), scheduler.peak(1));
+        let other = RandomAmplitude::new(CosineAnnealingWarmRestarts::new(1.0, 0.2, 2, 1, 0), 0.4, 0.8, 7);
+        assert_eq!(other.peak(3), scheduler.peak(3));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,261 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use crate::Scheduler;
+
+/// Command sent by an operator to a [`RemoteControlled`] scheduler.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Reports the status without changing anything.
+    Query,
+    /// Multiplies the learning rate of the current and later steps by the given factor.
+    Scale(f64),
+    /// Resets the wrapped scheduler to its state at construction.
+    Restart,
+    /// Stops advancing the wrapped scheduler.
+    Pause,
+    /// Advances the wrapped scheduler again.
+    Resume,
+}
+
+/// Status reported in response to every [`Command`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Status {
+    /// Learning rate of the current step, including the scale.
+    pub lr: f64,
+    /// Number of steps processed.
+    pub steps: usize,
+    /// Product of the factors of `Command::Scale`.
+    pub scale: f64,
+    /// True if the scheduler is paused.
+    pub paused: bool,
+}
+
+/// Transport carrying commands from an operator to the training process.
+/// 
+/// Implement this trait on top of a socket or any other channel. Both methods are called from the training loop,
+/// so `poll` must not block.
+pub trait ControlTransport {
+    /// Returns the next pending command, or None if there is none.
+    fn poll(&mut self) -> Option<Command>;
+    /// Sends the status after a command has been applied.
+    fn respond(&mut self, status: Status);
+}
+
+/// Lets an operator inspect and adjust a running schedule.
+/// 
+/// Pending commands are applied at the beginning of every `step`, on the thread of the training loop,
+/// so the schedule never changes in the middle of a step.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::remote::{channel, Command, RemoteControlled};
+/// # use lr_schedulers::Scheduler;
+/// # use std::thread;
+/// let (transport, operator) = channel();
+/// let mut scheduler = RemoteControlled::new(ExponentialLR::new(1.0, 0.5, 0), transport);
+/// let handle = thread::spawn(move || operator.send(Command::Scale(0.1)).unwrap());
+/// while !handle.is_finished() {
+///     // Note: loss value is not used in this scheduler.
+///     scheduler.step(0.01);
+/// }
+/// let status = handle.join().unwrap();
+/// assert_eq!(status.scale, 0.1);
+/// assert_eq!(scheduler.get_lr(0.01), 0.1 * 0.5f64.powi(scheduler.steps() as i32));
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemoteControlled<S, T> {
+    scheduler: S,
+    initial: S,
+    transport: T,
+    scale: f64,
+    paused: bool,
+    steps: usize,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler + Clone, T: ControlTransport> RemoteControlled<S, T> {
+    /// Constructs a RemoteControlled instance.
+    pub fn new(scheduler: S, transport: T) -> Self {
+        RemoteControlled {
+            initial: scheduler.clone(),
+            scheduler,
+            transport,
+            scale: 1.0,
+            paused: false,
+            steps: 0,
+            last_lr: None,
+        }
+    }
+
+    /// Applies all pending commands. This is also done at every `step`.
+    pub fn handle_commands(&mut self) {
+        while let Some(command) = self.transport.poll() {
+            match command {
+                Command::Query => {}
+                Command::Scale(factor) => self.scale *= factor,
+                Command::Restart => self.scheduler = self.initial.clone(),
+                Command::Pause => self.paused = true,
+                Command::Resume => self.paused = false,
+            }
+            let status = self.status();
+            self.transport.respond(status);
+        }
+    }
+
+    /// Returns the current status.
+    pub fn status(&self) -> Status {
+        Status { lr: self.get_lr(0.0), steps: self.steps, scale: self.scale, paused: self.paused }
+    }
+
+    /// Returns the number of steps processed.
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler + Clone, T: ControlTransport> Scheduler for RemoteControlled<S, T> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.handle_commands();
+        if !self.paused {
+            self.scheduler.step(loss);
+        }
+        self.steps += 1;
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scale * self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+/// Error returned when the other end of a channel has been dropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Disconnected;
+
+impl fmt::Display for Disconnected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the training process is disconnected")
+    }
+}
+
+impl Error for Disconnected {}
+
+/// Creates a transport over in-process channels and the operator handle connected to it.
+pub fn channel() -> (ChannelTransport, Operator) {
+    let (command_tx, command_rx) = mpsc::channel();
+    let (status_tx, status_rx) = mpsc::channel();
+    (ChannelTransport { commands: command_rx, statuses: status_tx }, Operator { commands: command_tx, statuses: status_rx })
+}
+
+/// Training side of [`channel`].
+#[derive(Debug)]
+pub struct ChannelTransport {
+    commands: Receiver<Command>,
+    statuses: Sender<Status>,
+}
+
+impl ControlTransport for ChannelTransport {
+    fn poll(&mut self) -> Option<Command> {
+        self.commands.try_recv().ok()
+    }
+
+    fn respond(&mut self, status: Status) {
+        // The operator may have gone away, which must not interrupt training.
+        let _ = self.statuses.send(status);
+    }
+}
+
+/// Operator side of [`channel`].
+#[derive(Debug)]
+pub struct Operator {
+    commands: Sender<Command>,
+    statuses: Receiver<Status>,
+}
+
+impl Operator {
+    /// Sends `command` and waits until the training loop applies it at its next step.
+    pub fn send(&self, command: Command) -> Result<Status, Disconnected> {
+        self.commands.send(command).map_err(|_| Disconnected)?;
+        self.statuses.recv().map_err(|_| Disconnected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use crate::linear::LinearLR;
+    use super::*;
+
+    /// Transport replaying scripted commands.
+    #[derive(Default)]
+    struct Script {
+        commands: VecDeque<Option<Command>>,
+        statuses: Vec<Status>,
+    }
+
+    impl ControlTransport for Script {
+        fn poll(&mut self) -> Option<Command> {
+            self.commands.pop_front().flatten()
+        }
+
+        fn respond(&mut self, status: Status) {
+            self.statuses.push(status);
+        }
+    }
+
+    #[test]
+    fn apply_commands_at_step() {
+        // Each `None` ends the commands of a step.
+        let commands = [
+            None,
+            Some(Command::Scale(0.5)), None,
+            Some(Command::Pause), None,
+            Some(Command::Resume), Some(Command::Query), None,
+            Some(Command::Restart), None,
+        ];
+        let transport = Script { commands: commands.into_iter().collect(), ..Default::default() };
+        let mut scheduler = RemoteControlled::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), transport);
+        let expected_lrs = [0.75, 0.25, 0.25, 0.125, 0.375];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            scheduler.step(0.0);
+            assert_eq!(scheduler.get_lr(0.0), *exp_lr, "Step {}", i);
+        }
+        let statuses = &scheduler.transport.statuses;
+        assert_eq!(statuses.len(), 5);
+        assert_eq!(statuses[0], Status { lr: 0.375, steps: 1, scale: 0.5, paused: false });
+        assert_eq!(statuses[4], Status { lr: 0.5, steps: 4, scale: 0.5, paused: false });
+    }
+
+    #[test]
+    fn disconnected_operator() {
+        let (transport, operator) = channel();
+        drop(transport);
+        assert_eq!(operator.send(Command::Query), Err(Disconnected));
+        let (transport, operator) = channel();
+        drop(operator);
+        let mut scheduler = RemoteControlled::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), transport);
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.75);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,142 @@
+use crate::compiled::min_max;
+use crate::{Bounded, ClosedForm, Scheduler};
+
+/// Behavior of [`TraceScheduler`] after the trace is exhausted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Exhausted {
+    /// Holds the last learning rate of the trace.
+    Hold,
+    /// Plays the trace again from the beginning.
+    Repeat,
+    /// Returns the given learning rate.
+    Constant(f64),
+}
+
+/// Plays back a recorded learning rate trace.
+/// 
+/// The learning rate at step i is the i-th element of the trace, e.g. exported from another framework,
+/// which makes comparisons against legacy runs straightforward.
+/// After the trace is exhausted, the learning rate follows [`Exhausted`].
+/// An empty trace returns 0.0 unless `Exhausted::Constant` is specified.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::replay::{Exhausted, TraceScheduler};
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = TraceScheduler::new(vec![0.1, 0.2, 0.3], Exhausted::Repeat, 0);
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 5 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(learning_rates, [0.1, 0.2, 0.3, 0.1, 0.2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TraceScheduler {
+    trace: Vec<f64>,
+    exhausted: Exhausted,
+    step: usize,
+}
+
+impl TraceScheduler {
+    /// Constructs a TraceScheduler instance.
+    /// 
+    /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
+    pub fn new(trace: Vec<f64>, exhausted: Exhausted, init_step: usize) -> Self {
+        TraceScheduler { trace, exhausted, step: init_step }
+    }
+
+    /// Returns the recorded trace.
+    pub fn trace(&self) -> &[f64] {
+        &self.trace
+    }
+
+    /// Returns true if the current step is beyond the end of the trace.
+    pub fn is_exhausted(&self) -> bool {
+        self.step >= self.trace.len()
+    }
+}
+
+impl Scheduler for TraceScheduler {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+}
+
+impl Bounded for TraceScheduler {
+    fn bounds(&self) -> (f64, f64) {
+        let after = match self.exhausted {
+            Exhausted::Constant(lr) => lr,
+            _ if self.trace.is_empty() => 0.0,
+            _ => return min_max(self.trace.iter().copied()).unwrap(),
+        };
+        let (min, max) = min_max(self.trace.iter().copied()).unwrap_or((after, after));
+        (min.min(after), max.max(after))
+    }
+}
+
+impl ClosedForm for TraceScheduler {
+    fn lr_at(&self, step: usize) -> f64 {
+        if let Some(lr) = self.trace.get(step) {
+            return *lr;
+        }
+        match self.exhausted {
+            Exhausted::Hold => self.trace.last().copied().unwrap_or(0.0),
+            Exhausted::Repeat if self.trace.is_empty() => 0.0,
+            Exhausted::Repeat => self.trace[step % self.trace.len()],
+            Exhausted::Constant(lr) => lr,
+        }
+    }
+
+    /// Returns the length of the trace unless the trace is repeated.
+    fn horizon(&self) -> Option<usize> {
+        match self.exhausted {
+            Exhausted::Repeat if !self.trace.is_empty() => None,
+            _ => Some(self.trace.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn after_exhausted() {
+        let trace = vec![1.0, 0.5, 0.25];
+        let cases = [
+            (Exhausted::Hold, [0.5, 0.25, 0.25, 0.25]),
+            (Exhausted::Repeat, [0.5, 0.25, 1.0, 0.5]),
+            (Exhausted::Constant(0.01), [0.5, 0.25, 0.01, 0.01]),
+        ];
+        for (exhausted, expected_lrs) in cases {
+            let mut scheduler = TraceScheduler::new(trace.clone(), exhausted, 1);
+            for (i, exp_lr) in expected_lrs.iter().enumerate() {
+                assert_eq!(scheduler.get_lr(0.0), *exp_lr, "{:?}, step {}", exhausted, i);
+                assert_eq!(scheduler.is_exhausted(), i >= 2, "{:?}, step {}", exhausted, i);
+                // Proceed a step with dummy loss.
+                scheduler.step(0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_trace() {
+        for exhausted in [Exhausted::Hold, Exhausted::Repeat] {
+            let scheduler = TraceScheduler::new(Vec::new(), exhausted, 0);
+            assert_eq!(scheduler.get_lr(0.0), 0.0, "{:?}", exhausted);
+        }
+        let scheduler = TraceScheduler::new(Vec::new(), Exhausted::Constant(0.1), 0);
+        assert_eq!(scheduler.get_lr(0.0), 0.1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,106 @@
+const GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Deterministic random number generator shared by every stochastic component of this crate.
+/// 
+/// Random results are reproducible from the seed alone, without extra dependencies. Values are available in two ways:
+/// 
+/// * `unit_at(index)` depends only on the seed and `index`, e.g. a step or a cycle, so per-step random decisions are reproduced
+///   after resuming at any step without storing the generator state.
+/// * `next_f64` and `next_below` draw a sequence. The state is the seed and the number of values drawn, `position`,
+///   and a generator restored with `at_position` continues the same sequence.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::rng::SchedulerRng;
+/// let mut rng = SchedulerRng::new(42);
+/// let first = rng.next_f64();
+/// // Save the state, e.g. in a checkpoint.
+/// let (seed, position) = (rng.seed(), rng.position());
+/// let second = rng.next_f64();
+/// let mut resumed = SchedulerRng::new(seed).at_position(position);
+/// assert_eq!(resumed.next_f64(), second);
+/// assert_ne!(first, second);
+/// assert_eq!(resumed.unit_at(7), SchedulerRng::new(42).unit_at(7));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SchedulerRng {
+    seed: u64,
+    position: u64,
+}
+
+impl SchedulerRng {
+    /// Constructs a SchedulerRng instance with the random seed `seed`.
+    pub fn new(seed: u64) -> Self {
+        SchedulerRng { seed, position: 0 }
+    }
+
+    /// Moves the sequence of `next_f64` and `next_below` to `position` values drawn.
+    pub fn at_position(self, position: u64) -> Self {
+        SchedulerRng { position, ..self }
+    }
+
+    /// Returns the random seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the number of values drawn from the sequence.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns a value uniformly distributed in `[0, 1)` determined by the seed and `index` only.
+    pub fn unit_at(&self, index: u64) -> f64 {
+        to_unit(mix((self.seed ^ index.wrapping_mul(0xd1b5_4a32_d192_ed03)).wrapping_add(GAMMA)))
+    }
+
+    /// Returns the next value of the sequence, uniformly distributed in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        to_unit(self.next_u64())
+    }
+
+    /// Returns the next value of the sequence, uniformly distributed in `0 .. bound` up to a negligible bias.
+    pub fn next_below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    /// SplitMix64, whose output depends only on the seed and the position.
+    fn next_u64(&mut self) -> u64 {
+        self.position = self.position.wrapping_add(1);
+        mix(self.seed.wrapping_add(self.position.wrapping_mul(GAMMA)))
+    }
+}
+
+fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+fn to_unit(z: u64) -> f64 {
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resume_sequence() {
+        let mut rng = SchedulerRng::new(7);
+        let values: Vec<_> = (0 .. 5).map(|_| rng.next_f64()).collect();
+        assert_eq!(rng.position(), 5);
+        for position in 0 .. 5 {
+            let mut resumed = SchedulerRng::new(7).at_position(position);
+            assert_eq!(resumed.next_f64(), values[position as usize], "Position {}", position);
+        }
+        for i in 0 .. 1000 {
+            let index = rng.next_below(10);
+            assert!(index < 10, "Draw {}", i);
+            let value = rng.unit_at(i);
+            assert!((0.0 .. 1.0).contains(&value), "Index {}", i);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,232 @@
+use crate::any::AnyScheduler;
+use crate::config::SchedulerConfig;
+use crate::rng::SchedulerRng;
+use crate::semantics::Semantics;
+
+/// Values a hyperparameter can take in a [`SearchSpace`].
+/// 
+/// Integer hyperparameters such as `t_max` are rounded to the nearest non-negative integer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Param {
+    /// A single value.
+    Fixed(f64),
+    /// Values evenly distributed in `[low, high]`.
+    Linear { low: f64, high: f64 },
+    /// Values evenly distributed in `[low, high]` on a logarithmic scale, e.g. for learning rates. Both bounds must be positive.
+    Log { low: f64, high: f64 },
+    /// One of the listed values.
+    Choice(Vec<f64>),
+}
+
+impl Param {
+    /// Maps `u` in `[0, 1]` to a value of this parameter.
+    fn value(&self, u: f64) -> f64 {
+        match self {
+            Param::Fixed(value) => *value,
+            // Return the bounds exactly, which rounding may miss.
+            Param::Linear { low, .. } | Param::Log { low, .. } if u <= 0.0 => *low,
+            Param::Linear { high, .. } | Param::Log { high, .. } if u >= 1.0 => *high,
+            Param::Linear { low, high } => (high - low).mul_add(u, *low),
+            Param::Log { low, high } => (high.ln() - low.ln()).mul_add(u, low.ln()).exp(),
+            Param::Choice(values) => values[((u * values.len() as f64) as usize).min(values.len() - 1)],
+        }
+    }
+
+    /// Returns the values of this parameter on a grid with `points` points per range.
+    fn grid(&self, points: usize) -> Vec<f64> {
+        match self {
+            Param::Fixed(value) => vec![*value],
+            Param::Choice(values) => values.clone(),
+            _ if points <= 1 => vec![self.value(0.0)],
+            _ => (0 .. points).map(|i| self.value(i as f64 / (points - 1) as f64)).collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Param::Choice(values) if values.is_empty())
+    }
+}
+
+/// Hyperparameter space of a scheduler.
+/// 
+/// Each variant holds a [`Param`] for each field of the [`SchedulerConfig`] variant of the same name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchSpace {
+    /// Space of [`SchedulerConfig::Constant`].
+    Constant { base_lr: Param, factor: Param, total_iters: Param },
+    /// Space of [`SchedulerConfig::Linear`].
+    Linear { base_lr: Param, start_factor: Param, end_factor: Param, total_iters: Param },
+    /// Space of [`SchedulerConfig::Exponential`].
+    Exponential { base_lr: Param, gamma: Param },
+    /// Space of [`SchedulerConfig::CosineAnnealing`].
+    CosineAnnealing { eta_0: Param, eta_1: Param, t_max: Param },
+    /// Space of [`SchedulerConfig::CosineAnnealingWarmRestarts`].
+    CosineAnnealingWarmRestarts { eta_0: Param, eta_1: Param, t_0: Param, t_mult: Param },
+}
+
+impl SearchSpace {
+    fn params(&self) -> Vec<&Param> {
+        match self {
+            SearchSpace::Constant { base_lr, factor, total_iters } => vec![base_lr, factor, total_iters],
+            SearchSpace::Linear { base_lr, start_factor, end_factor, total_iters } => {
+                vec![base_lr, start_factor, end_factor, total_iters]
+            }
+            SearchSpace::Exponential { base_lr, gamma } => vec![base_lr, gamma],
+            SearchSpace::CosineAnnealing { eta_0, eta_1, t_max } => vec![eta_0, eta_1, t_max],
+            SearchSpace::CosineAnnealingWarmRestarts { eta_0, eta_1, t_0, t_mult } => vec![eta_0, eta_1, t_0, t_mult],
+        }
+    }
+
+    /// Constructs the configuration from the values of `params` in the same order.
+    fn config(&self, values: &[f64]) -> SchedulerConfig {
+        let int = |value: f64| value.round() as usize;
+        match self {
+            SearchSpace::Constant { .. } => {
+                SchedulerConfig::Constant { base_lr: values[0], factor: values[1], total_iters: int(values[2]) }
+            }
+            SearchSpace::Linear { .. } => SchedulerConfig::Linear {
+                base_lr: values[0], start_factor: values[1], end_factor: values[2], total_iters: int(values[3])
+            },
+            SearchSpace::Exponential { .. } => SchedulerConfig::Exponential { base_lr: values[0], gamma: values[1], decay_steps: 1, staircase: false },
+            SearchSpace::CosineAnnealing { .. } => {
+                SchedulerConfig::CosineAnnealing { eta_0: values[0], eta_1: values[1], t_max: int(values[2]), decay_once: false, min_fraction: None }
+            }
+            SearchSpace::CosineAnnealingWarmRestarts { .. } => SchedulerConfig::CosineAnnealingWarmRestarts {
+                eta_0: values[0], eta_1: values[1], t_0: int(values[2]), t_mult: int(values[3]),
+                steps_per_epoch: 1, semantics: Semantics::Native,
+            },
+        }
+    }
+
+    /// Returns every combination of parameter values, with `points` evenly spaced values for each range.
+    /// 
+    /// A `Param::Choice` contributes all of its values and a `Param::Fixed` its single value.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use lr_schedulers::config::SchedulerConfig;
+    /// # use lr_schedulers::search::{Param, SearchSpace};
+    /// let space = SearchSpace::Exponential {
+    ///     base_lr: Param::Log { low: 1e-3, high: 1e-1 },
+    ///     gamma: Param::Choice(vec![0.9, 0.99]),
+    /// };
+    /// let configs = space.grid(3);
+    /// assert_eq!(configs.len(), 6);
+    /// assert_eq!(configs[0], SchedulerConfig::Exponential { base_lr: 1e-3, gamma: 0.9, decay_steps: 1, staircase: false });
+    /// ```
+    pub fn grid(&self, points: usize) -> Vec<SchedulerConfig> {
+        let mut combinations = vec![Vec::new()];
+        for param in self.params() {
+            let values = param.grid(points);
+            combinations = combinations
+                .into_iter()
+                .flat_map(|prefix| values.iter().map(move |&value| [prefix.as_slice(), &[value]].concat()))
+                .collect();
+        }
+        combinations.iter().map(|values| self.config(values)).collect()
+    }
+
+    /// Returns `n` configurations sampled independently and uniformly with the random seed `seed`.
+    pub fn random(&self, n: usize, seed: u64) -> Vec<SchedulerConfig> {
+        let params = self.params();
+        if params.iter().any(|param| param.is_empty()) {
+            return Vec::new();
+        }
+        let mut rng = SchedulerRng::new(seed);
+        (0 .. n)
+            .map(|_| {
+                let values: Vec<_> = params.iter().map(|param| param.value(rng.next_f64())).collect();
+                self.config(&values)
+            })
+            .collect()
+    }
+
+    /// Returns `n` configurations by Latin hypercube sampling with the random seed `seed`.
+    /// 
+    /// Each range is divided into `n` strata of equal probability, and every stratum of every parameter is sampled exactly once,
+    /// which covers the space more evenly than `random` for the same `n`.
+    pub fn latin_hypercube(&self, n: usize, seed: u64) -> Vec<SchedulerConfig> {
+        let params = self.params();
+        if params.iter().any(|param| param.is_empty()) {
+            return Vec::new();
+        }
+        let mut rng = SchedulerRng::new(seed);
+        let columns: Vec<Vec<f64>> = params
+            .iter()
+            .map(|param| {
+                let mut strata: Vec<usize> = (0 .. n).collect();
+                // Fisher-Yates shuffle.
+                for i in (1 .. n).rev() {
+                    strata.swap(i, rng.next_below(i + 1));
+                }
+                strata.iter().map(|&stratum| param.value((stratum as f64 + rng.next_f64()) / n as f64)).collect()
+            })
+            .collect();
+        (0 .. n)
+            .map(|i| {
+                let values: Vec<_> = columns.iter().map(|column| column[i]).collect();
+                self.config(&values)
+            })
+            .collect()
+    }
+}
+
+/// Constructs a scheduler starting from `init_step` for each configuration.
+pub fn materialize(configs: &[SchedulerConfig], init_step: usize) -> Vec<AnyScheduler> {
+    configs.iter().map(|config| config.build_any(init_step)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ClosedForm;
+    use super::*;
+
+    fn space() -> SearchSpace {
+        SearchSpace::CosineAnnealingWarmRestarts {
+            eta_0: Param::Log { low: 1e-4, high: 1e-1 },
+            eta_1: Param::Fixed(0.0),
+            t_0: Param::Linear { low: 10.0, high: 100.0 },
+            t_mult: Param::Choice(vec![1.0, 2.0]),
+        }
+    }
+
+    #[test]
+    fn grid_covers_ranges() {
+        let configs = space().grid(4);
+        assert_eq!(configs.len(), 4 * 4 * 2);
+        assert_eq!(configs[0], SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1e-4, eta_1: 0.0, t_0: 10, t_mult: 1, steps_per_epoch: 1, semantics: Semantics::Native });
+        assert_eq!(configs[configs.len() - 1], SchedulerConfig::CosineAnnealingWarmRestarts { eta_0: 1e-1, eta_1: 0.0, t_0: 100, t_mult: 2, steps_per_epoch: 1, semantics: Semantics::Native });
+        assert!(configs.iter().any(|config| matches!(config, SchedulerConfig::CosineAnnealingWarmRestarts { t_0: 40, .. })));
+    }
+
+    #[test]
+    fn latin_hypercube_strata() {
+        let n = 10;
+        let configs = space().latin_hypercube(n, 42);
+        let mut strata: Vec<_> = configs
+            .iter()
+            .map(|config| match config {
+                SchedulerConfig::CosineAnnealingWarmRestarts { eta_0, .. } => ((eta_0.log10() + 4.0) / 3.0 * n as f64) as usize,
+                _ => unreachable!(),
+            })
+            .collect();
+        strata.sort();
+        assert_eq!(strata, (0 .. n).collect::<Vec<_>>());
+        assert_eq!(configs, space().latin_hypercube(n, 42));
+    }
+
+    #[test]
+    fn random_is_reproducible() {
+        let configs = space().random(20, 7);
+        assert_eq!(configs, space().random(20, 7));
+        assert_ne!(configs, space().random(20, 8));
+        for scheduler in materialize(&configs, 0) {
+            let lr = scheduler.lr_at(0);
+            assert!((1e-4 ..= 1e-1).contains(&lr), "{}", lr);
+        }
+        let empty = SearchSpace::Exponential { base_lr: Param::Choice(Vec::new()), gamma: Param::Fixed(0.9) };
+        assert!(empty.random(3, 0).is_empty());
+        assert!(empty.grid(3).is_empty());
+    }
+}
\ No newline at end of file
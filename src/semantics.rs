@@ -0,0 +1,41 @@
+/// Version of the native learning rate formulas, which is part of [`CanonicalConfig::fingerprint`](crate::config::CanonicalConfig::fingerprint).
+/// 
+/// It is incremented whenever a configuration may generate different learning rates than in earlier releases.
+pub const SEMANTICS_VERSION: u32 = 1;
+
+/// Behavior of a scheduler where this crate intentionally differs from PyTorch.
+/// 
+/// Each scheduler with such a difference takes a `with_semantics` builder. The differences are:
+/// 
+/// * Cycle boundaries of [`CosineAnnealingWarmRestarts`](crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts).
+///   A native cycle of `t_max` is `t_max + 1` steps long and ends at `eta_1`,
+///   while a PyTorch cycle is `t_max` steps long and restarts one step before reaching `eta_1`.
+/// * Relative thresholds of [`Plateau`](crate::plateau::Plateau) and [`EarlyStopping`](crate::early_stopping::EarlyStopping).
+///   A native threshold is relative to `|best|`, while PyTorch multiplies `best` by `1 ± threshold`,
+///   which moves the bound past `best` for a negative metric, so that a slightly worse metric counts as an improvement.
+/// 
+/// Post-horizon behavior is selected by the constructors instead, e.g.
+/// [`CosineAnnealingLR::new`](crate::cosine_annealing::CosineAnnealingLR::new) keeps oscillating after `t_max` as in PyTorch,
+/// while [`CosineAnnealingLR::decay_once`](crate::cosine_annealing::CosineAnnealingLR::decay_once) holds `eta_1`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+/// # use lr_schedulers::semantics::Semantics;
+/// # use lr_schedulers::ClosedForm;
+/// let native = CosineAnnealingWarmRestarts::new(1.0, 0.0, 2, 1, 0);
+/// let torch = native.clone().with_semantics(Semantics::PyTorchParity);
+/// let lrs = |scheduler: &CosineAnnealingWarmRestarts| (0 .. 4).map(|step| scheduler.lr_at(step)).collect::<Vec<_>>();
+/// assert_eq!(lrs(&native), [1.0, 0.5, 0.0, 1.0]);
+/// assert_eq!(lrs(&torch), [1.0, 0.5, 1.0, 0.5]);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Semantics {
+    /// Replicates PyTorch exactly, including its off-by-one and sign quirks.
+    PyTorchParity,
+    /// The behavior of this crate, which is the default.
+    #[default]
+    Native,
+}
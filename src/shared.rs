@@ -0,0 +1,139 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::Scheduler;
+
+/// Shares a scheduler between threads.
+/// 
+/// The wrapped scheduler is guarded by a mutex, so `step` and `get_lr` can be called from any thread holding a clone of the handle.
+/// In addition, the learning rate of the current step is published to an atomic value after every `step`,
+/// and it can be read with `lr` without taking the lock.
+/// 
+/// # Examples
+/// 
+/// Clones of the handle observe the same scheduler:
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::shared::SharedScheduler;
+/// let scheduler = SharedScheduler::new(ExponentialLR::new(2.0, 0.5, 0));
+/// let reader = scheduler.clone();
+/// let handle = std::thread::spawn(move || {
+///     // Note: loss value is not used in this scheduler.
+///     scheduler.step(0.01);
+/// });
+/// handle.join().unwrap();
+/// assert_eq!(reader.lr(), 1.0);
+/// assert_eq!(reader.get_lr(0.01), 1.0);
+/// ```
+#[derive(Debug)]
+pub struct SharedScheduler<S> {
+    inner: Arc<Mutex<S>>,
+    lr: Arc<AtomicU64>,
+}
+
+impl<S: Scheduler> SharedScheduler<S> {
+    /// Constructs a SharedScheduler instance wrapping `scheduler`.
+    /// 
+    /// The atomic snapshot is initialized with the learning rate returned by `get_lr(0.0)`.
+    pub fn new(scheduler: S) -> Self {
+        let lr = scheduler.get_lr(0.0);
+        SharedScheduler {
+            inner: Arc::new(Mutex::new(scheduler)),
+            lr: Arc::new(AtomicU64::new(lr.to_bits())),
+        }
+    }
+
+    /// Proceeds the step of the wrapped scheduler and publishes the new learning rate.
+    pub fn step(&self, loss: f64) {
+        let mut scheduler = self.lock();
+        scheduler.step(loss);
+        let lr = scheduler.get_lr(loss);
+        self.lr.store(lr.to_bits(), Ordering::Release);
+    }
+
+    /// Returns a learning rate for the current step from the wrapped scheduler.
+    pub fn get_lr(&self, loss: f64) -> f64 {
+        self.lock().get_lr(loss)
+    }
+
+    /// Returns the learning rate used for the most recent step from the wrapped scheduler.
+    pub fn get_last_lr(&self) -> Option<f64> {
+        self.lock().get_last_lr()
+    }
+
+    /// Returns the number of steps left in the wrapped scheduler, or None if it is unbounded.
+    pub fn remaining_steps(&self) -> Option<usize> {
+        self.lock().remaining_steps()
+    }
+
+    /// Returns the completed fraction of the wrapped scheduler, or None if it is unbounded.
+    pub fn progress(&self) -> Option<f64> {
+        self.lock().progress()
+    }
+
+    /// Returns the learning rate published by the most recent `step` without locking.
+    pub fn lr(&self) -> f64 {
+        f64::from_bits(self.lr.load(Ordering::Acquire))
+    }
+
+    /// Locks the wrapped scheduler for direct access.
+    /// 
+    /// Changes made through the guard are not published to `lr` until the next `step`.
+    pub fn lock(&self) -> MutexGuard<'_, S> {
+        // A panic in another thread cannot leave a scheduler in a broken state, so the poison flag is ignored.
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<S> Clone for SharedScheduler<S> {
+    fn clone(&self) -> Self {
+        SharedScheduler {
+            inner: Arc::clone(&self.inner),
+            lr: Arc::clone(&self.lr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use crate::exponential::ExponentialLR;
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn snapshot_follows_steps() {
+        let scheduler = SharedScheduler::new(ExponentialLR::new(2.0, 0.5, 0));
+        let expected_lrs = [2.0, 1.0, 0.5, 0.25, 0.125];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            assert_eq!(scheduler.lr(), *exp_lr, "Step {}", i);
+            assert_eq!(scheduler.get_lr(0.0), *exp_lr, "Step {}", i);
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+        }
+    }
+
+    #[test]
+    fn step_from_many_threads() {
+        let num_threads = 4;
+        let steps_per_thread = 25;
+        let scheduler = SharedScheduler::new(LinearLR::new(1.0, 0.0, 1.0, 200, 0));
+        let handles: Vec<_> = (0 .. num_threads)
+            .map(|_| {
+                let scheduler = scheduler.clone();
+                thread::spawn(move || {
+                    for _ in 0 .. steps_per_thread {
+                        scheduler.step(0.0);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        let expected = LinearLR::new(1.0, 0.0, 1.0, 200, num_threads * steps_per_thread).get_lr(0.0);
+        assert_eq!(scheduler.lr(), expected);
+        assert_eq!(scheduler.get_lr(0.0), expected);
+    }
+}
\ No newline at end of file
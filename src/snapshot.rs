@@ -0,0 +1,125 @@
+use crate::{Cyclical, Scheduler};
+
+/// A point at which a snapshot of the model should be saved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotPoint {
+    /// Index of the cycle that reached its minimum.
+    pub cycle: usize,
+    /// Index of the step trained with the minimum learning rate.
+    pub step: usize,
+    /// Learning rate of the step.
+    pub lr: f64,
+}
+
+/// Signals the points to save models for Snapshot Ensembles.
+/// 
+/// Snapshot Ensembles train a model with a cyclical learning rate, e.g. [`CosineAnnealingWarmRestarts`](crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts),
+/// and save a snapshot every time the learning rate reaches the minimum of a cycle. The saved snapshots form an ensemble.
+/// Call `step_with_snapshot` instead of `step` to receive a [`SnapshotPoint`] after the step trained with the minimum learning rate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+/// # use lr_schedulers::snapshot::SnapshotSchedule;
+/// // Cycles of 3 steps, snapshots of the last 2 out of 4 cycles.
+/// let scheduler = CosineAnnealingWarmRestarts::new(1.0, 0.0, 2, 1, 0);
+/// let mut snapshots = SnapshotSchedule::new(scheduler).with_last_cycles(4, 2);
+/// let mut saved = Vec::new();
+/// for _ in 0 .. 12 {
+///     // Train a model with `snapshots.get_lr(loss)`, then:
+///     if let Some(point) = snapshots.step_with_snapshot(0.01) {
+///         saved.push(point.step);
+///     }
+/// }
+/// assert_eq!(saved, [8, 11]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapshotSchedule<S> {
+    scheduler: S,
+    step: usize,
+    first_cycle: usize,
+}
+
+impl<S: Cyclical> SnapshotSchedule<S> {
+    /// Constructs a SnapshotSchedule instance signaling the minimum of every cycle.
+    pub fn new(scheduler: S) -> Self {
+        SnapshotSchedule { scheduler, step: 0, first_cycle: 0 }
+    }
+
+    /// Limits snapshots to the last `k` cycles of a training of `total_cycles` cycles.
+    pub fn with_last_cycles(self, total_cycles: usize, k: usize) -> Self {
+        SnapshotSchedule { first_cycle: total_cycles.saturating_sub(k), ..self }
+    }
+
+    /// Proceeds the step and returns a snapshot point if the step was trained with the minimum learning rate of a cycle.
+    pub fn step_with_snapshot(&mut self, loss: f64) -> Option<SnapshotPoint> {
+        let point = if self.scheduler.at_cycle_minimum() && self.scheduler.current_cycle() >= self.first_cycle {
+            Some(SnapshotPoint {
+                cycle: self.scheduler.current_cycle(),
+                step: self.step,
+                lr: self.scheduler.get_lr(loss),
+            })
+        } else {
+            None
+        };
+        self.step(loss);
+        point
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Cyclical> Scheduler for SnapshotSchedule<S> {
+    fn step(&mut self, loss: f64) {
+        self.scheduler.step(loss);
+        self.step += 1;
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.scheduler.get_last_lr()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosine_annealing::CosineAnnealingLR;
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use super::*;
+
+    #[test]
+    fn snapshot_at_every_minimum() {
+        // Cycles of 2, 3 and 5 steps.
+        let mut snapshots = SnapshotSchedule::new(CosineAnnealingWarmRestarts::new(1.0, 0.1, 1, 2, 0));
+        let points: Vec<_> = (0 .. 10).filter_map(|_| snapshots.step_with_snapshot(0.0)).collect();
+        let expected = [(0, 1), (1, 4), (2, 9)];
+        assert_eq!(points.len(), expected.len());
+        for (point, (cycle, step)) in points.iter().zip(expected) {
+            assert_eq!(point.cycle, cycle);
+            assert_eq!(point.step, step);
+            assert!((point.lr - 0.1).abs() < 1e-12, "{:?}", point);
+        }
+    }
+
+    #[test]
+    fn cosine_annealing_minimum() {
+        let mut snapshots = SnapshotSchedule::new(CosineAnnealingLR::new(1.0, 0.0, 3, 0));
+        let steps: Vec<_> = (0 .. 12).filter_map(|_| snapshots.step_with_snapshot(0.0)).map(|p| p.step).collect();
+        assert_eq!(steps, [3, 9]);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,154 @@
+use crate::Scheduler;
+
+/// Restarts a scheduler when the loss spikes above its moving baseline.
+/// 
+/// The baseline is an exponential moving average of the losses passed to `step`, updated as
+/// `baseline = smoothing * baseline + (1 - smoothing) * loss` and initialized with the first loss.
+/// A loss larger than `factor * baseline`, or NaN, is a spike: the wrapped scheduler is reset to its state at construction,
+/// i.e. a warm restart, and the spike is excluded from the baseline.
+/// Spikes within `cooldown` steps after a restart are ignored, so that a single divergence does not restart repeatedly.
+/// Losses are expected to be positive.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::spike_restart::SpikeRestart;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = SpikeRestart::new(ExponentialLR::new(1.0, 0.5, 0), 2.0, 0.9, 0);
+/// for loss in [1.0, 1.0, 1.0] {
+///     scheduler.step(loss);
+/// }
+/// assert_eq!(scheduler.get_lr(1.0), 0.125);
+/// // The loss jumps above twice the baseline.
+/// scheduler.step(3.0);
+/// assert_eq!(scheduler.get_lr(1.0), 1.0);
+/// assert_eq!(scheduler.restarts(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpikeRestart<S> {
+    scheduler: S,
+    initial: S,
+    factor: f64,
+    smoothing: f64,
+    cooldown: usize,
+    baseline: Option<f64>,
+    steps_since_restart: Option<usize>,
+    restarts: usize,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler + Clone> SpikeRestart<S> {
+    /// Constructs a SpikeRestart instance.
+    /// 
+    /// The parameter `smoothing` must be in `[0, 1)`. Values outside the range are clamped.
+    pub fn new(scheduler: S, factor: f64, smoothing: f64, cooldown: usize) -> Self {
+        SpikeRestart {
+            initial: scheduler.clone(),
+            scheduler,
+            factor,
+            smoothing: smoothing.clamp(0.0, 1.0 - f64::EPSILON),
+            cooldown,
+            baseline: None,
+            steps_since_restart: None,
+            restarts: 0,
+            last_lr: None,
+        }
+    }
+
+    /// Returns true if `loss` is a spike with respect to the current baseline.
+    pub fn is_spike(&self, loss: f64) -> bool {
+        match self.baseline {
+            Some(baseline) => loss > self.factor * baseline || loss.is_nan(),
+            None => loss.is_nan(),
+        }
+    }
+
+    /// Returns the moving baseline of the loss, or None before the first finite loss.
+    pub fn baseline(&self) -> Option<f64> {
+        self.baseline
+    }
+
+    /// Returns the number of restarts triggered so far.
+    pub fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+
+    fn in_cooldown(&self) -> bool {
+        self.steps_since_restart.is_some_and(|steps| steps < self.cooldown)
+    }
+}
+
+impl<S: Scheduler + Clone> Scheduler for SpikeRestart<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.scheduler.get_lr(loss));
+        if self.is_spike(loss) {
+            if !self.in_cooldown() {
+                self.scheduler = self.initial.clone();
+                self.steps_since_restart = Some(0);
+                self.restarts += 1;
+                return;
+            }
+        } else {
+            self.baseline = Some(match self.baseline {
+                Some(baseline) => self.smoothing.mul_add(baseline, (1.0 - self.smoothing) * loss),
+                None => loss,
+            });
+        }
+        self.scheduler.step(loss);
+        self.steps_since_restart = self.steps_since_restart.map(|steps| steps + 1);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn restart_on_spike() {
+        let mut scheduler = SpikeRestart::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), 1.5, 0.5, 1);
+        let losses = [1.0, 1.0, 2.0, 2.0, 1.0, 1.0, f64::NAN];
+        let expected_lrs = [0.75, 0.5, 1.0, 0.75, 0.5, 0.25, 1.0];
+        let expected_restarts = [0, 0, 1, 1, 1, 1, 2];
+        for (i, loss) in losses.iter().enumerate() {
+            scheduler.step(*loss);
+            assert_eq!(scheduler.get_lr(0.0), expected_lrs[i], "Step {}", i);
+            assert_eq!(scheduler.restarts(), expected_restarts[i], "Step {}", i);
+        }
+        // Spikes are excluded from the baseline.
+        assert_eq!(scheduler.baseline(), Some(1.0));
+    }
+
+    #[test]
+    fn follow_moving_baseline() {
+        let mut scheduler = SpikeRestart::new(LinearLR::new(1.0, 1.0, 0.0, 10, 0), 1.5, 0.2, 0);
+        // A gradual increase moves the baseline along without restarts.
+        for loss in [1.0, 1.4, 1.9, 2.6, 3.5] {
+            scheduler.step(loss);
+        }
+        assert_eq!(scheduler.restarts(), 0);
+        assert!(scheduler.is_spike(5.0));
+    }
+}
\ No newline at end of file
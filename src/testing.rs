@@ -0,0 +1,141 @@
+use std::fmt;
+
+use crate::analysis::trajectory;
+use crate::Scheduler;
+
+pub use crate::expect_schedule;
+
+/// Number of steps shown before and after a mismatch by [`ScheduleMismatch`].
+const CONTEXT_STEPS: usize = 3;
+
+/// A step at which a schedule differs from the table of [`check_schedule`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleMismatch {
+    /// The first step of the table, in order of steps, whose learning rate differs from the expected one.
+    pub step: usize,
+    /// Expected learning rate at the step.
+    pub expected: f64,
+    /// Allowed absolute difference.
+    pub tolerance: f64,
+    /// Learning rate of the scheduler at the step.
+    pub actual: f64,
+    /// Rendered learning rates of the steps around the mismatch, with the expected ones where the table has them.
+    pub snippet: String,
+}
+
+impl fmt::Display for ScheduleMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "the learning rate at step {} is {:e}, expected {:e} ± {:e}", self.step, self.actual, self.expected, self.tolerance)?;
+        write!(f, "{}", self.snippet)
+    }
+}
+
+impl std::error::Error for ScheduleMismatch {}
+
+/// Checks the learning rates of `scheduler` against a table of `(step, expected_lr, tolerance)`, without modifying the scheduler.
+/// 
+/// A learning rate matches if it differs from the expected one by at most the tolerance, and NaN matches only NaN.
+/// The table may list the steps in any order, and the scheduler is run with a loss of 0.0 as in [`trajectory`].
+/// See [`expect_schedule!`] for a panicking form for tests.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::testing::check_schedule;
+/// let scheduler = LinearLR::new(1.0, 1.0, 0.0, 4, 0);
+/// assert!(check_schedule(&scheduler, &[(0, 1.0, 0.0), (2, 0.5, 1e-12), (10, 0.0, 0.0)]).is_ok());
+/// let mismatch = check_schedule(&scheduler, &[(3, 0.5, 1e-12)]).unwrap_err();
+/// assert_eq!(mismatch.actual, 0.25);
+/// assert!(mismatch.snippet.contains(">         3  2.5000e-1  5.0000e-1 ± 1e-12"));
+/// ```
+pub fn check_schedule<S: Scheduler + Clone>(scheduler: &S, table: &[(usize, f64, f64)]) -> Result<(), ScheduleMismatch> {
+    let Some(last) = table.iter().map(|&(step, _, _)| step).max() else {
+        return Ok(());
+    };
+    let lrs = trajectory(scheduler, last.saturating_add(CONTEXT_STEPS).saturating_add(1));
+    let mut rows = table.to_vec();
+    rows.sort_by_key(|&(step, _, _)| step);
+    let mismatch = rows.iter().find(|&&(step, expected, tolerance)| {
+        let actual = lrs[step];
+        !(actual.is_nan() && expected.is_nan() || (actual - expected).abs() <= tolerance)
+    });
+    match mismatch {
+        Some(&(step, expected, tolerance)) => {
+            Err(ScheduleMismatch { step, expected, tolerance, actual: lrs[step], snippet: render_snippet(&lrs, &rows, step) })
+        }
+        None => Ok(()),
+    }
+}
+
+fn render_snippet(lrs: &[f64], rows: &[(usize, f64, f64)], step: usize) -> String {
+    let mut snippet = String::from("       step  actual     expected\n");
+    let start = step.saturating_sub(CONTEXT_STEPS);
+    for (i, lr) in lrs.iter().enumerate().take(step + CONTEXT_STEPS + 1).skip(start) {
+        let marker = if i == step { '>' } else { ' ' };
+        let expected = match rows.iter().find(|&&(s, _, _)| s == i) {
+            Some(&(_, expected, tolerance)) => format!("{:.4e} ± {:e}", expected, tolerance),
+            None => String::new(),
+        };
+        let line = format!("{} {:>9}  {:<9.4e}  {}", marker, i, lr, expected);
+        snippet.push_str(line.trim_end());
+        snippet.push('\n');
+    }
+    snippet
+}
+
+/// Asserts that a scheduler follows a table of `(step, expected_lr, tolerance)`, as [`check_schedule`](crate::testing::check_schedule).
+/// 
+/// On a mismatch, the panic message shows the learning rates around the first mismatching step.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::testing::expect_schedule;
+/// expect_schedule!(ExponentialLR::new(1.0, 0.5, 0), [
+///     (0, 1.0, 0.0),
+///     (1, 0.5, 1e-12),
+///     (3, 0.125, 1e-12),
+/// ]);
+/// ```
+#[macro_export]
+macro_rules! expect_schedule {
+    ($scheduler:expr, [$(($step:expr, $lr:expr, $tolerance:expr)),* $(,)?] $(,)?) => {
+        if let Err(mismatch) = $crate::testing::check_schedule(&$scheduler, &[$(($step, $lr, $tolerance)),*]) {
+            panic!("{}", mismatch);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cosine_annealing_warm_restarts::CosineAnnealingWarmRestarts;
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn render_mismatch() {
+        let scheduler = LinearLR::new(1.0, 1.0, 0.0, 4, 0);
+        let mismatch = check_schedule(&scheduler, &[(4, 0.0, 0.0), (1, 0.75, 0.0), (2, 0.4, 0.05), (5, f64::NAN, 0.0)]).unwrap_err();
+        assert_eq!((mismatch.step, mismatch.actual), (2, 0.5));
+        let expected = "\
+the learning rate at step 2 is 5e-1, expected 4e-1 ± 5e-2
+       step  actual     expected
+          0  1.0000e0
+          1  7.5000e-1  7.5000e-1 ± 0e0
+>         2  5.0000e-1  4.0000e-1 ± 5e-2
+          3  2.5000e-1
+          4  0.0000e0   0.0000e0 ± 0e0
+          5  0.0000e0   NaN ± 0e0
+";
+        assert_eq!(mismatch.to_string(), expected);
+        assert!(check_schedule(&scheduler, &[]).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "the learning rate at step 5 is 1e0")]
+    fn panic_on_mismatch() {
+        expect_schedule!(CosineAnnealingWarmRestarts::new(1.0, 0.0, 4, 1, 0), [(4, 0.0, 1e-12), (5, 0.0, 1e-12)]);
+    }
+}
@@ -0,0 +1,242 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::{ClosedForm, Scheduler};
+
+/// Source of elapsed training time.
+pub trait Clock {
+    /// Returns the time elapsed since training started.
+    fn elapsed(&self) -> Duration;
+}
+
+/// Clock measuring wall-clock time since its construction.
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl SystemClock {
+    /// Constructs a SystemClock instance starting now.
+    pub fn new() -> Self {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for SystemClock {
+    fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Clock advanced by hand, mainly for testing.
+/// 
+/// Clones share the same time, so a clone can be kept to advance the clock owned by a scheduler.
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    nanos: Arc<AtomicU64>,
+}
+
+impl ManualClock {
+    /// Constructs a ManualClock instance at zero elapsed time.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        let nanos = u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        // The closure never returns None, so the update always succeeds.
+        let _ = self.nanos.fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| Some(n.saturating_add(nanos)));
+    }
+}
+
+impl Clock for ManualClock {
+    fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Acquire))
+    }
+}
+
+/// Drives a step-indexed scheduler by elapsed time against a total time budget.
+/// 
+/// The wrapped scheduler is expected to span `total_steps` steps over the whole `budget`.
+/// At every `step` the elapsed fraction of the budget is mapped to the corresponding step of the wrapped scheduler,
+/// which is then advanced to that step. The number of `step` calls itself does not affect the learning rate.
+/// 
+/// A [`ClosedForm`] scheduler can instead be driven continuously with `continuous`, which evaluates the fractional step
+/// and never advances the wrapped scheduler, so a long pause between `step` calls costs nothing.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::linear::LinearLR;
+/// # use lr_schedulers::time_based::{ManualClock, TimeBasedScheduler};
+/// # use lr_schedulers::Scheduler;
+/// # use std::time::Duration;
+/// let clock = ManualClock::new();
+/// let mut scheduler = TimeBasedScheduler::new(
+///     LinearLR::new(1.0, 1.0, 0.0, 100, 0), 100, Duration::from_secs(3600), clock.clone()
+/// );
+/// clock.advance(Duration::from_secs(900));
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_lr(0.01), 0.75);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeBasedScheduler<S, C> {
+    scheduler: S,
+    clock: C,
+    budget: Duration,
+    total_steps: usize,
+    steps: usize,
+    position: f64,
+    continuous: Option<fn(&S, f64) -> f64>,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler, C: Clock> TimeBasedScheduler<S, C> {
+    /// Constructs a TimeBasedScheduler instance.
+    /// 
+    /// The parameter `budget` must be longer than 0. When zero is provided, the wrapped scheduler jumps to `total_steps` at the first `step`.
+    pub fn new(scheduler: S, total_steps: usize, budget: Duration, clock: C) -> Self {
+        TimeBasedScheduler { scheduler, clock, budget, total_steps, steps: 0, position: 0.0, continuous: None, last_lr: None }
+    }
+
+    /// Returns the elapsed fraction of the time budget clamped to `[0, 1]`.
+    pub fn elapsed_fraction(&self) -> f64 {
+        if self.budget.is_zero() {
+            return 1.0;
+        }
+        (self.clock.elapsed().as_secs_f64() / self.budget.as_secs_f64()).min(1.0)
+    }
+
+    /// Returns the time left in the budget.
+    pub fn remaining(&self) -> Duration {
+        self.budget.saturating_sub(self.clock.elapsed())
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: ClosedForm, C: Clock> TimeBasedScheduler<S, C> {
+    /// Constructs a TimeBasedScheduler instance whose learning rate is `scheduler.lr_at_f64(t)`,
+    /// where `t` is the elapsed fraction of the budget at the last `step` times `total_steps`.
+    /// 
+    /// The steps are counted from 0 regardless of the step of `scheduler`.
+    /// 
+    /// ```
+    /// # use lr_schedulers::linear::LinearLR;
+    /// # use lr_schedulers::time_based::{ManualClock, TimeBasedScheduler};
+    /// # use lr_schedulers::Scheduler;
+    /// # use std::time::Duration;
+    /// let clock = ManualClock::new();
+    /// let mut scheduler = TimeBasedScheduler::continuous(
+    ///     LinearLR::new(1.0, 1.0, 0.0, 2, 0), 2, Duration::from_secs(3600), clock.clone()
+    /// );
+    /// clock.advance(Duration::from_secs(900));
+    /// // Note: loss value is not used in this scheduler.
+    /// scheduler.step(0.01);
+    /// assert_eq!(scheduler.get_lr(0.01), 0.75);
+    /// ```
+    pub fn continuous(scheduler: S, total_steps: usize, budget: Duration, clock: C) -> Self {
+        TimeBasedScheduler { continuous: Some(|scheduler: &S, t| scheduler.lr_at_f64(t)), ..TimeBasedScheduler::new(scheduler, total_steps, budget, clock) }
+    }
+}
+
+impl<S: Scheduler, C: Clock> Scheduler for TimeBasedScheduler<S, C> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.position = self.elapsed_fraction() * self.total_steps as f64;
+        let target = self.position.floor() as usize;
+        if self.continuous.is_some() {
+            self.steps = target;
+        }
+        while self.steps < target {
+            self.scheduler.step(loss);
+            self.steps += 1;
+        }
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        match self.continuous {
+            Some(lr_at_f64) => lr_at_f64(&self.scheduler, self.position),
+            None => self.scheduler.get_lr(loss),
+        }
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    /// Returns the number of steps of the wrapped scheduler left as of the last `step`.
+    fn remaining_steps(&self) -> Option<usize> {
+        Some(self.total_steps.saturating_sub(self.steps))
+    }
+
+    /// Returns the elapsed fraction of the time budget.
+    fn progress(&self) -> Option<f64> {
+        Some(self.elapsed_fraction())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use crate::Scheduler;
+    use super::*;
+
+    #[test]
+    fn follow_elapsed_time() {
+        let clock = ManualClock::new();
+        let mut scheduler = TimeBasedScheduler::new(
+            LinearLR::new(1.0, 1.0, 0.0, 4, 0), 4, Duration::from_secs(40), clock.clone()
+        );
+        let elapsed_secs = [0, 5, 10, 25, 30, 60];
+        let expected_lrs = [1.0, 1.0, 0.75, 0.5, 0.25, 0.0];
+        for (i, (secs, exp_lr)) in elapsed_secs.iter().zip(expected_lrs).enumerate() {
+            clock.advance(Duration::from_secs(*secs) - clock.elapsed());
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+            let lr = scheduler.get_lr(0.0);
+            assert_eq!(lr, exp_lr, "Call {}", i);
+        }
+        assert_eq!(scheduler.elapsed_fraction(), 1.0);
+        assert_eq!((scheduler.remaining_steps(), scheduler.progress()), (Some(0), Some(1.0)));
+        assert_eq!(scheduler.remaining(), Duration::ZERO);
+    }
+
+    #[test]
+    fn continuous_time() {
+        let clock = ManualClock::new();
+        let mut scheduler = TimeBasedScheduler::continuous(
+            LinearLR::new(1.0, 1.0, 0.0, 4, 0), 4, Duration::from_secs(40), clock.clone()
+        );
+        let elapsed_secs = [0, 5, 25, 3600 * 24 * 365];
+        let expected_lrs = [1.0, 0.875, 0.375, 0.0];
+        for (i, (secs, exp_lr)) in elapsed_secs.iter().zip(expected_lrs).enumerate() {
+            clock.advance(Duration::from_secs(*secs) - clock.elapsed());
+            // Proceed a step with dummy loss.
+            scheduler.step(0.0);
+            assert_eq!(scheduler.get_lr(0.0), exp_lr, "Call {}", i);
+        }
+        assert_eq!(scheduler.inner().get_lr(0.0), 1.0);
+    }
+
+    #[test]
+    fn zero_budget() {
+        let mut scheduler = TimeBasedScheduler::new(
+            LinearLR::new(1.0, 1.0, 0.0, 4, 0), 4, Duration::ZERO, ManualClock::new()
+        );
+        scheduler.step(0.0);
+        assert_eq!(scheduler.get_lr(0.0), 0.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,124 @@
+use crate::Scheduler;
+
+/// A step taken by [`TrustRatioScheduler`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaledStep {
+    /// Learning rate of the wrapped scheduler.
+    pub base_lr: f64,
+    /// External multiplier, e.g. a layer-wise trust ratio.
+    pub multiplier: f64,
+}
+
+impl ScaledStep {
+    /// Returns the learning rate actually applied, `base_lr * multiplier`.
+    pub fn effective_lr(&self) -> f64 {
+        self.base_lr * self.multiplier
+    }
+}
+
+/// Combines a scheduler with an external per-step multiplier, as in LARS and LAMB.
+/// 
+/// The wrapped scheduler provides the base learning rate, and the optimizer scales it at every step by its trust ratio through `step_scaled`.
+/// The steps are kept in a history, so the effective learning rates can be reproduced after restoring a checkpoint.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::trust_ratio::TrustRatioScheduler;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = TrustRatioScheduler::new(ExponentialLR::new(1.0, 0.5, 0));
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step_scaled(0.01, 0.2);
+/// scheduler.step_scaled(0.01, 0.4);
+/// assert_eq!(scheduler.get_last_lr(), Some(0.2));
+/// assert_eq!(scheduler.get_lr(0.01), 0.25);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrustRatioScheduler<S> {
+    scheduler: S,
+    history: Vec<ScaledStep>,
+}
+
+impl<S: Scheduler> TrustRatioScheduler<S> {
+    /// Constructs a TrustRatioScheduler instance.
+    pub fn new(scheduler: S) -> Self {
+        TrustRatioScheduler { scheduler, history: Vec::new() }
+    }
+
+    /// Constructs a TrustRatioScheduler instance restored from a checkpoint.
+    /// 
+    /// The parameter `scheduler` must be at the step following the last step of `history`.
+    pub fn with_history(scheduler: S, history: Vec<ScaledStep>) -> Self {
+        TrustRatioScheduler { scheduler, history }
+    }
+
+    /// Records the step with the learning rate scaled by `multiplier`, and proceeds the wrapped scheduler.
+    pub fn step_scaled(&mut self, loss: f64, multiplier: f64) {
+        self.history.push(ScaledStep { base_lr: self.scheduler.get_lr(loss), multiplier });
+        self.scheduler.step(loss);
+    }
+
+    /// Returns the learning rate of the current step scaled by `multiplier` without proceeding the step.
+    pub fn scaled_lr(&self, loss: f64, multiplier: f64) -> f64 {
+        self.scheduler.get_lr(loss) * multiplier
+    }
+
+    /// Returns the steps taken so far, in order.
+    pub fn history(&self) -> &[ScaledStep] {
+        &self.history
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> Scheduler for TrustRatioScheduler<S> {
+    /// Proceeds the step with a multiplier of 1.
+    fn step(&mut self, loss: f64) {
+        self.step_scaled(loss, 1.0);
+    }
+
+    /// Returns the base learning rate of the current step.
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    /// Returns the effective learning rate of the most recent step.
+    fn get_last_lr(&self) -> Option<f64> {
+        self.history.last().map(ScaledStep::effective_lr)
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn restore_history() {
+        let mut scheduler = TrustRatioScheduler::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0));
+        let multipliers = [0.5, 2.0, 1.0];
+        for multiplier in multipliers {
+            scheduler.step_scaled(0.0, multiplier);
+        }
+        let effective_lrs: Vec<_> = scheduler.history().iter().map(ScaledStep::effective_lr).collect();
+        assert_eq!(effective_lrs, [0.5, 1.5, 0.5]);
+
+        let restored = TrustRatioScheduler::with_history(LinearLR::new(1.0, 1.0, 0.0, 4, 3), scheduler.history().to_vec());
+        assert_eq!(restored.history(), scheduler.history());
+        assert_eq!(restored.get_lr(0.0), scheduler.get_lr(0.0));
+        assert_eq!(restored.get_last_lr(), Some(0.5));
+        assert_eq!(restored.scaled_lr(0.0, 3.0), 0.75);
+    }
+}
\ No newline at end of file
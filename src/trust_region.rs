@@ -0,0 +1,146 @@
+use crate::Scheduler;
+
+/// An override rejected by [`TrustRegion`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rejection {
+    /// Number of steps processed before the override.
+    pub step: usize,
+    /// Learning rate requested by the override.
+    pub requested: f64,
+    /// Learning rate of the wrapped scheduler.
+    pub scheduled: f64,
+    /// Learning rate applied instead, i.e. the nearest one within the trust region.
+    pub applied: f64,
+}
+
+/// Lets an outer loop override the learning rate of each step within a trust region around the schedule.
+/// 
+/// The trust region of a step is `[(1 - radius) * lr, (1 + radius) * lr]`, where `lr` is the learning rate of the wrapped scheduler.
+/// An override set by `set_override` applies to the current step only, and is clamped to the trust region.
+/// Overrides outside the region are recorded as rejections, and a NaN override falls back to the scheduled learning rate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::trust_region::TrustRegion;
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = TrustRegion::new(ExponentialLR::new(1.0, 0.5, 0), 0.25);
+/// assert_eq!(scheduler.set_override(1.125), 1.125);
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_last_lr(), Some(1.125));
+/// // The override is only for a step.
+/// assert_eq!(scheduler.get_lr(0.01), 0.5);
+/// // An override outside the trust region is clamped.
+/// assert_eq!(scheduler.set_override(2.0), 0.625);
+/// assert_eq!(scheduler.rejections().len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TrustRegion<S> {
+    scheduler: S,
+    radius: f64,
+    step: usize,
+    override_lr: Option<f64>,
+    rejections: Vec<Rejection>,
+    last_lr: Option<f64>,
+}
+
+impl<S: Scheduler> TrustRegion<S> {
+    /// Constructs a TrustRegion instance allowing overrides within `radius` times the scheduled learning rate, e.g. 0.1 for ±10%.
+    /// 
+    /// A negative radius is taken as its absolute value, and NaN as 0.
+    pub fn new(scheduler: S, radius: f64) -> Self {
+        let radius = if radius.is_nan() { 0.0 } else { radius.abs() };
+        TrustRegion { scheduler, radius, step: 0, override_lr: None, rejections: Vec::new(), last_lr: None }
+    }
+
+    /// Overrides the learning rate of the current step, and returns the learning rate applied.
+    /// 
+    /// The scheduled learning rate is taken with a loss of 0, since the override is set before the loss of the step is known.
+    pub fn set_override(&mut self, lr: f64) -> f64 {
+        let scheduled = self.scheduler.get_lr(0.0);
+        let (low, high) = self.region(scheduled);
+        let applied = if lr.is_nan() { scheduled } else { lr.clamp(low, high) };
+        if applied != lr {
+            self.rejections.push(Rejection { step: self.step, requested: lr, scheduled, applied });
+        }
+        self.override_lr = Some(applied);
+        applied
+    }
+
+    /// Removes the override of the current step.
+    pub fn clear_override(&mut self) {
+        self.override_lr = None;
+    }
+
+    /// Returns the range of learning rates allowed at the current step.
+    pub fn trust_region(&self, loss: f64) -> (f64, f64) {
+        self.region(self.scheduler.get_lr(loss))
+    }
+
+    /// Returns the overrides rejected so far, in order.
+    pub fn rejections(&self) -> &[Rejection] {
+        &self.rejections
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+
+    fn region(&self, scheduled: f64) -> (f64, f64) {
+        let (a, b) = ((1.0 - self.radius) * scheduled, (1.0 + self.radius) * scheduled);
+        (a.min(b), a.max(b))
+    }
+}
+
+impl<S: Scheduler> Scheduler for TrustRegion<S> {
+    fn step(&mut self, loss: f64) {
+        self.last_lr = Some(self.get_lr(loss));
+        self.override_lr = None;
+        self.scheduler.step(loss);
+        self.step = self.step.saturating_add(1);
+    }
+
+    /// Returns the override of the current step if any, or the learning rate of the wrapped scheduler.
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.override_lr.unwrap_or_else(|| self.scheduler.get_lr(loss))
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.last_lr
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn clamp_overrides() {
+        let mut scheduler = TrustRegion::new(LinearLR::new(1.0, 1.0, 0.0, 4, 0), -0.5);
+        let overrides = [Some(0.25), None, Some(0.5), Some(f64::NAN)];
+        let expected_lrs = [0.5, 0.75, 0.5, 0.25];
+        for (i, (lr, exp_lr)) in overrides.iter().zip(expected_lrs).enumerate() {
+            if let Some(lr) = lr {
+                scheduler.set_override(*lr);
+            }
+            assert_eq!(scheduler.get_lr(0.0), exp_lr, "Step {}", i);
+            scheduler.step(0.0);
+            assert_eq!(scheduler.get_last_lr(), Some(exp_lr), "Step {}", i);
+        }
+        let rejected: Vec<_> = scheduler.rejections().iter().map(|r| (r.step, r.applied)).collect();
+        assert_eq!(rejected, [(0, 0.5), (3, 0.25)]);
+        assert_eq!(scheduler.trust_region(0.0), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+/// A number of optimizer steps, i.e. batches.
+/// 
+/// Every scheduler of this crate is indexed by steps. The newtypes of this module make the unit of a count explicit
+/// where it is converted, so that a count of epochs or tokens is not passed as steps by mistake.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::units::{Epoch, Step, Tokens};
+/// let warmup = Epoch(2).to_steps(391);
+/// assert_eq!(warmup, Step(782));
+/// assert_eq!(Tokens(1_000_000).to_steps(4096), Step(244));
+/// let init_step: usize = warmup.into();
+/// assert_eq!(init_step, 782);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Step(pub u64);
+
+/// A number of passes over the training data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Epoch(pub u32);
+
+/// A number of training tokens, e.g. for schedules of language models defined by a token budget.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tokens(pub u64);
+
+impl Epoch {
+    /// Converts to the number of steps with `steps_per_epoch`, saturating at `u64::MAX`.
+    pub fn to_steps(self, steps_per_epoch: usize) -> Step {
+        Step(u64::from(self.0).saturating_mul(steps_per_epoch as u64))
+    }
+}
+
+impl Tokens {
+    /// Converts to the number of completed steps of `tokens_per_step` tokens. A `tokens_per_step` of 0 is treated as 1.
+    pub fn to_steps(self, tokens_per_step: u64) -> Step {
+        Step(self.0 / tokens_per_step.max(1))
+    }
+}
+
+impl From<usize> for Step {
+    fn from(step: usize) -> Self {
+        Step(step as u64)
+    }
+}
+
+/// Saturates at `usize::MAX` on targets where `usize` is narrower than 64 bits.
+impl From<Step> for usize {
+    fn from(step: Step) -> Self {
+        usize::try_from(step.0).unwrap_or(usize::MAX)
+    }
+}
+
+impl From<u32> for Epoch {
+    fn from(epoch: u32) -> Self {
+        Epoch(epoch)
+    }
+}
+
+impl From<u64> for Tokens {
+    fn from(tokens: u64) -> Self {
+        Tokens(tokens)
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} steps", self.0)
+    }
+}
+
+impl fmt::Display for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} epochs", self.0)
+    }
+}
+
+impl fmt::Display for Tokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} tokens", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_saturating() {
+        assert_eq!(Epoch(u32::MAX).to_steps(usize::MAX), Step(u64::MAX));
+        assert_eq!(Tokens(10).to_steps(0), Step(10));
+        assert_eq!(Tokens(10).to_steps(3), Step(3));
+        assert_eq!(usize::from(Step::from(5usize)), 5);
+        assert_eq!(Epoch(3).to_string(), "3 epochs");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,51 @@
+use crate::Scheduler;
+
+/// Common interface of schedulers producing a value of type `T` at every step.
+/// 
+/// A learning rate [`Scheduler`] is used as a `ValueScheduler<f64>` through [`AsValue`].
+/// Other hyperparameters, such as the batch size, are scheduled by reusing the same curves.
+pub trait ValueScheduler<T> {
+    /// Proceeds the step of scheduler.
+    fn step(&mut self, loss: f64);
+    /// Returns a value for the current step.
+    fn get_value(&self, loss: f64) -> T;
+}
+
+/// Adapts a learning rate scheduler to a `ValueScheduler<f64>` whose value is the learning rate.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::value::{AsValue, ValueScheduler};
+/// let mut scheduler = AsValue::new(ExponentialLR::new(1.0, 0.5, 0));
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_value(0.01), 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AsValue<S> {
+    scheduler: S,
+}
+
+impl<S: Scheduler> AsValue<S> {
+    /// Constructs an AsValue instance.
+    pub fn new(scheduler: S) -> Self {
+        AsValue { scheduler }
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+}
+
+impl<S: Scheduler> ValueScheduler<f64> for AsValue<S> {
+    fn step(&mut self, loss: f64) {
+        self.scheduler.step(loss);
+    }
+
+    fn get_value(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+}
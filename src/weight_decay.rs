@@ -0,0 +1,137 @@
+use crate::policy::{PolicyOutput, PolicyScheduler};
+use crate::value::ValueScheduler;
+use crate::Scheduler;
+
+/// Relation between the learning rate and the weight decay in [`CoupledWeightDecay`].
+/// 
+/// `lr_0` and `wd_0` denote the learning rate at construction and the given base weight decay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coupling {
+    /// `wd = wd_0 * lr / lr_0`, i.e. the weight decay follows the schedule.
+    Proportional,
+    /// `wd = wd_0 * lr_0 / lr`, i.e. the product of the learning rate and the weight decay is kept constant.
+    Inverse,
+    /// `wd = wd_0 * (lr - min_lr) / (max_lr - min_lr)`, i.e. the weight decay follows the position of the learning rate within its range.
+    Normalized { min_lr: f64, max_lr: f64 },
+}
+
+/// Derives the weight decay from the learning rate of a scheduler.
+/// 
+/// Both values are stepped together, so that they never go out of sync. The learning rate is available through [`Scheduler`],
+/// and the pair `(lr, wd)` through [`ValueScheduler`].
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::exponential::ExponentialLR;
+/// # use lr_schedulers::weight_decay::{CoupledWeightDecay, Coupling};
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = CoupledWeightDecay::new(ExponentialLR::new(1e-3, 0.5, 0), 0.1, Coupling::Proportional);
+/// // Note: loss value is not used in this scheduler.
+/// scheduler.step(0.01);
+/// assert_eq!(scheduler.get_pair(0.01), (5e-4, 0.05));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CoupledWeightDecay<S> {
+    scheduler: S,
+    lr_0: f64,
+    wd_0: f64,
+    coupling: Coupling,
+}
+
+impl<S: Scheduler> CoupledWeightDecay<S> {
+    /// Constructs a CoupledWeightDecay instance.
+    /// 
+    /// The learning rate of `scheduler` at construction is used as `lr_0`.
+    pub fn new(scheduler: S, wd_0: f64, coupling: Coupling) -> Self {
+        let lr_0 = scheduler.get_lr(0.0);
+        CoupledWeightDecay { scheduler, lr_0, wd_0, coupling }
+    }
+
+    /// Returns the weight decay for the current step.
+    pub fn get_wd(&self, loss: f64) -> f64 {
+        self.wd_for(self.scheduler.get_lr(loss))
+    }
+
+    /// Returns the learning rate and the weight decay for the current step.
+    pub fn get_pair(&self, loss: f64) -> (f64, f64) {
+        let lr = self.scheduler.get_lr(loss);
+        (lr, self.wd_for(lr))
+    }
+
+    /// Returns the wrapped scheduler.
+    pub fn inner(&self) -> &S {
+        &self.scheduler
+    }
+
+    fn wd_for(&self, lr: f64) -> f64 {
+        match self.coupling {
+            Coupling::Proportional => self.wd_0 * lr / self.lr_0,
+            Coupling::Inverse => self.wd_0 * self.lr_0 / lr,
+            Coupling::Normalized { min_lr, max_lr } => self.wd_0 * (lr - min_lr) / (max_lr - min_lr),
+        }
+    }
+}
+
+impl<S: Scheduler> Scheduler for CoupledWeightDecay<S> {
+    fn step(&mut self, loss: f64) {
+        self.scheduler.step(loss);
+    }
+
+    fn get_lr(&self, loss: f64) -> f64 {
+        self.scheduler.get_lr(loss)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.scheduler.get_last_lr()
+    }
+
+    fn remaining_steps(&self) -> Option<usize> {
+        self.scheduler.remaining_steps()
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.scheduler.progress()
+    }
+}
+
+impl<S: Scheduler> ValueScheduler<(f64, f64)> for CoupledWeightDecay<S> {
+    fn step(&mut self, loss: f64) {
+        self.scheduler.step(loss);
+    }
+
+    fn get_value(&self, loss: f64) -> (f64, f64) {
+        self.get_pair(loss)
+    }
+}
+
+impl<S: Scheduler> PolicyScheduler for CoupledWeightDecay<S> {
+    fn get_policy(&self, loss: f64) -> PolicyOutput {
+        let (lr, wd) = self.get_pair(loss);
+        PolicyOutput { weight_decay: Some(wd), ..PolicyOutput::lr(lr) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::linear::LinearLR;
+    use super::*;
+
+    #[test]
+    fn couplings() {
+        let cases = [
+            (Coupling::Proportional, [0.1, 0.075, 0.05]),
+            (Coupling::Inverse, [0.1, 0.4 / 3.0, 0.2]),
+            (Coupling::Normalized { min_lr: 0.5, max_lr: 1.0 }, [0.1, 0.05, 0.0]),
+        ];
+        for (coupling, expected_wds) in cases {
+            let mut scheduler = CoupledWeightDecay::new(LinearLR::new(1.0, 1.0, 0.5, 2, 0), 0.1, coupling);
+            for (i, exp_wd) in expected_wds.iter().enumerate() {
+                let (lr, wd) = scheduler.get_value(0.0);
+                assert_eq!(lr, scheduler.get_lr(0.0), "{:?}: Step {}", coupling, i);
+                assert!((wd - exp_wd).abs() < 1e-15, "{:?}: Step {}: left: {}, right: {}", coupling, i, wd, exp_wd);
+                ValueScheduler::step(&mut scheduler, 0.0);
+            }
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,234 @@
+use crate::pbt::Perturbable;
+use crate::{progress_at, Bounded, ClosedForm, PiecewiseMonotone, Scheduler};
+
+const PI: f64 = std::f64::consts::PI;
+
+/// Shape of the decay phase of [`WarmupStableDecay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecayShape {
+    /// Decreases linearly.
+    Linear,
+    /// Decreases along a half cosine.
+    Cosine,
+    /// Decreases as `1 - sqrt(progress)`, which drops quickly at the beginning of the decay.
+    OneMinusSqrt,
+}
+
+impl DecayShape {
+    /// Returns the remaining fraction of the decay at `progress` in `[0, 1]`.
+    fn remaining(self, progress: f64) -> f64 {
+        match self {
+            DecayShape::Linear => 1.0 - progress,
+            DecayShape::Cosine => 0.5 * (1.0 + (PI * progress).cos()),
+            DecayShape::OneMinusSqrt => 1.0 - progress.sqrt(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Decay {
+    from_step: usize,
+    steps: usize,
+    final_lr: f64,
+    shape: DecayShape,
+}
+
+/// Warmup-stable-decay schedule used for LLM pretraining.
+/// 
+/// The learning rate rises linearly from 0 to `peak_lr` over `warmup_steps` steps and stays at `peak_lr`.
+/// Without a decay, the stable phase lasts forever, so training can be extended at will.
+/// `with_decay` adds the decay phase starting at any step, which allows continual pretraining:
+/// resume from a checkpoint of the stable phase at step `s` with a scheduler constructed with `init_step = s`
+/// and a decay from step `s`, and the decay is run again without retraining the stable phase.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use lr_schedulers::wsd::{DecayShape, WarmupStableDecay};
+/// # use lr_schedulers::Scheduler;
+/// let mut scheduler = WarmupStableDecay::new(1.0, 2, 0).with_decay(4, 2, 0.0, DecayShape::Linear);
+/// let mut learning_rates = Vec::new();
+/// for _ in 0 .. 8 {
+///     // Note: loss value is not used in this scheduler.
+///     learning_rates.push(scheduler.get_lr(0.01));
+///     scheduler.step(0.01);
+/// }
+/// assert_eq!(learning_rates, [0.0, 0.5, 1.0, 1.0, 1.0, 0.5, 0.0, 0.0]);
+/// ```
+/// 
+/// Branching a decay from a checkpoint of the stable phase:
+/// 
+/// ```
+/// # use lr_schedulers::wsd::{DecayShape, WarmupStableDecay};
+/// # use lr_schedulers::ClosedForm;
+/// let checkpoint_step = 1000;
+/// let branch = WarmupStableDecay::new(1.0, 100, checkpoint_step).with_decay(checkpoint_step, 100, 0.1, DecayShape::Cosine);
+/// assert_eq!(branch.lr_at(checkpoint_step), 1.0);
+/// assert_eq!(branch.lr_at(checkpoint_step + 100), 0.1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WarmupStableDecay {
+    peak_lr: f64,
+    warmup_steps: usize,
+    decay: Option<Decay>,
+    step: usize,
+}
+
+impl WarmupStableDecay {
+    /// Constructs a WarmupStableDecay instance without a decay phase.
+    /// 
+    /// Starting step can be specified by `init_step`. Use `init_step=0` to train a model from the beginning.
+    pub fn new(peak_lr: f64, warmup_steps: usize, init_step: usize) -> Self {
+        WarmupStableDecay { peak_lr, warmup_steps, decay: None, step: init_step }
+    }
+
+    /// Decays the learning rate to `final_lr` over `decay_steps` steps from step `decay_from_step` along `shape`.
+    /// 
+    /// The decay starts from the learning rate at `decay_from_step`, which is `peak_lr` unless the decay starts during the warmup.
+    /// The learning rate stays at `final_lr` after the decay.
+    pub fn with_decay(self, decay_from_step: usize, decay_steps: usize, final_lr: f64, shape: DecayShape) -> Self {
+        let decay = Decay { from_step: decay_from_step, steps: decay_steps, final_lr, shape };
+        WarmupStableDecay { decay: Some(decay), ..self }
+    }
+
+    /// Returns the first step of the decay phase, or None if there is no decay.
+    pub fn decay_from_step(&self) -> Option<usize> {
+        self.decay.map(|decay| decay.from_step)
+    }
+
+    /// Returns true if the current step is in the stable phase.
+    pub fn is_stable(&self) -> bool {
+        self.step >= self.warmup_steps && self.decay.is_none_or(|decay| self.step < decay.from_step)
+    }
+
+    fn warmup_stable_lr(&self, step: usize) -> f64 {
+        if step >= self.warmup_steps {
+            self.peak_lr
+        } else {
+            self.peak_lr * step as f64 / self.warmup_steps as f64
+        }
+    }
+
+    /// Returns the current step, counted from 0 in the same way as `init_step`.
+    pub(crate) fn current_step(&self) -> usize {
+        self.step
+    }
+}
+
+impl Scheduler for WarmupStableDecay {
+    fn step(&mut self, _loss: f64) {
+        self.step = self.step.saturating_add(1);
+    }
+
+    fn get_lr(&self, _loss: f64) -> f64 {
+        self.lr_at(self.step)
+    }
+
+    fn get_last_lr(&self) -> Option<f64> {
+        self.step.checked_sub(1).map(|step| self.lr_at(step))
+    }
+
+    /// Returns the steps until the end of the decay, or None before `with_decay`, since the stable phase has no end.
+    fn remaining_steps(&self) -> Option<usize> {
+        self.decay.and(self.horizon()).map(|horizon| horizon.saturating_sub(self.step))
+    }
+
+    fn progress(&self) -> Option<f64> {
+        self.decay.and(self.horizon()).map(|horizon| progress_at(self.step, horizon))
+    }
+}
+
+impl ClosedForm for WarmupStableDecay {
+    fn lr_at(&self, step: usize) -> f64 {
+        match self.decay {
+            Some(decay) if step >= decay.from_step => {
+                let elapsed = step - decay.from_step;
+                if elapsed >= decay.steps {
+                    return decay.final_lr;
+                }
+                let start_lr = self.warmup_stable_lr(decay.from_step);
+                let remaining = decay.shape.remaining(elapsed as f64 / decay.steps as f64);
+                (start_lr - decay.final_lr).mul_add(remaining, decay.final_lr)
+            }
+            _ => self.warmup_stable_lr(step),
+        }
+    }
+
+    /// Returns the end of the decay, or the end of the warmup if there is no decay.
+    fn horizon(&self) -> Option<usize> {
+        match self.decay {
+            Some(decay) => Some(decay.from_step.saturating_add(decay.steps)),
+            None => Some(self.warmup_steps),
+        }
+    }
+}
+
+impl PiecewiseMonotone for WarmupStableDecay {
+    fn monotone_pieces(&self) -> Vec<(usize, usize)> {
+        match self.decay {
+            Some(decay) => vec![(0, decay.from_step), (decay.from_step, decay.from_step.saturating_add(decay.steps))],
+            None => vec![(0, self.warmup_steps)],
+        }
+    }
+}
+
+impl Bounded for WarmupStableDecay {
+    fn bounds(&self) -> (f64, f64) {
+        let start = self.lr_at(0);
+        let top = self.decay.map_or(self.peak_lr, |decay| self.warmup_stable_lr(decay.from_step));
+        let end = self.decay.map_or(self.peak_lr, |decay| decay.final_lr);
+        (start.min(top).min(end), start.max(top).max(end))
+    }
+}
+
+impl Perturbable for WarmupStableDecay {
+    fn perturb(&mut self, factor: f64) {
+        self.peak_lr *= factor;
+        if let Some(decay) = &mut self.decay {
+            decay.final_lr *= factor;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::relative_eq;
+    use super::*;
+
+    #[test]
+    fn phases() {
+        let mut scheduler = WarmupStableDecay::new(2.0, 4, 0).with_decay(10, 4, 0.2, DecayShape::Cosine);
+        let expected_lrs = [0.0, 0.5, 1.0, 1.5, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 2.0, 0.2 + 1.8 * 0.5 * (1.0 + (PI / 4.0).cos()), 1.1];
+        for (i, exp_lr) in expected_lrs.iter().enumerate() {
+            let lr = scheduler.get_lr(0.0);
+            assert!(relative_eq!(lr, *exp_lr, epsilon = 1e-12), "Step {}: left: {}, right: {}", i, lr, *exp_lr);
+            assert_eq!(scheduler.is_stable(), (4 .. 10).contains(&i), "Step {}", i);
+            scheduler.step(0.0);
+        }
+        assert_eq!(scheduler.horizon(), Some(14));
+        assert_eq!(scheduler.lr_at(14), 0.2);
+        assert_eq!(scheduler.bounds(), (0.0, 2.0));
+    }
+
+    #[test]
+    fn branch_decays_from_checkpoint() {
+        let stable = WarmupStableDecay::new(1.0, 10, 0);
+        assert_eq!(stable.horizon(), Some(10));
+        for checkpoint in [20, 50] {
+            let mut branch = WarmupStableDecay::new(1.0, 10, checkpoint).with_decay(checkpoint, 4, 0.0, DecayShape::OneMinusSqrt);
+            assert_eq!(branch.get_last_lr(), Some(stable.lr_at(checkpoint - 1)));
+            let expected_lrs = [1.0, 0.5, 1.0 - 0.5f64.sqrt(), 1.0 - 0.75f64.sqrt(), 0.0];
+            for (i, exp_lr) in expected_lrs.iter().enumerate() {
+                assert_eq!(branch.get_lr(0.0), *exp_lr, "Checkpoint {}: Step {}", checkpoint, i);
+                branch.step(0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn decay_during_warmup() {
+        let scheduler = WarmupStableDecay::new(1.0, 4, 0).with_decay(2, 2, 0.0, DecayShape::Linear);
+        assert_eq!((0 .. 5).map(|step| scheduler.lr_at(step)).collect::<Vec<_>>(), [0.0, 0.25, 0.5, 0.25, 0.0]);
+        assert_eq!(scheduler.bounds(), (0.0, 0.5));
+    }
+}
\ No newline at end of file